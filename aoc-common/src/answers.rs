@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use crate::input::{InputError, InputKind};
+use crate::parse_error::{parse_token, ParseError};
+use crate::solution::Part;
+
+/// name of the known answers file, kept in each year's input directory
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// The known answers for a year's puzzles.
+///
+/// The answers file has one answer per line, as `<day> <part> <input> <answer>`, where input is
+/// `real`, or `example` or `exampleN` for the numbered example inputs. Blank lines and lines
+/// starting with `#` are ignored
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, Part, InputKind), String>,
+}
+
+/// parse the input column of the answers file
+fn parse_kind(s: &str) -> Result<InputKind, ParseError> {
+    match s {
+        "real" => Ok(InputKind::Real),
+        "example" => Ok(InputKind::Example(1)),
+        _ => s
+            .strip_prefix("example")
+            .and_then(|n| n.parse::<u8>().ok())
+            .filter(|&n| n > 0)
+            .map(InputKind::Example)
+            .ok_or_else(|| ParseError::new(s, "real, example or exampleN")),
+    }
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut answers = BTreeMap::new();
+        for (idx, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            let located = |e: ParseError, token: &str| e.locate(line, token).on_line(idx + 1);
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() != 4 {
+                return Err(ParseError::new(line, "<day> <part> <input> <answer>").on_line(idx + 1));
+            }
+            let day = parse_token::<u8>(fields[0], "a day").map_err(|e| located(e, fields[0]))?;
+            let part = fields[1].parse::<Part>().map_err(|e| located(e, fields[1]))?;
+            let kind = parse_kind(fields[2]).map_err(|e| located(e, fields[2]))?;
+            if answers.insert((day, part, kind), fields[3].to_string()).is_some() {
+                return Err(located(ParseError::new(fields[3], "one answer per day, part and input"), fields[3]));
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    /// load the answers file at `path`, a missing file has no answers
+    pub fn load(path: &Path) -> Result<Self, InputError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|e| InputError::Parse(path.to_path_buf(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(InputError::Io(path.to_path_buf(), e)),
+        }
+    }
+
+    /// the known answer to `part` of `day` for the `kind` input
+    pub fn get(&self, day: u8, part: Part, kind: InputKind) -> Option<&str> {
+        self.answers.get(&(day, part, kind)).map(String::as_str)
+    }
+
+    /// the kinds of input that `day` has known answers for
+    pub fn kinds(&self, day: u8) -> Vec<InputKind> {
+        let mut kinds: Vec<InputKind> = self.answers.keys().filter(|k| k.0 == day).map(|k| k.2).collect();
+        kinds.sort();
+        kinds.dedup();
+        kinds
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::Answers;
+    use crate::input::InputKind;
+    use crate::solution::Part;
+
+    #[test]
+    fn parse_answers() {
+        let text = "# day part input answer\n1 1 real 69177\n\n5 2 real PGSQBFLDP\n11 1 example 10605\n11 2 example2 7\n11 2 example 2713310158\n";
+        let answers = Answers::parse(text).unwrap();
+        assert_eq!(answers.len(), 5);
+        assert_eq!(answers.get(1, Part::One, InputKind::Real), Some("69177"));
+        assert_eq!(answers.get(5, Part::Two, InputKind::Real), Some("PGSQBFLDP"));
+        assert_eq!(answers.get(11, Part::One, InputKind::Example(1)), Some("10605"));
+        assert_eq!(answers.get(1, Part::Two, InputKind::Real), None);
+        assert_eq!(answers.kinds(11), vec![InputKind::Example(1), InputKind::Example(2)]);
+    }
+
+    #[test]
+    fn invalid_answers_are_errors() {
+        let err = Answers::parse("1 1 real 5\n2 3 real 5\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = Answers::parse("1 1 sample 5\n").unwrap_err();
+        assert_eq!(err.column, 5);
+        assert!(Answers::parse("1 1 real\n").is_err());
+        assert!(Answers::parse("1 1 real 5\n1 1 real 6\n").is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use crate::input::{InputKind, InputLocator};
+use crate::record::{run_parts, Record};
+use crate::solution::{Day, Part};
+
+/// the number of worker threads to use when none is asked for
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Solve both parts of every day in `days` against its `kind` input, with the days shared out
+/// between `threads` worker threads. A day whose input can't be read has failed records without
+/// an input hash.
+///
+/// Panics are caught by `run_parts`, so one day can't stop the others. Returns the records sorted
+/// by day and part
+pub fn run_all(year: u16, days: &[Day], locator: &InputLocator, kind: InputKind, threads: usize) -> Vec<Record> {
+    let next = AtomicUsize::new(0);
+    let records = Mutex::new(vec![]);
+    let source = kind.to_string();
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, days.len().max(1)) {
+            scope.spawn(|| {
+                while let Some(day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let day_records = match locator.read(day.day, kind) {
+                        Ok(text) => run_parts(year, day, &Part::BOTH, &source, &text),
+                        Err(e) => Part::BOTH.iter()
+                            .map(|&part| Record::failed(year, day.day, part, &source, e.to_string()))
+                            .collect(),
+                    };
+                    records.lock().unwrap().extend(day_records);
+                }
+            });
+        }
+    });
+
+    let mut records = records.into_inner().unwrap();
+    records.sort_by_key(|r| (r.day, r.part));
+    records
+}
+
+/// the status column of a record in the summary
+fn status(record: &Record) -> &str {
+    match (record.ok, &record.input_hash) {
+        (true, _) => "ok",
+        (false, None) => "skipped",
+        (false, Some(_)) => "FAIL",
+    }
+}
+
+fn duration(ns: Option<u64>) -> String {
+    ns.map_or_else(|| "-".to_string(), |ns| format!("{:.2?}", Duration::from_nanos(ns)))
+}
+
+/// the number of parts in `records` that failed. Parts skipped because their input couldn't be
+/// read aren't counted
+pub fn failures(records: &[Record]) -> usize {
+    records.iter().filter(|r| status(r) == "FAIL").count()
+}
+
+/// format `records` as a table of answers, timings and errors, one row per part, followed by a
+/// count of each status and the total time spent parsing and solving
+pub fn summary(records: &[Record]) -> String {
+    let header = ["day", "part", "answer", "parse", "solve", "status"];
+    let mut rows: Vec<[String; 6]> = vec![header.map(String::from)];
+    for record in records {
+        let error = record.error.as_deref().and_then(|e| e.lines().next()).unwrap_or("");
+        rows.push([
+            record.day.to_string(),
+            record.part.to_string(),
+            record.answer.clone().unwrap_or_else(|| "-".to_string()),
+            duration(record.parse_ns),
+            duration(record.solve_ns),
+            format!("{} {}", status(record), error),
+        ]);
+    }
+    let mut widths = [0; 6];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut out = String::new();
+    for row in &rows {
+        let cells: Vec<String> = row.iter().zip(widths).map(|(cell, width)| format!("{:<width$}", cell)).collect();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+    let count = |s| records.iter().filter(|r| status(r) == s).count();
+    // the parse time is shared by both parts of a day, so it is only counted once
+    let parse_ns: u64 = records.iter().filter(|r| r.part == 1).filter_map(|r| r.parse_ns).sum();
+    let solve_ns: u64 = records.iter().filter_map(|r| r.solve_ns).sum();
+    out.push_str(&format!(
+        "{} ok, {} failed, {} skipped without input, {} parsing and solving\n",
+        count("ok"),
+        failures(records),
+        count("skipped"),
+        duration(Some(parse_ns + solve_ns))
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::{InputKind, InputLocator};
+    use crate::parse_error::ParseError;
+    use crate::record::Record;
+    use crate::solution::{Day, Solution};
+    use crate::test_util::scratch_dir;
+    use super::{failures, run_all, summary};
+
+    /// counts the lines of its input, and panics solving part 2 of an empty one
+    struct Lines;
+
+    impl Solution for Lines {
+        type Input = usize;
+        type Answer1 = usize;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            Ok(input.lines().count())
+        }
+
+        fn part1(lines: &usize) -> usize {
+            *lines
+        }
+
+        fn part2(lines: &usize) -> usize {
+            assert!(*lines > 0, "no lines");
+            lines * 2
+        }
+    }
+
+    const DAYS: &[Day] = &[
+        Day::new::<Lines>(1, "One"),
+        Day::new::<Lines>(2, "Two"),
+        Day::new::<Lines>(3, "Three"),
+        Day::new::<Lines>(4, "Four"),
+    ];
+
+    #[test]
+    fn run_every_day_on_a_pool() {
+        let dir = scratch_dir("batch");
+        std::fs::write(dir.join("d01-input.txt"), "a\n").unwrap();
+        std::fs::write(dir.join("d02-input.txt"), "a\nb\n").unwrap();
+        std::fs::write(dir.join("d04-input.txt"), "").unwrap();
+        let locator = InputLocator::new(2021, &dir);
+
+        let records = run_all(2021, DAYS, &locator, InputKind::Real, 3);
+        let keys: Vec<(u8, u8)> = records.iter().map(|r| (r.day, r.part)).collect();
+        assert_eq!(keys, vec![(1, 1), (1, 2), (2, 1), (2, 2), (3, 1), (3, 2), (4, 1), (4, 2)]);
+        let answers: Vec<Option<&str>> = records.iter().map(|r| r.answer.as_deref()).collect();
+        assert_eq!(answers, vec![Some("1"), Some("2"), Some("2"), Some("4"), None, None, Some("0"), None]);
+        assert_eq!(records[7].error.as_deref(), Some("panicked: no lines"));
+
+        let table = summary(&records);
+        assert!(table.starts_with("day  part  answer  parse"));
+        assert!(table.contains("skipped no real input for day 3"));
+        assert!(table.contains("FAIL panicked: no lines"));
+        assert!(table.lines().last().unwrap().starts_with("5 ok, 1 failed, 2 skipped without input,"));
+        // the panic fails, but day 3's missing input doesn't
+        assert_eq!(failures(&records), 1);
+
+        let one_thread = run_all(2021, DAYS, &locator, InputKind::Real, 1);
+        let answers_of = |records: &[Record]| records.iter().map(|r| r.answer.clone()).collect::<Vec<_>>();
+        assert_eq!(answers_of(&one_thread), answers_of(&records));
+    }
+
+    #[test]
+    fn no_days() {
+        let dir = scratch_dir("batch-empty");
+        let locator = InputLocator::new(2021, &dir);
+        assert!(run_all(2021, &[], &locator, InputKind::Real, 4).is_empty());
+        assert_eq!(summary(&[]).lines().count(), 2);
+    }
+}
@@ -0,0 +1,30 @@
+use criterion::{black_box, Criterion};
+use crate::input::{InputKind, InputLocator};
+use crate::solution::{Day, Part};
+
+/// Benchmark parsing and each part separately for each of `days` of `year`, with a criterion
+/// group per day named like `day07`. Each day uses its real input, or its first example input if
+/// the real one isn't in the input directory, and is skipped if it has neither
+pub fn bench_days(c: &mut Criterion, days: &[Day], year: u16) {
+    let locator = InputLocator::find(year).unwrap_or_else(|e| panic!("the {} input directory: {}", year, e));
+    for day in days {
+        let input = locator
+            .read(day.day, InputKind::Real)
+            .or_else(|_| locator.read(day.day, InputKind::Example(1)));
+        let input = match input {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("skipping day {}: {}", day.day, e);
+                continue;
+            }
+        };
+        let parsed = day.parse(&input).unwrap_or_else(|e| panic!("day {}: {}", day.day, e.diagnostic(&input)));
+
+        let mut group = c.benchmark_group(format!("day{:02}", day.day));
+        group.bench_function("parse", |b| b.iter(|| day.parse(black_box(&input))));
+        for part in Part::BOTH {
+            group.bench_function(format!("part{}", part), |b| b.iter(|| parsed.solve(black_box(part))));
+        }
+        group.finish();
+    }
+}
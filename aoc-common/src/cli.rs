@@ -0,0 +1,616 @@
+// Command line runner shared by the solution crates of every year
+//
+// usage: <program> [--json] [--example[=N]] <day> [<part> [input-path | -]]
+//        <program> all [--json] [--example[=N]] [--threads=N]
+//        <program> new <day> <title> [example-answer1 [example-answer2]]
+//        <program> fetch <day>
+//        <program> submit <day> <part> [answer]
+//        <program> verify
+//        <program> visualise [--example[=N]] [--format=ansi|png|gif|svg] [--out=PATH] [--scale=N]
+//                            [--delay=MS] <day> [part]
+//        <program> report [--example[=N]] <day>
+//
+// If no input path is given, the day's input is found by the input locator, see
+// `InputLocator::find`. `--example` uses the day's example input instead of the real one.
+// A path of `-` reads the puzzle input from stdin. Inputs compressed with gzip or zstd are
+// decompressed. Both parts are solved if no part is given.
+//
+// `--json` prints one line of JSON per part solved instead of text, see `Record` for its fields.
+// A part that can't be solved is still printed, with `ok` false and the error.
+//
+// `all` solves both parts of every day, sharing the days between a pool of threads (one per CPU
+// unless `--threads` is given). A day that panics or can't be parsed is reported as failed
+// without stopping the others. It prints a table of the answers and timings sorted by day, or
+// every record as JSON with `--json`, and exits with an error if any part failed. Days without an
+// input are skipped: they are listed, but don't count as failures.
+//
+// `new` adds a day to the year's crate, `rust-<year>` in the workspace: a module implementing
+// `Solution` from the template in `aoc-common/templates`, its entry in `lib.rs`, and empty real and
+// example input files. The generated test checks the example answers given, or 0 if none are.
+//
+// `fetch` downloads the day's input into the input directory, unless it is already there. It
+// needs the adventofcode.com session cookie, see `Client::from_env`.
+//
+// `submit` posts an answer, or the day's solution if no answer is given, and records the result
+// in the answers ledger in the input directory. Answers already known to be wrong are not sent.
+//
+// `verify` runs every day against the known answers in the input directory's `answers.txt`, and
+// prints a table of the answers that pass, fail or are missing. It exits with an error if any fail.
+//
+// `visualise` draws the day's puzzle as part 1 (or the given part) is solved, for days that have a
+// visualisation. The frames are played in the terminal `--delay` milliseconds apart, or written as
+// numbered PNG or SVG images to a directory, or as an animated GIF, with each cell `--scale` pixels
+// across. Images go to `dayNN-partN` (or `dayNN-partN.gif`) unless `--out` is given.
+//
+// `report` prints the day's analysis of its input beyond the two answers, for days that have one,
+// such as every reading of the 2022 day 2 strategy guide ranked by score.
+
+use std::fs::File;
+use std::panic;
+use std::io::stdout;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use crate::answers::{Answers, ANSWERS_FILE};
+use crate::batch::{default_threads, failures, run_all, summary};
+use crate::client::{fetch_input, Client, Fetched};
+use crate::input::{read_input, read_text, InputKind, InputLocator};
+use crate::record::{run_parts, Failure, Record};
+use crate::scaffold::{scaffold, NewDay};
+use crate::solution::{Day, Part};
+use crate::submit::{submit_answer, Ledger, LEDGER_FILE};
+use crate::verify::{table, verify, Status};
+use crate::visual::{Format, FrameWriter};
+
+/// the usage message, for a binary called `program`
+fn usage(program: &str) -> String {
+    format!("usage: {0} [--json] [--example[=N]] <day> [<part> [input-path | -]]
+       {0} all [--json] [--example[=N]] [--threads=N]
+       {0} new <day> <title> [example-answer1 [example-answer2]]
+       {0} fetch <day>
+       {0} submit <day> <part> [answer]
+       {0} verify
+       {0} visualise [--example[=N]] [--format=ansi|png|gif|svg] [--out=PATH] [--scale=N] [--delay=MS] <day> [part]
+       {0} report [--example[=N]] <day>", program)
+}
+
+enum Command {
+    Solve(Args),
+    All { kind: InputKind, json: bool, threads: usize },
+    New { day: u8, title: String, answers: [String; 2] },
+    Fetch(u8),
+    Submit { day: u8, part: Part, answer: Option<String> },
+    Verify,
+    Visualise(Show),
+    Report { day: u8, kind: InputKind },
+}
+
+/// the parsed command line arguments
+struct Args {
+    day: u8,
+    /// the part to solve, or both if `None`
+    part: Option<Part>,
+    input: Option<String>,
+    kind: InputKind,
+    json: bool,
+}
+
+/// the options of the `visualise` command
+struct Show {
+    day: u8,
+    part: Part,
+    kind: InputKind,
+    format: Format,
+    out: Option<PathBuf>,
+    /// the pixels across each cell of an image
+    scale: usize,
+    /// the time between frames
+    delay: Duration,
+}
+
+/// the registered solutions of a puzzle year
+struct Year<'a> {
+    year: u16,
+    days: &'a [Day],
+}
+
+impl Year<'_> {
+    fn find_day(&self, day: u8) -> Result<&Day, String> {
+        self.days.iter()
+            .find(|d| d.day == day)
+            .ok_or_else(|| format!("no {} solution for day {}", self.year, day))
+    }
+}
+
+/// parse the arguments into a command. The error is a message to print before the usage, and is
+/// empty when the arguments don't match any command
+fn parse_command(args: &[String]) -> Result<Command, String> {
+    match args {
+        [cmd] if cmd == "verify" => Ok(Command::Verify),
+        [cmd, flags @ ..] if cmd == "all" => parse_all(flags),
+        [cmd, args @ ..] if cmd == "visualise" => parse_visualise(args).map(Command::Visualise),
+        [cmd, args @ ..] if cmd == "report" => parse_report(args),
+        [cmd, day] if cmd == "fetch" => Ok(Command::Fetch(parse_day(day)?)),
+        [cmd, day, title, answers @ ..] if cmd == "new" && answers.len() <= 2 => {
+            let answer = |i: usize| answers.get(i).cloned().unwrap_or_else(|| "0".to_string());
+            Ok(Command::New { day: parse_day(day)?, title: title.clone(), answers: [answer(0), answer(1)] })
+        }
+        [cmd, day, part, answer @ ..] if cmd == "submit" && answer.len() <= 1 => Ok(Command::Submit {
+            day: parse_day(day)?,
+            part: parse_part(part)?,
+            answer: answer.first().cloned(),
+        }),
+        [cmd, ..] if ["new", "fetch", "submit", "verify"].contains(&cmd.as_str()) => Err(String::new()),
+        _ => parse_args(args).map(Command::Solve),
+    }
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    day.parse::<u8>().map_err(|_| format!("invalid day '{}'", day))
+}
+
+fn parse_part(part: &str) -> Result<Part, String> {
+    part.parse::<Part>().map_err(|_| format!("part must be 1 or 2, found '{}'", part))
+}
+
+/// parse an `--example` or `--example=N` flag, `None` if `flag` is some other flag
+fn parse_example(flag: &str) -> Result<Option<InputKind>, String> {
+    match flag.strip_prefix("--example=") {
+        Some(n) => {
+            let n = n.parse::<u8>().map_err(|_| format!("invalid example number '{}'", n))?;
+            Ok(Some(InputKind::Example(n)))
+        }
+        None if flag == "--example" => Ok(Some(InputKind::Example(1))),
+        None => Ok(None),
+    }
+}
+
+fn parse_all(flags: &[String]) -> Result<Command, String> {
+    let mut kind = InputKind::Real;
+    let mut json = false;
+    let mut threads = default_threads();
+    for flag in flags {
+        match parse_example(flag)? {
+            Some(example) => kind = example,
+            None if flag == "--json" => json = true,
+            None => match flag.strip_prefix("--threads=") {
+                Some(n) => threads = n.parse::<usize>().ok().filter(|&n| n > 0)
+                    .ok_or_else(|| format!("invalid thread count '{}'", n))?,
+                None => return Err(format!("unknown option '{}'", flag)),
+            },
+        }
+    }
+    Ok(Command::All { kind, json, threads })
+}
+
+fn parse_visualise(mut args: &[String]) -> Result<Show, String> {
+    let mut kind = InputKind::Real;
+    let mut format = Format::Ansi;
+    let mut out = None;
+    let mut scale = 8;
+    let mut delay = Duration::from_millis(100);
+    while let Some((flag, rest)) = args.split_first().filter(|(flag, _)| flag.starts_with("--")) {
+        let (name, value) = flag.split_once('=').unwrap_or((flag, ""));
+        match (parse_example(flag)?, name) {
+            (Some(example), _) => kind = example,
+            (None, "--format") => format = value.parse::<Format>().map_err(|e| e.to_string())?,
+            (None, "--out") if !value.is_empty() => out = Some(PathBuf::from(value)),
+            (None, "--scale") => scale = value.parse::<usize>().ok().filter(|&n| n > 0)
+                .ok_or_else(|| format!("invalid scale '{}'", value))?,
+            (None, "--delay") => delay = value.parse::<u64>().map(Duration::from_millis)
+                .map_err(|_| format!("invalid delay '{}'", value))?,
+            _ => return Err(format!("unknown option '{}'", flag)),
+        }
+        args = rest;
+    }
+    let (day, part) = match args {
+        [day] => (parse_day(day)?, Part::One),
+        [day, part] => (parse_day(day)?, parse_part(part)?),
+        _ => return Err(String::new()),
+    };
+    Ok(Show { day, part, kind, format, out, scale, delay })
+}
+
+fn parse_report(args: &[String]) -> Result<Command, String> {
+    let (day, flags) = args.split_last().ok_or_else(String::new)?;
+    let mut kind = InputKind::Real;
+    for flag in flags {
+        kind = parse_example(flag)?.ok_or_else(|| format!("unknown option '{}'", flag))?;
+    }
+    Ok(Command::Report { day: parse_day(day)?, kind })
+}
+
+fn parse_args(mut args: &[String]) -> Result<Args, String> {
+    let mut kind = InputKind::Real;
+    let mut json = false;
+    while let Some((flag, rest)) = args.split_first().filter(|(flag, _)| flag.starts_with("--")) {
+        match parse_example(flag)? {
+            Some(example) => kind = example,
+            None if flag == "--json" => json = true,
+            None => return Err(format!("unknown option '{}'", flag)),
+        }
+        args = rest;
+    }
+    let (day, part, input) = match args {
+        [day] => (day, None, None),
+        [day, part] => (day, Some(part), None),
+        [day, part, input] => (day, Some(part), Some(input.clone())),
+        _ => return Err(String::new()),
+    };
+    let day = parse_day(day)?;
+    let part = part.map(|p| parse_part(p)).transpose()?;
+    Ok(Args { day, part, input, kind, json })
+}
+
+/// read the puzzle input from a file, or from stdin if the path is `-`, decompressing it if it is
+/// compressed. Without a path, the day's input of the given kind is located
+fn load_input(year: u16, day: u8, input: Option<&str>, kind: InputKind) -> Result<String, String> {
+    match input {
+        Some("-") => read_text(std::io::stdin().lock())
+            .map_err(|e| format!("could not read input from stdin: {}", e)),
+        Some(path) => File::open(path)
+            .and_then(read_text)
+            .map_err(|e| format!("could not read input file {}: {}", path, e)),
+        None => read_input(year, day, kind).map_err(|e| e.to_string()),
+    }
+}
+
+fn run_command(year: &Year, command: &Command) -> Result<(), String> {
+    match command {
+        Command::Solve(args) => run(year, args),
+        Command::All { kind, json, threads } => run_every_day(year, *kind, *json, *threads),
+        Command::New { day, title, answers } => new_day(year, *day, title, answers),
+        Command::Fetch(day) => fetch(year, *day),
+        Command::Submit { day, part, answer } => submit(year, *day, *part, answer.as_deref()),
+        Command::Verify => verify_all(year),
+        Command::Visualise(show) => visualise(year, show),
+        Command::Report { day, kind } => report(year, *day, *kind),
+    }
+}
+
+fn run(year: &Year, args: &Args) -> Result<(), String> {
+    let day = year.find_day(args.day)?;
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+    let source = args.input.clone().unwrap_or_else(|| args.kind.to_string());
+    let records = match load_input(year.year, args.day, args.input.as_deref(), args.kind) {
+        Ok(text) => run_parts(year.year, day, &parts, &source, &text),
+        Err(e) if args.json => parts.iter()
+            .map(|&part| Record::failed(year.year, day.day, part, &source, e.clone()))
+            .collect(),
+        Err(e) => return Err(e),
+    };
+
+    for record in &records {
+        match (&record.answer, args.json) {
+            (_, true) => println!("{}", record.to_json()),
+            (Some(answer), false) => {
+                println!("day {} part {} ({}): {}", record.day, record.part, day.title, answer);
+                let elapsed = record.parse_ns.unwrap_or(0) + record.solve_ns.unwrap_or(0);
+                println!("time: {:?}", Duration::from_nanos(elapsed));
+            }
+            (None, false) => (),
+        }
+    }
+    match records.iter().find(|r| !r.ok) {
+        Some(_) if args.json => Err(format!("day {} could not be solved", day.day)),
+        Some(record) => Err(failure_message(record)),
+        None => Ok(()),
+    }
+}
+
+/// what went wrong with a failed `record`, worded for its kind of failure
+fn failure_message(record: &Record) -> String {
+    let error = record.error.as_deref().unwrap_or("");
+    match record.failure {
+        Some(Failure::Input) => format!("day {} input could not be read\n{}", record.day, error),
+        Some(Failure::Parse) => format!("day {} input could not be parsed\n{}", record.day, error),
+        Some(Failure::NoAnswer) => format!("day {} part {} has no answer for this input\n{}", record.day, record.part, error),
+        Some(Failure::Panic) | None => format!("day {} part {} failed\n{}", record.day, record.part, error),
+    }
+}
+
+/// solve every day on a pool of `threads` threads, and print their answers
+fn run_every_day(year: &Year, kind: InputKind, json: bool, threads: usize) -> Result<(), String> {
+    let locator = InputLocator::find(year.year).map_err(|e| e.to_string())?;
+    // panics are reported in the records, so the default message would only interleave with them
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let start = Instant::now();
+    let records = run_all(year.year, year.days, &locator, kind, threads);
+    let elapsed = start.elapsed();
+    panic::set_hook(hook);
+
+    if json {
+        records.iter().for_each(|r| println!("{}", r.to_json()));
+    } else {
+        print!("{}", summary(&records));
+        let plural = if threads == 1 { "" } else { "s" };
+        println!("{} days in {:.2?} on {} thread{}", year.days.len(), elapsed, threads, plural);
+    }
+    match failures(&records) {
+        0 => Ok(()),
+        n => Err(format!("{} parts failed", n)),
+    }
+}
+
+/// generate the module for a new day in the year's crate, and its input files
+fn new_day(year: &Year, day: u8, title: &str, answers: &[String; 2]) -> Result<(), String> {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc-common is in the workspace directory")
+        .join(format!("rust-{}", year.year));
+    let locator = InputLocator::find(year.year).map_err(|e| e.to_string())?;
+    let new = NewDay { year: year.year, day, title: title.to_string(), answers: answers.clone() };
+    for path in scaffold(&new, &crate_dir, &locator).map_err(|e| e.to_string())? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+/// download the input for `day`, if it isn't already in the input directory
+fn fetch(year: &Year, day: u8) -> Result<(), String> {
+    let locator = InputLocator::find(year.year).map_err(|e| e.to_string())?;
+    let client = Client::from_env().map_err(|e| e.to_string())?;
+    match fetch_input(&client, &locator, day).map_err(|e| e.to_string())? {
+        Fetched::Cached(path) => println!("day {} input is already in {}", day, path.display()),
+        Fetched::Downloaded(path) => println!("day {} input saved to {}", day, path.display()),
+    }
+    Ok(())
+}
+
+/// submit `answer`, or the solution to the real input if there is no answer
+fn submit(year: &Year, day: u8, part: Part, answer: Option<&str>) -> Result<(), String> {
+    let locator = InputLocator::find(year.year).map_err(|e| e.to_string())?;
+    let answer = match answer {
+        Some(answer) => answer.to_string(),
+        None => {
+            let solution = year.find_day(day)?;
+            let input = locator.read(day, InputKind::Real).map_err(|e| e.to_string())?;
+            solution.solve(&input, part).map_err(|e| e.diagnostic(&input))?
+        }
+    };
+
+    let client = Client::from_env().map_err(|e| e.to_string())?;
+    let mut ledger = Ledger::open(&locator.dir().join(LEDGER_FILE)).map_err(|e| e.to_string())?;
+    let verdict = submit_answer(&client, &mut ledger, year.year, day, part, &answer).map_err(|e| e.to_string())?;
+    println!("day {} part {} answer {}: {}", day, part, answer, verdict.outcome);
+    if let Some(wait) = verdict.wait {
+        println!("wait {}s before submitting again", wait.as_secs());
+    }
+    Ok(())
+}
+
+/// check every day against the known answers
+fn verify_all(year: &Year) -> Result<(), String> {
+    let locator = InputLocator::find(year.year).map_err(|e| e.to_string())?;
+    let answers = Answers::load(&locator.dir().join(ANSWERS_FILE)).map_err(|e| e.to_string())?;
+    let checks = verify(year.days, &locator, &answers);
+    print!("{}", table(&checks));
+    match checks.iter().filter(|c| c.status() == Status::Fail).count() {
+        0 => Ok(()),
+        n => Err(format!("{} answers do not match the known answers", n)),
+    }
+}
+
+/// draw the frames of the day's visualisation to the terminal or to image files
+fn visualise(year: &Year, show: &Show) -> Result<(), String> {
+    let day = year.find_day(show.day)?;
+    let input = load_input(year.year, show.day, None, show.kind)?;
+    let solvable = day.parse(&input).map_err(|e| e.diagnostic(&input))?;
+    let name = format!("day{:02}-part{}", show.day, show.part);
+    let out = match (&show.out, show.format) {
+        (Some(out), _) => out.clone(),
+        (None, Format::Gif) => PathBuf::from(format!("{}.gif", name)),
+        (None, _) => PathBuf::from(name),
+    };
+    let mut frames = match show.format {
+        Format::Ansi => FrameWriter::terminal(stdout(), show.delay),
+        Format::Gif => FrameWriter::gif(&out, show.scale, show.delay),
+        format => FrameWriter::images(&out, format, show.scale),
+    };
+    if !solvable.visualise(show.part, &mut frames) {
+        return Err(format!("day {} has no visualisation", show.day));
+    }
+    let count = frames.finish().map_err(|e| format!("could not write the frames: {}", e))?;
+    if show.format != Format::Ansi {
+        println!("wrote {} frames to {}", count, out.display());
+    }
+    Ok(())
+}
+
+/// print the day's report on its input
+fn report(year: &Year, day: u8, kind: InputKind) -> Result<(), String> {
+    let solution = year.find_day(day)?;
+    let input = load_input(year.year, day, None, kind)?;
+    let solvable = solution.parse(&input).map_err(|e| e.diagnostic(&input))?;
+    let report = solvable.report().ok_or_else(|| format!("day {} has no report", day))?;
+    print!("{}", report);
+    Ok(())
+}
+
+/// run the command given on the command line against the solutions `days` for `year`
+pub fn main(year: u16, days: &[Day]) -> ExitCode {
+    let mut args = std::env::args();
+    let program = args.next()
+        .and_then(|arg| Path::new(&arg).file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "runner".to_string());
+    let args: Vec<String> = args.collect();
+
+    let year = Year { year, days };
+    let command = parse_command(&args);
+    // json output is kept to one record per line
+    let json = matches!(&command, Ok(Command::Solve(Args { json: true, .. }) | Command::All { json: true, .. }));
+    let status = match command {
+        Ok(command) => match run_command(&year, &command) {
+            Ok(()) => 0,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                1
+            }
+        },
+        Err(e) if e.is_empty() => {
+            eprintln!("{}", usage(&program));
+            2
+        }
+        Err(e) => {
+            eprintln!("{}\n{}", e, usage(&program));
+            2
+        }
+    };
+    if !json {
+        println!("exit status: {}", status);
+    }
+    ExitCode::from(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::InputKind;
+    use crate::solution::Part;
+    use std::path::Path;
+    use std::time::Duration;
+    use crate::record::{Failure, Record};
+    use crate::visual::Format;
+    use super::{failure_message, parse_args, parse_command, usage, Command};
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_day_and_part() {
+        let parsed = parse_args(&args("5 2")).unwrap();
+        assert_eq!(parsed.day, 5);
+        assert_eq!(parsed.part, Some(Part::Two));
+        assert!(parsed.input.is_none());
+        assert_eq!(parsed.kind, InputKind::Real);
+        assert!(!parsed.json);
+    }
+
+    #[test]
+    fn parse_day_without_part() {
+        let parsed = parse_args(&args("5")).unwrap();
+        assert_eq!((parsed.day, parsed.part), (5, None));
+    }
+
+    #[test]
+    fn parse_json_flag() {
+        let parsed = parse_args(&args("--json 7 1")).unwrap();
+        assert!(parsed.json);
+        let parsed = parse_args(&args("--example=2 --json 7")).unwrap();
+        assert_eq!((parsed.kind, parsed.json, parsed.part), (InputKind::Example(2), true, None));
+        let parsed = parse_args(&args("--json --example 7 2")).unwrap();
+        assert_eq!((parsed.kind, parsed.json), (InputKind::Example(1), true));
+        assert!(parse_args(&args("--jsonl 7 1")).is_err());
+    }
+
+    #[test]
+    fn parse_example_flag() {
+        assert_eq!(parse_args(&args("--example 7 1")).unwrap().kind, InputKind::Example(1));
+        let parsed = parse_args(&args("--example=3 7 1")).unwrap();
+        assert_eq!((parsed.day, parsed.kind), (7, InputKind::Example(3)));
+        assert!(parse_args(&args("--examples 7 1")).is_err());
+    }
+
+    #[test]
+    fn parse_fetch_command() {
+        assert!(matches!(parse_command(&args("fetch 12")), Ok(Command::Fetch(12))));
+        assert!(matches!(parse_command(&args("7 2")), Ok(Command::Solve(_))));
+        assert!(parse_command(&args("fetch")).is_err());
+        assert!(parse_command(&args("fetch 1 2")).is_err());
+    }
+
+    #[test]
+    fn parse_submit_command() {
+        assert!(matches!(parse_command(&args("submit 4 2 933")),
+            Ok(Command::Submit { day: 4, part: Part::Two, answer: Some(a) }) if a == "933"));
+        assert!(matches!(parse_command(&args("submit 4 1")), Ok(Command::Submit { answer: None, .. })));
+        assert!(parse_command(&args("submit 4 3 933")).is_err());
+        assert!(parse_command(&args("submit 4")).is_err());
+        assert!(parse_command(&args("submit 4 1 2 3")).is_err());
+    }
+
+    #[test]
+    fn parse_verify_command() {
+        assert!(matches!(parse_command(&args("verify")), Ok(Command::Verify)));
+        assert!(parse_command(&args("verify 1")).is_err());
+    }
+
+    #[test]
+    fn parse_all_command() {
+        assert!(matches!(parse_command(&args("all")), Ok(Command::All { kind: InputKind::Real, json: false, .. })));
+        assert!(matches!(parse_command(&args("all --json --threads=3 --example=2")),
+            Ok(Command::All { kind: InputKind::Example(2), json: true, threads: 3 })));
+        assert!(parse_command(&args("all --threads=0")).is_err());
+        assert!(parse_command(&args("all 7")).is_err());
+    }
+
+    #[test]
+    fn parse_visualise_command() {
+        let Ok(Command::Visualise(show)) = parse_command(&args("visualise 9")) else { panic!("not a visualise command") };
+        assert_eq!((show.day, show.part, show.kind, show.format), (9, Part::One, InputKind::Real, Format::Ansi));
+        assert!(show.out.is_none());
+        let parsed = parse_command(&args("visualise --example=2 --format=gif --out=rope.gif --scale=4 --delay=50 9 2"));
+        let Ok(Command::Visualise(show)) = parsed else { panic!("not a visualise command") };
+        assert_eq!((show.part, show.kind, show.format), (Part::Two, InputKind::Example(2), Format::Gif));
+        assert_eq!(show.out.as_deref(), Some(Path::new("rope.gif")));
+        assert_eq!((show.scale, show.delay), (4, Duration::from_millis(50)));
+        assert!(parse_command(&args("visualise --format=bmp 9")).is_err());
+        assert!(parse_command(&args("visualise --scale=0 9")).is_err());
+        assert!(parse_command(&args("visualise")).is_err());
+    }
+
+    #[test]
+    fn parse_report_command() {
+        assert!(matches!(parse_command(&args("report 2")), Ok(Command::Report { day: 2, kind: InputKind::Real })));
+        assert!(matches!(parse_command(&args("report --example 2")),
+                         Ok(Command::Report { day: 2, kind: InputKind::Example(1) })));
+        assert!(parse_command(&args("report --format=gif 2")).is_err());
+        assert!(parse_command(&args("report")).is_err());
+    }
+
+    #[test]
+    fn parse_new_command() {
+        assert!(matches!(parse_command(&args("new 10 Tube")),
+            Ok(Command::New { day: 10, title, answers }) if title == "Tube" && answers == ["0", "0"]));
+        let parsed = parse_command(&["new", "10", "Cathode-Ray Tube", "13140"].map(String::from));
+        assert!(matches!(parsed, Ok(Command::New { title, answers, .. }) if title == "Cathode-Ray Tube" && answers == ["13140", "0"]));
+        assert!(parse_command(&args("new 10")).is_err());
+        assert!(parse_command(&args("new 10 Tube 1 2 3")).is_err());
+    }
+
+    #[test]
+    fn parse_stdin_input() {
+        let parsed = parse_args(&args("11 1 -")).unwrap();
+        assert_eq!(parsed.input.as_deref(), Some("-"));
+    }
+
+    #[test]
+    fn invalid_part_is_an_error() {
+        assert_eq!(parse_args(&args("5 3")).err().unwrap(), "part must be 1 or 2, found '3'");
+        assert_eq!(parse_args(&args("")).err().unwrap(), "");
+        assert_eq!(parse_args(&args("5 1 - x")).err().unwrap(), "");
+    }
+
+    #[test]
+    fn failure_messages_depend_on_the_kind_of_failure() {
+        let mut record = Record::failed(2022, 3, Part::Two, "real", "the error");
+        assert_eq!(failure_message(&record), "day 3 input could not be read\nthe error");
+        record.failure = Some(Failure::Parse);
+        assert_eq!(failure_message(&record), "day 3 input could not be parsed\nthe error");
+        record.failure = Some(Failure::NoAnswer);
+        assert_eq!(failure_message(&record), "day 3 part 2 has no answer for this input\nthe error");
+        record.failure = Some(Failure::Panic);
+        assert_eq!(failure_message(&record), "day 3 part 2 failed\nthe error");
+    }
+
+    #[test]
+    fn usage_names_the_program() {
+        assert!(usage("rust-2020").starts_with("usage: rust-2020 [--json] [--example[=N]]"));
+        assert!(usage("rust-2020").contains("\n       rust-2020 verify\n"));
+        assert!(usage("rust-2020").ends_with("report [--example[=N]] <day>"));
+    }
+}
@@ -0,0 +1,343 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::config::{Config, ConfigError};
+use crate::input::{InputError, InputKind, InputLocator};
+use crate::solution::Part;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// environment variable holding the adventofcode.com session cookie, the `session` config
+/// setting is used if it is not set
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// environment variable overriding the server base URL, the `base_url` config setting is used
+/// if it is not set
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// the least amount of time between two requests to the server
+pub const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = "github.com/strohs/advent-of-code by strohs1@gmail.com";
+
+/// An error talking to the puzzle server
+#[derive(Debug)]
+pub enum ClientError {
+    /// no session token was configured
+    NoSession,
+    /// the server did not accept the session token
+    BadSession,
+    /// the puzzle has not unlocked yet, or does not exist
+    NotAvailable { year: u16, day: u8 },
+    /// the server asked us to slow down, `wait` is how long it asked us to wait, if it said
+    RateLimited { wait: Option<Duration> },
+    /// the server responded with an unexpected status code
+    Status(u16, String),
+    /// the request could not be made or its response could not be read
+    Transport(String),
+    Input(InputError),
+    Config(ConfigError),
+    Io(PathBuf, std::io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::NoSession => write!(
+                f,
+                "no session token, set {} or the 'session' setting in aoc.conf",
+                SESSION_ENV
+            ),
+            ClientError::BadSession => f.write_str("the server did not accept the session token"),
+            ClientError::NotAvailable { year, day } => write!(f, "the puzzle for {} day {} is not available", year, day),
+            ClientError::RateLimited { wait: Some(wait) } => write!(f, "rate limited, try again in {}s", wait.as_secs()),
+            ClientError::RateLimited { wait: None } => f.write_str("rate limited, try again later"),
+            ClientError::Status(code, body) => write!(f, "unexpected response {}: {}", code, body.trim()),
+            ClientError::Transport(e) => write!(f, "request failed: {}", e),
+            ClientError::Input(e) => e.fmt(f),
+            ClientError::Config(e) => e.fmt(f),
+            ClientError::Io(path, e) => write!(f, "could not write {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl Error for ClientError {}
+
+/// Keeps requests to the server at least `interval` apart. The time of the last request is
+/// kept in `state_file`, so separate runs are kept apart too
+#[derive(Debug, Clone)]
+struct Throttle {
+    interval: Duration,
+    state_file: PathBuf,
+}
+
+impl Throttle {
+    /// sleep until `interval` has passed since the last request, then record a new request
+    fn wait(&self) {
+        let now = SystemTime::now();
+        let last = std::fs::read_to_string(&self.state_file)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        if let Some(elapsed) = last.and_then(|last| now.duration_since(last).ok()) {
+            if elapsed < self.interval {
+                std::thread::sleep(self.interval - elapsed);
+            }
+        }
+        let millis = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+        let _ = std::fs::write(&self.state_file, millis.to_string());
+    }
+}
+
+/// A client for the puzzle server
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    agent: ureq::Agent,
+    throttle: Throttle,
+}
+
+impl Client {
+    /// a client for the server at `base_url`, authenticating with the `session` cookie
+    pub fn new(base_url: &str, session: Option<String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent,
+            throttle: Throttle {
+                interval: DEFAULT_REQUEST_INTERVAL,
+                state_file: std::env::temp_dir().join("aoc-last-request"),
+            },
+        }
+    }
+
+    /// a client configured by the `AOC_SESSION` and `AOC_BASE_URL` environment variables, or
+    /// else the `session` and `base_url` settings of the config file
+    pub fn from_env() -> Result<Self, ClientError> {
+        let config = Config::find().map_err(ClientError::Config)?.unwrap_or_default();
+        let setting = |env: &str, key: &str| {
+            std::env::var(env)
+                .ok()
+                .or_else(|| config.get(key).map(String::from))
+                .filter(|value| !value.is_empty())
+        };
+        let base_url = setting(BASE_URL_ENV, "base_url").unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        Ok(Self::new(&base_url, setting(SESSION_ENV, "session")))
+    }
+
+    /// keep requests at least `interval` apart, recording the time of the last request in
+    /// `state_file`
+    pub fn with_request_interval(mut self, interval: Duration, state_file: &Path) -> Self {
+        self.throttle = Throttle { interval, state_file: state_file.to_path_buf() };
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn cookie(&self) -> Result<String, ClientError> {
+        self.session
+            .as_ref()
+            .map(|session| format!("session={}", session))
+            .ok_or(ClientError::NoSession)
+    }
+
+    /// send `request`, with `form` as its body if given, returning the body of a successful
+    /// response
+    fn send(
+        &self,
+        request: ureq::Request,
+        form: Option<&[(&str, &str)]>,
+        year: u16,
+        day: u8,
+    ) -> Result<String, ClientError> {
+        let request = request.set("Cookie", &self.cookie()?);
+        self.throttle.wait();
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+        match response {
+            Ok(response) => response.into_string().map_err(|e| ClientError::Transport(e.to_string())),
+            Err(ureq::Error::Status(code, response)) => {
+                let wait = response
+                    .header("Retry-After")
+                    .and_then(|secs| secs.trim().parse::<u64>().ok())
+                    .map(Duration::from_secs);
+                let body = response.into_string().unwrap_or_default();
+                Err(match code {
+                    400 | 401 | 403 => ClientError::BadSession,
+                    404 => ClientError::NotAvailable { year, day },
+                    429 => ClientError::RateLimited { wait },
+                    _ => ClientError::Status(code, body),
+                })
+            }
+            Err(ureq::Error::Transport(e)) => Err(ClientError::Transport(e.to_string())),
+        }
+    }
+
+    /// download the puzzle input for `day` of `year`
+    pub fn download_input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        if !(1..=25).contains(&day) || SystemTime::now() < unlock_time(year, day) {
+            return Err(ClientError::NotAvailable { year, day });
+        }
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        self.send(self.agent.get(&url), None, year, day)
+    }
+
+    /// post `answer` for `part` of `day`, returning the body of the server's response
+    pub fn post_answer(&self, year: u16, day: u8, part: Part, answer: &str) -> Result<String, ClientError> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let level = part.to_string();
+        self.send(self.agent.post(&url), Some(&[("level", &level), ("answer", answer)]), year, day)
+    }
+}
+
+/// where the input for a day came from
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Fetched {
+    /// the input was already in the input directory
+    Cached(PathBuf),
+    /// the input was downloaded and saved
+    Downloaded(PathBuf),
+}
+
+/// Make sure the real input for `day` is in the input directory, downloading it if it isn't.
+/// An input that is already in the directory is never fetched again, unless it is an empty
+/// placeholder, like the one created by the `new` command
+pub fn fetch_input(client: &Client, locator: &InputLocator, day: u8) -> Result<Fetched, ClientError> {
+    match locator.path(day, InputKind::Real) {
+        Ok(path) if std::fs::metadata(&path).map_or(true, |m| m.len() > 0) => return Ok(Fetched::Cached(path)),
+        Ok(_) | Err(InputError::NotFound { .. }) => (),
+        Err(e) => return Err(ClientError::Input(e)),
+    }
+
+    let input = client.download_input(locator.year(), day)?;
+    if input.trim().is_empty() {
+        return Err(ClientError::Status(200, "the input was empty".to_string()));
+    }
+
+    // write to a temporary file first, so an interrupted write is never mistaken for an input
+    let path = locator.cache_path(day);
+    let partial = path.with_extension("partial");
+    std::fs::write(&partial, &input)
+        .and_then(|_| std::fs::rename(&partial, &path))
+        .map_err(|e| ClientError::Io(path.clone(), e))?;
+    Ok(Fetched::Downloaded(path))
+}
+
+/// the time the puzzle for `day` of `year` unlocks, midnight US Eastern time (UTC-5) on the
+/// day of December
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    // days from 1970-01-01 to December `day` of `year`, see http://howardhinnant.github.io/date_algorithms.html
+    let (y, m, d) = (year as i64, 12_i64, day as i64);
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * (m - 3) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+    let secs = days * 86_400 + 5 * 3_600;
+    UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+    use crate::client::{fetch_input, unlock_time, Client, ClientError, Fetched};
+    use crate::input::InputLocator;
+    use crate::test_util::{scratch_dir, TestServer};
+
+    fn test_client(server: &TestServer, name: &str) -> Client {
+        let state = scratch_dir(name).join("last-request");
+        Client::new(&server.base_url, Some("abc123".to_string())).with_request_interval(Duration::ZERO, &state)
+    }
+
+    #[test]
+    fn unlock_at_midnight_eastern() {
+        // 2022-12-01T05:00:00Z
+        assert_eq!(unlock_time(2022, 1), UNIX_EPOCH + Duration::from_secs(1_669_870_800));
+    }
+
+    #[test]
+    fn download_and_cache_input() {
+        let server = TestServer::ok("1000\n2000\n", 1);
+        let client = test_client(&server, "fetch-client");
+        let locator = InputLocator::new(2022, scratch_dir("fetch"));
+
+        let fetched = fetch_input(&client, &locator, 1).unwrap();
+        let path = locator.dir().join("d01-input.txt");
+        assert_eq!(fetched, Fetched::Downloaded(path.clone()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2022/day/1/input");
+        assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+
+        // a cached input is never fetched again
+        assert_eq!(fetch_input(&client, &locator, 1).unwrap(), Fetched::Cached(path));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn empty_placeholder_is_replaced() {
+        let server = TestServer::ok("1000\n", 1);
+        let client = test_client(&server, "placeholder-client");
+        let locator = InputLocator::new(2020, scratch_dir("placeholder"));
+        let path = locator.dir().join("05-input.txt");
+        std::fs::write(&path, "").unwrap();
+
+        assert_eq!(fetch_input(&client, &locator, 5).unwrap(), Fetched::Downloaded(path.clone()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1000\n");
+    }
+
+    #[test]
+    fn rate_limit_and_errors() {
+        let server = TestServer::start(vec![
+            (429, vec![("Retry-After", "60".to_string())], String::new()),
+            (400, vec![], "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_string()),
+            (500, vec![], "oops".to_string()),
+        ]);
+        let client = test_client(&server, "errors-client");
+        let locator = InputLocator::new(2022, scratch_dir("errors"));
+
+        let err = fetch_input(&client, &locator, 2).unwrap_err();
+        assert!(matches!(err, ClientError::RateLimited { wait: Some(wait) } if wait == Duration::from_secs(60)));
+        assert!(matches!(fetch_input(&client, &locator, 2), Err(ClientError::BadSession)));
+        assert!(matches!(fetch_input(&client, &locator, 2), Err(ClientError::Status(500, _))));
+        assert!(locator.path(2, crate::input::InputKind::Real).is_err());
+    }
+
+    #[test]
+    fn no_request_without_session_or_for_locked_days() {
+        let server = TestServer::ok("", 0);
+        let locator = InputLocator::new(2022, scratch_dir("locked"));
+        let client = Client::new(&server.base_url, None);
+        assert!(matches!(fetch_input(&client, &locator, 3), Err(ClientError::NoSession)));
+
+        let client = test_client(&server, "locked-client");
+        assert!(matches!(client.download_input(2022, 26), Err(ClientError::NotAvailable { .. })));
+        assert!(matches!(client.download_input(9999, 1), Err(ClientError::NotAvailable { .. })));
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn requests_are_spaced_out() {
+        let server = TestServer::ok("1\n", 2);
+        let state = scratch_dir("throttle").join("last-request");
+        let client = Client::new(&server.base_url, Some("abc123".to_string()))
+            .with_request_interval(Duration::from_millis(200), &state);
+        let start = std::time::Instant::now();
+        client.download_input(2022, 1).unwrap();
+        client.download_input(2022, 1).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
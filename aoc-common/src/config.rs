@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use crate::parse_error::ParseError;
+
+/// name of the optional config file
+pub const CONFIG_FILE: &str = "aoc.conf";
+
+/// environment variable that can point at a config file, overriding the search for `aoc.conf`
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+
+/// Settings read from an `aoc.conf` file.
+///
+/// The file holds one `key = value` setting per line. Blank lines and lines starting with `#`
+/// are ignored
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// directory of the config file, relative paths in the config are resolved against it
+    dir: PathBuf,
+    values: HashMap<String, String>,
+}
+
+/// An error loading a config file
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, ParseError),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "could not read config file {}: {}", path.display(), e),
+            ConfigError::Parse(path, e) => write!(f, "invalid config file {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+impl Config {
+    /// parse the text of a config file that lives in `dir`
+    pub fn parse(text: &str, dir: impl Into<PathBuf>) -> Result<Self, ParseError> {
+        let mut values = HashMap::new();
+        for (idx, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let (key, value) = trimmed
+                .split_once('=')
+                .ok_or_else(|| ParseError::new(trimmed, "a 'key = value' setting").locate(line, trimmed).on_line(idx + 1))?;
+            values.insert(key.trim().to_string(), value.trim().to_string());
+        }
+        Ok(Self { dir: dir.into(), values })
+    }
+
+    /// load the config file at `path`
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Self::parse(&text, dir).map_err(|e| ConfigError::Parse(path.to_path_buf(), e))
+    }
+
+    /// find and load the config file named by `AOC_CONFIG`, or else the first `aoc.conf` in
+    /// the search directories. Returns `None` if there is no config file
+    pub fn find() -> Result<Option<Self>, ConfigError> {
+        if let Ok(path) = std::env::var(CONFIG_ENV) {
+            return Self::load(Path::new(&path)).map(Some);
+        }
+        search_dirs()
+            .iter()
+            .map(|dir| dir.join(CONFIG_FILE))
+            .find(|path| path.is_file())
+            .map(|path| Self::load(&path))
+            .transpose()
+    }
+
+    /// returns the value of the setting `key`
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    /// returns the setting `key` as a path, relative paths are resolved against the directory
+    /// the config file is in
+    pub fn path(&self, key: &str) -> Option<PathBuf> {
+        self.get(key).map(|value| self.dir.join(value))
+    }
+}
+
+/// the directories searched for input and config files, in order: the current directory and its
+/// ancestors, then this crate's directory and its ancestors. Searching from the crate directory
+/// means tests find their inputs whatever directory they are run from
+pub fn search_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = vec![];
+    let starts = [std::env::current_dir().ok(), Some(PathBuf::from(env!("CARGO_MANIFEST_DIR")))];
+    for start in starts.into_iter().flatten() {
+        for dir in start.ancestors() {
+            if !dirs.iter().any(|d| d == dir) {
+                dirs.push(dir.to_path_buf());
+            }
+        }
+    }
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use crate::config::Config;
+
+    #[test]
+    fn parse_settings() {
+        let text = "# inputs\ninput_dir = ../inputs\n\n  session=abc123  \n";
+        let config = Config::parse(text, "/aoc").unwrap();
+        assert_eq!(config.get("session"), Some("abc123"));
+        assert_eq!(config.path("input_dir"), Some(PathBuf::from("/aoc/../inputs")));
+        assert!(config.get("base_url").is_none());
+    }
+
+    #[test]
+    fn setting_without_equals_is_an_error() {
+        let err = Config::parse("a = 1\n  session\n", "").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+}
@@ -0,0 +1,261 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point on the integer plane, `x` increases to the east and `y` to the north
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default, Ord, PartialOrd)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A displacement between two `Point2`s
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default, Ord, PartialOrd)]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// a compass direction, north is towards +y
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// a direction relative to a heading
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Relative {
+    Ahead,
+    Right,
+    Back,
+    Left,
+}
+
+/// the number of quarter turns in `degrees`, if it is a multiple of 90
+pub fn quarter_turns(degrees: i64) -> Option<i32> {
+    (degrees % 90 == 0).then(|| (degrees / 90).rem_euclid(4) as i32)
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// the number of orthogonal steps to `other`
+    pub fn manhattan(&self, other: Point2) -> i64 {
+        (other - *self).manhattan_len()
+    }
+
+    /// the number of king's moves (orthogonal or diagonal steps) to `other`
+    pub fn chebyshev(&self, other: Point2) -> i64 {
+        (other - *self).chebyshev_len()
+    }
+
+    /// the square of the straight line distance to `other`
+    pub fn distance_squared(&self, other: Point2) -> i64 {
+        (other - *self).length_squared()
+    }
+
+    /// this point rotated about `pivot` by `quarter_turns` quarter turns, counter-clockwise when
+    /// positive and clockwise when negative
+    pub fn rotate_about(&self, pivot: Point2, quarter_turns: i32) -> Point2 {
+        pivot + (*self - pivot).rotate(quarter_turns)
+    }
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_len(&self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev_len(&self) -> i64 {
+        self.x.abs().max(self.y.abs())
+    }
+
+    pub fn length_squared(&self) -> i64 {
+        self.x * self.x + self.y * self.y
+    }
+
+    /// the vector with each component reduced to -1, 0 or 1. This is the single step, orthogonal
+    /// or diagonal, that moves most directly along this vector
+    pub fn signum(&self) -> Vec2 {
+        Vec2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// this vector rotated by `quarter_turns` quarter turns, counter-clockwise when positive and
+    /// clockwise when negative
+    pub fn rotate(&self, quarter_turns: i32) -> Vec2 {
+        match quarter_turns.rem_euclid(4) {
+            0 => *self,
+            1 => Vec2::new(-self.y, self.x),
+            2 => Vec2::new(-self.x, -self.y),
+            _ => Vec2::new(self.y, -self.x),
+        }
+    }
+}
+
+impl Direction {
+    /// the compass directions, clockwise from north
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    /// the unit vector pointing in this direction
+    pub fn vec(&self) -> Vec2 {
+        match self {
+            Direction::North => Vec2::new(0, 1),
+            Direction::East => Vec2::new(1, 0),
+            Direction::South => Vec2::new(0, -1),
+            Direction::West => Vec2::new(-1, 0),
+        }
+    }
+
+    /// the direction reached by turning `quarter_turns` quarter turns, counter-clockwise when
+    /// positive and clockwise when negative
+    pub fn rotate(&self, quarter_turns: i32) -> Direction {
+        let idx = Direction::ALL.iter().position(|d| d == self).unwrap_or_default() as i32;
+        Direction::ALL[(idx - quarter_turns).rem_euclid(4) as usize]
+    }
+
+    /// the direction that is `relative` to this one
+    pub fn turn(&self, relative: Relative) -> Direction {
+        match relative {
+            Relative::Ahead => *self,
+            Relative::Right => self.rotate(-1),
+            Relative::Back => self.rotate(2),
+            Relative::Left => self.rotate(1),
+        }
+    }
+}
+
+impl Add<Vec2> for Point2 {
+    type Output = Point2;
+
+    fn add(self, v: Vec2) -> Point2 {
+        Point2::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl AddAssign<Vec2> for Point2 {
+    fn add_assign(&mut self, v: Vec2) {
+        *self = *self + v;
+    }
+}
+
+impl Sub<Vec2> for Point2 {
+    type Output = Point2;
+
+    fn sub(self, v: Vec2) -> Point2 {
+        Point2::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl SubAssign<Vec2> for Point2 {
+    fn sub_assign(&mut self, v: Vec2) {
+        *self = *self - v;
+    }
+}
+
+/// the vector from `other` to this point
+impl Sub for Point2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Point2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, v: Vec2) -> Vec2 {
+        Vec2::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, v: Vec2) {
+        *self = *self + v;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, v: Vec2) -> Vec2 {
+        Vec2::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl Mul<i64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, n: i64) -> Vec2 {
+        Vec2::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::{quarter_turns, Direction, Point2, Relative, Vec2};
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point2::new(1, -2), Point2::new(-3, 5));
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(a.distance_squared(b), 65);
+        assert_eq!(b - a, Vec2::new(-4, 7));
+        assert_eq!((b - a).signum(), Vec2::new(-1, 1));
+        assert_eq!(a + (b - a) * 2, Point2::new(-7, 12));
+    }
+
+    #[test]
+    fn rotations_are_exact() {
+        let v = Vec2::new(10, 4);
+        assert_eq!(v.rotate(1), Vec2::new(-4, 10));
+        assert_eq!(v.rotate(2), -v);
+        assert_eq!(v.rotate(-1), Vec2::new(4, -10));
+        assert_eq!(v.rotate(3), v.rotate(-1));
+        assert_eq!(v.rotate(4), v);
+        assert_eq!(Point2::new(180, 42).rotate_about(Point2::new(170, 38), -1), Point2::new(174, 28));
+        assert_eq!(Point2::new(10, 0).rotate_about(Point2::ORIGIN, 1), Point2::new(0, 10));
+        let big = Point2::new(123_456_789, -987_654_321);
+        assert_eq!(big.rotate_about(Point2::new(7, 7), 1).rotate_about(Point2::new(7, 7), -1), big);
+    }
+
+    #[test]
+    fn turn_directions() {
+        assert_eq!(Direction::North.turn(Relative::Right), Direction::East);
+        assert_eq!(Direction::North.turn(Relative::Left), Direction::West);
+        assert_eq!(Direction::East.turn(Relative::Back), Direction::West);
+        assert_eq!(Direction::South.turn(Relative::Ahead), Direction::South);
+        assert_eq!(Direction::West.rotate(-3), Direction::South);
+        for d in Direction::ALL {
+            assert_eq!(d.rotate(1).vec(), d.vec().rotate(1));
+        }
+    }
+
+    #[test]
+    fn degrees_to_quarter_turns() {
+        assert_eq!(quarter_turns(90), Some(1));
+        assert_eq!(quarter_turns(-90), Some(3));
+        assert_eq!(quarter_turns(450), Some(1));
+        assert_eq!(quarter_turns(45), None);
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+use crate::parse_error::ParseError;
+
+/// a position in a grid, as (row, column)
+pub type Pos = (usize, usize);
+
+/// the (row, column) steps to the four orthogonal neighbours, clockwise from up
+pub const DIRECTIONS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// the (row, column) steps to all eight neighbours, clockwise from up
+pub const DIRECTIONS8: [(isize, isize); 8] = [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)];
+
+/// A rectangular grid of cells, stored row by row
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// a grid made from equal length `rows`.
+    /// Panics if the rows are not all the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|row| row.len() == width), "grid rows must all have the same length");
+        let height = rows.len();
+        Self { cells: rows.into_iter().flatten().collect(), width, height }
+    }
+
+    /// Parse a character map into a grid, with one row per line and one cell per character.
+    /// `cell` parses a single character; errors are positioned at that character
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Result<T, ParseError>) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut width = 0;
+        let mut height = 0;
+        for (idx, line) in input.lines().enumerate() {
+            for (i, c) in line.char_indices() {
+                cells.push(cell(c).map_err(|e| e.locate(line, &line[i..]).on_line(idx + 1))?);
+            }
+            let len = line.chars().count();
+            if idx == 0 {
+                width = len;
+            }
+            if len == 0 || len != width {
+                return Err(ParseError::new(line, format!("a row of {} cells", width.max(1))).on_line(idx + 1));
+            }
+            height += 1;
+        }
+        if height == 0 {
+            return Err(ParseError::new(input, "a grid of at least one row"));
+        }
+        Ok(Self { cells, width, height })
+    }
+
+    /// the number of columns
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// the number of rows
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (row, col): Pos) -> Option<&T> {
+        (row < self.height && col < self.width).then(|| &self.cells[row * self.width + col])
+    }
+
+    pub fn get_mut(&mut self, (row, col): Pos) -> Option<&mut T> {
+        (row < self.height && col < self.width).then(|| &mut self.cells[row * self.width + col])
+    }
+
+    /// get the cell at `row`, `col` of the grid repeated endlessly in every direction.
+    /// Panics if the grid is empty
+    pub fn get_wrapping(&self, row: isize, col: isize) -> &T {
+        let row = row.rem_euclid(self.height as isize) as usize;
+        let col = col.rem_euclid(self.width as isize) as usize;
+        &self.cells[row * self.width + col]
+    }
+
+    /// the position one `step` from `pos`, if it is within the grid
+    pub fn offset(&self, (row, col): Pos, (dr, dc): (isize, isize)) -> Option<Pos> {
+        let row = row.checked_add_signed(dr).filter(|&r| r < self.height)?;
+        let col = col.checked_add_signed(dc).filter(|&c| c < self.width)?;
+        Some((row, col))
+    }
+
+    /// the positions of the up to four orthogonal neighbours of `pos`
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS4.into_iter().filter_map(move |step| self.offset(pos, step))
+    }
+
+    /// the positions of the up to eight neighbours of `pos`, including diagonals
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS8.into_iter().filter_map(move |step| self.offset(pos, step))
+    }
+
+    /// the positions seen looking from `pos` in the direction `step`, nearest first, up to the
+    /// edge of the grid. `pos` itself is not included
+    pub fn ray(&self, pos: Pos, step: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.offset(pos, step), move |&p| self.offset(p, step))
+    }
+
+    /// the cells of `row`. Panics if the row is out of bounds
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// the cells of `col`, from top to bottom. Panics if the column is out of bounds
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {} is outside a grid of width {}", col, self.width);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    /// all positions in the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i / width, i % width))
+    }
+
+    /// all cells, row by row
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// a grid of the same size with `f` applied to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { cells: self.cells.iter().map(f).collect(), width: self.width, height: self.height }
+    }
+}
+
+impl<T: TryFrom<char, Error = ParseError>> FromStr for Grid<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, T::try_from)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): Pos) -> &T {
+        assert!(row < self.height && col < self.width, "({}, {}) is outside the grid", row, col);
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (row, col): Pos) -> &mut T {
+        assert!(row < self.height && col < self.width, "({}, {}) is outside the grid", row, col);
+        &mut self.cells[row * self.width + col]
+    }
+}
+
+/// writes one line per row, with no separators between cells
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{Grid, DIRECTIONS8, Pos};
+    use crate::parse_error::ParseError;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(input, |c| c.to_digit(10).ok_or_else(|| ParseError::new(&c.to_string(), "a digit")))
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = digits("1234\n5678\n9012\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+        assert_eq!(grid[(1, 2)], 7);
+        assert_eq!(grid.get((2, 4)), None);
+        assert_eq!(grid.row(2), &[9, 0, 1, 2]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<u32>>(), vec![2, 6, 0]);
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.to_string(), "1234\n5678\n9012");
+    }
+
+    #[test]
+    fn invalid_grids_are_errors() {
+        let err = digits("123\n1x3\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+        let err = digits("123\n12\n").unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (2, "a row of 3 cells"));
+        assert!(digits("").is_err());
+        assert!(digits("12\n\n12").is_err());
+    }
+
+    #[test]
+    fn wrapping_access() {
+        let grid = digits("12\n34\n").unwrap();
+        assert_eq!(*grid.get_wrapping(0, 2), 1);
+        assert_eq!(*grid.get_wrapping(5, 7), 4);
+        assert_eq!(*grid.get_wrapping(-1, -1), 4);
+    }
+
+    #[test]
+    fn neighbours_at_corners_edges_and_middle() {
+        let grid = Grid::from_rows(vec![vec![0; 4]; 3]);
+        let count8 = |pos: Pos| grid.neighbours8(pos).count();
+        assert_eq!([count8((0, 0)), count8((0, 3)), count8((2, 0)), count8((2, 3))], [3; 4]);
+        assert_eq!([count8((0, 1)), count8((1, 0)), count8((1, 3)), count8((2, 1))], [5; 4]);
+        assert_eq!(count8((1, 1)), 8);
+        let mut around: Vec<Pos> = grid.neighbours8((2, 1)).collect();
+        around.sort();
+        assert_eq!(around, vec![(1, 0), (1, 1), (1, 2), (2, 0), (2, 2)]);
+
+        assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<Pos>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+    }
+
+    #[test]
+    fn cast_rays() {
+        let grid = Grid::from_rows(vec![vec![0; 4]; 4]);
+        let ray = |pos, step| grid.ray(pos, step).collect::<Vec<Pos>>();
+        assert_eq!(ray((1, 1), (0, 1)), vec![(1, 2), (1, 3)]);
+        assert_eq!(ray((1, 1), (-1, -1)), vec![(0, 0)]);
+        assert_eq!(ray((1, 2), (1, -1)), vec![(2, 1), (3, 0)]);
+        assert_eq!(ray((0, 0), (-1, 0)), vec![]);
+        let seen: usize = DIRECTIONS8.iter().map(|&step| grid.ray((1, 1), step).count()).sum();
+        assert_eq!(seen, 1 + 1 + 2 + 2 + 2 + 1 + 1 + 1);
+    }
+}
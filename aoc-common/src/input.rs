@@ -0,0 +1,349 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use crate::config::{search_dirs, Config, ConfigError};
+use crate::parse_error::ParseError;
+
+/// environment variable naming the input directory for any year. `AOC_INPUT_DIR_<year>` takes
+/// precedence over it
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+/// which input file to use for a day
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum InputKind {
+    /// the full puzzle input
+    Real,
+    /// an example input from the puzzle description, numbered from 1
+    Example(u8),
+}
+
+/// An error finding or reading a puzzle input file
+#[derive(Debug)]
+pub enum InputError {
+    /// no input directory was found for `year`, `searched` holds the directories tried
+    NoInputDir { year: u16, searched: Vec<PathBuf> },
+    /// the input directory has no file for the day, `tried` holds the file names tried
+    NotFound { dir: PathBuf, day: u8, kind: InputKind, tried: Vec<String> },
+    Io(PathBuf, std::io::Error),
+    /// a file in the input directory could not be parsed
+    Parse(PathBuf, ParseError),
+    Config(ConfigError),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NoInputDir { year, searched } => {
+                write!(f, "no input directory found for {}, searched:", year)?;
+                for dir in searched {
+                    write!(f, "\n    {}", dir.display())?;
+                }
+                Ok(())
+            }
+            InputError::NotFound { dir, day, kind, tried } => write!(
+                f,
+                "no {} input for day {} in {}, tried: {}",
+                kind,
+                day,
+                dir.display(),
+                tried.join(", ")
+            ),
+            InputError::Io(path, e) => write!(f, "could not read input file {}: {}", path.display(), e),
+            InputError::Parse(path, e) => write!(f, "could not parse {}: {}", path.display(), e),
+            InputError::Config(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// An error reading a puzzle input from a reader, such as stdin
+#[derive(Debug)]
+pub enum ReadError {
+    Io(std::io::Error),
+    Parse(ParseError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "could not read input: {}", e),
+            ReadError::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for ReadError {}
+
+/// the extensions of compressed input files, tried after each uncompressed file name
+const COMPRESSED_EXTENSIONS: [&str; 2] = ["gz", "zst"];
+
+/// the first bytes of gzip data
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+
+/// the first bytes of a zstd frame
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// read all of `reader` as UTF-8 text. Input that starts like gzip or zstd data is decompressed,
+/// whatever it is called
+pub fn read_text(mut reader: impl Read) -> std::io::Result<String> {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+    let mut text = String::new();
+    if bytes.starts_with(GZIP_MAGIC) {
+        flate2::read::MultiGzDecoder::new(bytes.as_slice()).read_to_string(&mut text)?;
+    } else if bytes.starts_with(ZSTD_MAGIC) {
+        zstd::Decoder::new(bytes.as_slice())?.read_to_string(&mut text)?;
+    } else {
+        text = String::from_utf8(bytes).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    }
+    Ok(text)
+}
+
+impl Display for InputKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputKind::Real => f.write_str("real"),
+            InputKind::Example(n) => write!(f, "example {}", n),
+        }
+    }
+}
+
+/// Finds the input files for a single year
+#[derive(Debug, Clone)]
+pub struct InputLocator {
+    year: u16,
+    dir: PathBuf,
+}
+
+impl InputLocator {
+    /// a locator for the inputs of `year` that are in `dir`
+    pub fn new(year: u16, dir: impl Into<PathBuf>) -> Self {
+        Self { year, dir: dir.into() }
+    }
+
+    /// Find the input directory for `year`. The first of these is used:
+    /// - the `AOC_INPUT_DIR_<year>` or `AOC_INPUT_DIR` environment variable
+    /// - the `input_dir.<year>` or `input_dir` setting of the `aoc.conf` config file
+    /// - an `input-<year>` or `rust-<year>/input` directory within the current directory, the
+    ///   crate directory, or any of their ancestors
+    pub fn find(year: u16) -> Result<Self, InputError> {
+        let config = Config::find().map_err(InputError::Config)?;
+        Self::resolve(year, |key| std::env::var(key).ok(), config.as_ref(), &search_dirs())
+    }
+
+    fn resolve(
+        year: u16,
+        env: impl Fn(&str) -> Option<String>,
+        config: Option<&Config>,
+        search: &[PathBuf],
+    ) -> Result<Self, InputError> {
+        let year_key = |key: &str| format!("{}_{}", key, year);
+        let configured = env(&year_key(INPUT_DIR_ENV))
+            .or_else(|| env(INPUT_DIR_ENV))
+            .map(PathBuf::from)
+            .or_else(|| config.and_then(|c| c.path(&format!("input_dir.{}", year)).or_else(|| c.path("input_dir"))));
+        if let Some(dir) = configured {
+            return if dir.is_dir() {
+                Ok(Self::new(year, dir))
+            } else {
+                Err(InputError::NoInputDir { year, searched: vec![dir] })
+            };
+        }
+
+        let conventions = [format!("input-{}", year), format!("rust-{}/input", year)];
+        let candidates: Vec<PathBuf> = search
+            .iter()
+            .flat_map(|dir| conventions.iter().map(move |c| dir.join(c)))
+            .collect();
+        match candidates.iter().find(|dir| dir.is_dir()) {
+            Some(dir) => Ok(Self::new(year, dir)),
+            None => Err(InputError::NoInputDir { year, searched: candidates }),
+        }
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// the directory holding this year's input files
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// the file names that can hold the `kind` input for `day`, in the order they are tried.
+    /// Real inputs are named like `d07-input.txt` or `07-input.txt`; example inputs like
+    /// `d07-test-input.txt`, `d10-test.txt`, `11-ex1.txt` or `07-input-test3.txt`
+    pub fn file_names(day: u8, kind: InputKind) -> Vec<String> {
+        match kind {
+            InputKind::Real => vec![format!("d{:02}-input.txt", day), format!("{:02}-input.txt", day)],
+            InputKind::Example(n) => {
+                let mut names = vec![];
+                if n == 1 {
+                    names.push(format!("d{:02}-test-input.txt", day));
+                    names.push(format!("d{:02}-test.txt", day));
+                }
+                names.push(format!("d{:02}-test{}.txt", day, n));
+                names.push(format!("{:02}-ex{}.txt", day, n));
+                names.push(format!("{:02}-input-test{}.txt", day, n));
+                names
+            }
+        }
+    }
+
+    /// the path of the `kind` input file for `day`. Each name is also tried compressed, as
+    /// `d07-input.txt.gz` or `d07-input.txt.zst`
+    pub fn path(&self, day: u8, kind: InputKind) -> Result<PathBuf, InputError> {
+        let tried = Self::file_names(day, kind);
+        tried
+            .iter()
+            .flat_map(|name| {
+                let compressed = COMPRESSED_EXTENSIONS.iter().map(move |ext| format!("{}.{}", name, ext));
+                std::iter::once(name.clone()).chain(compressed)
+            })
+            .map(|name| self.dir.join(name))
+            .find(|path| path.is_file())
+            .ok_or_else(|| InputError::NotFound { dir: self.dir.clone(), day, kind, tried })
+    }
+
+    /// the path that the real input for `day` is, or should be, stored at. New inputs are named
+    /// like the inputs already in the directory, `07-input.txt` or else `d07-input.txt`
+    pub fn cache_path(&self, day: u8) -> PathBuf {
+        if let Ok(path) = self.path(day, InputKind::Real) {
+            return path;
+        }
+        let undecorated = std::fs::read_dir(&self.dir)
+            .map(|entries| {
+                entries.flatten().any(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    name.len() == 12 && name.ends_with("-input.txt") && name[..2].chars().all(|c| c.is_ascii_digit())
+                })
+            })
+            .unwrap_or(false);
+        let names = Self::file_names(day, InputKind::Real);
+        self.dir.join(if undecorated { &names[1] } else { &names[0] })
+    }
+
+    /// read the `kind` input file for `day`, decompressing it if it is compressed
+    pub fn read(&self, day: u8, kind: InputKind) -> Result<String, InputError> {
+        let path = self.path(day, kind)?;
+        File::open(&path).and_then(read_text).map_err(|e| InputError::Io(path, e))
+    }
+}
+
+/// the path of the `kind` input for `day` of `year`
+pub fn input_path(year: u16, day: u8, kind: InputKind) -> Result<PathBuf, InputError> {
+    InputLocator::find(year)?.path(day, kind)
+}
+
+/// read the `kind` input for `day` of `year`
+pub fn read_input(year: u16, day: u8, kind: InputKind) -> Result<String, InputError> {
+    InputLocator::find(year)?.read(day, kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::path::PathBuf;
+    use crate::config::Config;
+    use crate::input::{read_text, InputError, InputKind, InputLocator};
+    use crate::test_util::scratch_dir;
+
+    #[test]
+    fn env_var_takes_precedence() {
+        let root = scratch_dir("env");
+        std::fs::create_dir_all(root.join("from-env")).unwrap();
+        std::fs::create_dir_all(root.join("input-2022")).unwrap();
+        let env_dir = root.join("from-env").to_string_lossy().to_string();
+        let env = |key: &str| (key == "AOC_INPUT_DIR_2022").then(|| env_dir.clone());
+
+        let locator = InputLocator::resolve(2022, env, None, std::slice::from_ref(&root)).unwrap();
+        assert_eq!(locator.dir(), root.join("from-env"));
+    }
+
+    #[test]
+    fn config_file_takes_precedence_over_conventions() {
+        let root = scratch_dir("config");
+        std::fs::create_dir_all(root.join("inputs/2020")).unwrap();
+        std::fs::create_dir_all(root.join("rust-2020/input")).unwrap();
+        let config = Config::parse("input_dir.2020 = inputs/2020", &root).unwrap();
+
+        let locator = InputLocator::resolve(2020, |_| None, Some(&config), std::slice::from_ref(&root)).unwrap();
+        assert_eq!(locator.dir(), root.join("inputs/2020"));
+    }
+
+    #[test]
+    fn missing_configured_dir_is_an_error() {
+        let env = |key: &str| (key == "AOC_INPUT_DIR").then(|| "/no/such/dir".to_string());
+        let err = InputLocator::resolve(2022, env, None, &[]).unwrap_err();
+        assert!(matches!(err, InputError::NoInputDir { year: 2022, .. }));
+    }
+
+    #[test]
+    fn find_conventional_dirs_from_any_ancestor() {
+        let root = scratch_dir("conventions");
+        std::fs::create_dir_all(root.join("rust-2020/input")).unwrap();
+        std::fs::create_dir_all(root.join("input-2022")).unwrap();
+        let search: Vec<PathBuf> = root.join("rust-2022/src").ancestors().map(PathBuf::from).collect();
+
+        let y2020 = InputLocator::resolve(2020, |_| None, None, &search).unwrap();
+        assert_eq!(y2020.dir(), root.join("rust-2020/input"));
+        let y2022 = InputLocator::resolve(2022, |_| None, None, &search).unwrap();
+        assert_eq!(y2022.dir(), root.join("input-2022"));
+        assert!(InputLocator::resolve(2021, |_| None, None, &search).is_err());
+    }
+
+    #[test]
+    fn cache_path_follows_existing_names() {
+        let root = scratch_dir("cache-path");
+        let locator = InputLocator::new(2022, &root);
+        assert_eq!(locator.cache_path(3), root.join("d03-input.txt"));
+        std::fs::write(root.join("01-input.txt"), "1").unwrap();
+        assert_eq!(locator.cache_path(3), root.join("03-input.txt"));
+        assert_eq!(locator.cache_path(1), root.join("01-input.txt"));
+    }
+
+    #[test]
+    fn locate_real_and_example_inputs() {
+        let locator = InputLocator::find(2022).unwrap();
+        assert!(locator.path(7, InputKind::Real).unwrap().ends_with("d07-input.txt"));
+        assert!(locator.path(7, InputKind::Example(1)).unwrap().ends_with("d07-test-input.txt"));
+        assert!(locator.path(10, InputKind::Example(1)).unwrap().ends_with("d10-test.txt"));
+        assert!(locator.path(7, InputKind::Example(2)).unwrap().ends_with("d07-test2.txt"));
+        assert!(matches!(locator.path(7, InputKind::Example(3)), Err(InputError::NotFound { day: 7, .. })));
+
+        let locator = InputLocator::find(2020).unwrap();
+        assert!(locator.path(11, InputKind::Example(1)).unwrap().ends_with("11-ex1.txt"));
+        assert!(locator.path(7, InputKind::Example(3)).unwrap().ends_with("07-input-test3.txt"));
+        assert!(locator.path(14, InputKind::Real).unwrap().ends_with("14-input.txt"));
+    }
+
+    fn gzip(text: &str) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn read_plain_and_compressed_text() {
+        assert_eq!(read_text("1000\n2000\n".as_bytes()).unwrap(), "1000\n2000\n");
+        assert_eq!(read_text(gzip("1000\n2000\n").as_slice()).unwrap(), "1000\n2000\n");
+        let zstd = zstd::encode_all("1000\n2000\n".as_bytes(), 0).unwrap();
+        assert_eq!(read_text(zstd.as_slice()).unwrap(), "1000\n2000\n");
+        assert_eq!(read_text([0xff, 0xfe].as_slice()).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+        assert!(read_text(&gzip("1000\n")[..8]).is_err());
+    }
+
+    #[test]
+    fn locate_compressed_inputs() {
+        let root = scratch_dir("compressed");
+        let locator = InputLocator::new(2022, &root);
+        std::fs::write(root.join("d01-input.txt.gz"), gzip("1000\n")).unwrap();
+        assert_eq!(locator.path(1, InputKind::Real).unwrap(), root.join("d01-input.txt.gz"));
+        assert_eq!(locator.read(1, InputKind::Real).unwrap(), "1000\n");
+        std::fs::write(root.join("d01-input.txt"), "2000\n").unwrap();
+        assert_eq!(locator.read(1, InputKind::Real).unwrap(), "2000\n");
+    }
+}
@@ -0,0 +1,372 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::RangeInclusive;
+
+/// A set of integers, kept as sorted, disjoint inclusive ranges. Ranges that overlap or touch
+/// are merged, so two sets holding the same integers are equal
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    /// the first and last integer of each range, in order, with gaps between them
+    ranges: Vec<(i64, i64)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add the integers of `range`, merging it with the ranges it overlaps or touches. An empty
+    /// range adds nothing
+    pub fn insert(&mut self, range: RangeInclusive<i64>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        // the ranges from `first` up to `last` overlap or touch the new range
+        let first = self.ranges.partition_point(|&(_, e)| e < start.saturating_sub(1));
+        let last = self.ranges.partition_point(|&(s, _)| s <= end.saturating_add(1));
+        if first < last {
+            start = start.min(self.ranges[first].0);
+            end = end.max(self.ranges[last - 1].1);
+        }
+        self.ranges.splice(first..last, [(start, end)]);
+    }
+
+    /// the ranges of the set, in order
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<i64>> + '_ {
+        self.ranges.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// the number of integers in the set, at most `u64::MAX`
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().map(|&(start, end)| end.abs_diff(start).saturating_add(1)).fold(0, u64::saturating_add)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, n: i64) -> bool {
+        let i = self.ranges.partition_point(|&(_, end)| end < n);
+        self.ranges.get(i).is_some_and(|&(start, _)| start <= n)
+    }
+
+    /// true if every integer of `other` is in this set
+    pub fn is_superset(&self, other: &IntervalSet) -> bool {
+        other.difference(self).is_empty()
+    }
+
+    /// the integers in either set
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.ranges().chain(other.ranges()).collect()
+    }
+
+    /// the integers in both sets
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&(s1, e1)), Some(&(s2, e2))) = (self.ranges.get(i), other.ranges.get(j)) {
+            let (start, end) = (s1.max(s2), e1.min(e2));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            // the range that ends first can't overlap anything else in the other set
+            if e1 < e2 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// the integers in this set that are not in `other`
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = vec![];
+        let mut j = 0;
+        for &(start, end) in &self.ranges {
+            // what is left of the range, from `from`, or nothing once the other set covers its end
+            let mut from = Some(start);
+            while let (Some(s), Some(&(os, oe))) = (from, other.ranges.get(j)) {
+                if os > end {
+                    break;
+                }
+                if oe >= s {
+                    if os > s {
+                        ranges.push((s, os - 1));
+                    }
+                    from = oe.checked_add(1).filter(|&next| next <= end);
+                }
+                if oe > end {
+                    break;
+                }
+                j += 1;
+            }
+            if let Some(s) = from {
+                ranges.push((s, end));
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// the integers that are in `k` or more of `ranges`, counting each range once. A `k` of 0 is
+    /// taken as 1
+    pub fn covered_by(ranges: impl IntoIterator<Item = RangeInclusive<i64>>, k: usize) -> IntervalSet {
+        // each range starts counting at its start, and stops after its end
+        let mut events: Vec<(i128, i64)> = ranges.into_iter()
+            .filter(|range| !range.is_empty())
+            .flat_map(|range| [(*range.start() as i128, 1), (*range.end() as i128 + 1, -1)])
+            .collect();
+        events.sort_unstable();
+
+        let k = k.max(1) as i64;
+        let mut ranges = vec![];
+        let mut count = 0;
+        let mut covered_from = None;
+        for chunk in events.chunk_by(|a, b| a.0 == b.0) {
+            let at = chunk[0].0;
+            count += chunk.iter().map(|&(_, change)| change).sum::<i64>();
+            match covered_from {
+                None if count >= k => covered_from = Some(at as i64),
+                Some(from) if count < k => {
+                    ranges.push((from, (at - 1) as i64));
+                    covered_from = None;
+                }
+                _ => (),
+            }
+        }
+        // the events are merged at each position, so the covered ranges never touch
+        IntervalSet { ranges }
+    }
+}
+
+impl From<RangeInclusive<i64>> for IntervalSet {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl FromIterator<RangeInclusive<i64>> for IntervalSet {
+    /// the set of the integers in any of the ranges, sorted and merged in one pass
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<i64>>>(ranges: I) -> Self {
+        let mut sorted: Vec<(i64, i64)> = ranges.into_iter()
+            .filter(|range| !range.is_empty())
+            .map(RangeInclusive::into_inner)
+            .collect();
+        sorted.sort_unstable();
+        let mut merged: Vec<(i64, i64)> = Vec::with_capacity(sorted.len());
+        for (start, end) in sorted {
+            match merged.last_mut() {
+                Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        IntervalSet { ranges: merged }
+    }
+}
+
+/// An interval tree over a fixed list of inclusive ranges, which finds the ranges holding a
+/// point or overlapping a range without comparing against every one. Each range is known by its
+/// position in the list it was built from.
+///
+/// The ranges are sorted by start and kept in an array, read as a balanced binary tree where
+/// the middle of each slice is the root of the slice, and each root also knows the last end of
+/// any range under it
+#[derive(Debug, Clone)]
+pub struct IntervalIndex {
+    /// the start, end and position in the list of each range, sorted by start
+    ranges: Vec<(i64, i64, usize)>,
+    /// the greatest end in the subtree rooted at the same index
+    max_end: Vec<i64>,
+}
+
+impl IntervalIndex {
+    /// index `ranges`. Empty ranges are kept, but never found
+    pub fn new(ranges: impl IntoIterator<Item = RangeInclusive<i64>>) -> Self {
+        let mut sorted: Vec<(i64, i64, usize)> = ranges.into_iter()
+            .enumerate()
+            .map(|(id, range)| (*range.start(), *range.end(), id))
+            .collect();
+        sorted.sort_unstable();
+        let mut index = Self { max_end: vec![i64::MIN; sorted.len()], ranges: sorted };
+        index.build(0, index.ranges.len());
+        index
+    }
+
+    /// fill in `max_end` for the subtree of `ranges[lo..hi]`, returning its greatest end
+    fn build(&mut self, lo: usize, hi: usize) -> i64 {
+        if lo >= hi {
+            return i64::MIN;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let max_end = self.ranges[mid].1.max(self.build(lo, mid)).max(self.build(mid + 1, hi));
+        self.max_end[mid] = max_end;
+        max_end
+    }
+
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// the positions of the ranges holding `n`, in order
+    pub fn stab(&self, n: i64) -> Vec<usize> {
+        self.overlapping(n..=n)
+    }
+
+    /// the positions of the ranges sharing any integer with `range`, in order
+    pub fn overlapping(&self, range: RangeInclusive<i64>) -> Vec<usize> {
+        let mut found = vec![];
+        if !range.is_empty() {
+            self.search(0, self.ranges.len(), *range.start(), *range.end(), &mut found);
+        }
+        found.sort_unstable();
+        found
+    }
+
+    fn search(&self, lo: usize, hi: usize, start: i64, end: i64, found: &mut Vec<usize>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        // nothing in this subtree reaches the range
+        if self.max_end[mid] < start {
+            return;
+        }
+        self.search(lo, mid, start, end, found);
+        let (s, e, id) = self.ranges[mid];
+        // this range, and every range after it, starts after the end of the range
+        if s > end {
+            return;
+        }
+        if s <= e && start <= e {
+            found.push(id);
+        }
+        self.search(mid + 1, hi, start, end, found);
+    }
+
+    /// every pair of ranges that share an integer, as their positions with the smaller first,
+    /// in order. Found by sweeping through the ranges by start, keeping the ranges that haven't
+    /// ended yet
+    pub fn overlapping_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = vec![];
+        // the ranges the sweep is inside of, soonest ending first
+        let mut open: BinaryHeap<Reverse<(i64, usize)>> = BinaryHeap::new();
+        for &(start, end, id) in self.ranges.iter().filter(|&&(s, e, _)| s <= e) {
+            while open.peek().is_some_and(|&Reverse((open_end, _))| open_end < start) {
+                open.pop();
+            }
+            pairs.extend(open.iter().map(|&Reverse((_, other))| (other.min(id), other.max(id))));
+            open.push(Reverse((end, id)));
+        }
+        pairs.sort_unstable();
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeInclusive;
+    use crate::interval::{IntervalIndex, IntervalSet};
+
+    fn set(ranges: &[(i64, i64)]) -> IntervalSet {
+        ranges.iter().map(|&(start, end)| start..=end).collect()
+    }
+
+    #[test]
+    fn merge_overlapping_and_touching_ranges() {
+        let mut set = IntervalSet::new();
+        set.insert(10..=12);
+        set.insert(1..=3);
+        set.insert(5..=6);
+        set.insert(4..=4);
+        // a range that ends before it starts is empty
+        let (start, end) = (20, 19);
+        set.insert(start..=end);
+        assert_eq!(set.ranges().collect::<Vec<_>>(), [1..=6, 10..=12]);
+        set.insert(0..=11);
+        assert_eq!(set.ranges().collect::<Vec<_>>(), [0..=12]);
+        assert_eq!(set.len(), 13);
+        assert_eq!(set, [5..=12, 0..=4, 7..=8].into_iter().collect());
+        assert!(set.contains(12) && !set.contains(13) && !set.contains(-1));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(1, 5), (10, 15)]);
+        let b = set(&[(4, 11), (15, 20)]);
+        assert_eq!(a.union(&b), set(&[(1, 20)]));
+        assert_eq!(a.intersection(&b), set(&[(4, 5), (10, 11), (15, 15)]));
+        assert_eq!(a.difference(&b), set(&[(1, 3), (12, 14)]));
+        assert_eq!(b.difference(&a), set(&[(6, 9), (16, 20)]));
+        assert!(a.is_superset(&set(&[(2, 3), (11, 12)])));
+        assert!(!a.is_superset(&b));
+        assert!(a.is_superset(&IntervalSet::new()));
+        assert!(set(&[(1, 3)]).difference(&set(&[(0, 4)])).is_empty());
+    }
+
+    #[test]
+    fn extreme_values() {
+        let all = IntervalSet::from(i64::MIN..=i64::MAX);
+        assert_eq!(all.len(), u64::MAX);
+        assert_eq!(all.difference(&IntervalSet::from(0..=i64::MAX)), IntervalSet::from(i64::MIN..=-1));
+        assert!(IntervalSet::from(i64::MAX - 1..=i64::MAX).difference(&all).is_empty());
+        assert_eq!(IntervalSet::covered_by([i64::MAX..=i64::MAX], 1), IntervalSet::from(i64::MAX..=i64::MAX));
+    }
+
+    #[test]
+    fn sections_covered_by_k_ranges() {
+        let ranges = [2..=4, 6..=8, 2..=8, 3..=7, 6..=6, 4..=8];
+        assert_eq!(IntervalSet::covered_by(ranges.clone(), 1), set(&[(2, 8)]));
+        assert_eq!(IntervalSet::covered_by(ranges.clone(), 3), set(&[(3, 8)]));
+        assert_eq!(IntervalSet::covered_by(ranges.clone(), 4), set(&[(4, 4), (6, 7)]));
+        assert_eq!(IntervalSet::covered_by(ranges.clone(), 5), set(&[(6, 6)]));
+        assert!(IntervalSet::covered_by(ranges, 6).is_empty());
+        // ranges that end and start at the same section don't split what they cover
+        assert_eq!(IntervalSet::covered_by([1..=2, 3..=4], 1), set(&[(1, 4)]));
+    }
+
+    /// ranges to index, the last of them empty
+    fn indexed() -> Vec<RangeInclusive<i64>> {
+        let (start, end) = (8, 2);
+        vec![2..=4, 6..=8, 2..=3, 4..=5, 5..=7, 7..=9, 20..=30, start..=end]
+    }
+
+    #[test]
+    fn find_ranges_in_the_index() {
+        let index = IntervalIndex::new(indexed());
+        assert_eq!(index.len(), 8);
+        assert_eq!(index.stab(4), [0, 3]);
+        assert_eq!(index.stab(7), [1, 4, 5]);
+        assert!(index.stab(12).is_empty());
+        assert_eq!(index.overlapping(9..=25), [5, 6]);
+        assert_eq!(index.overlapping(i64::MIN..=2), [0, 2]);
+        // every range, except the empty one, overlaps everything
+        assert_eq!(index.overlapping(i64::MIN..=i64::MAX), [0, 1, 2, 3, 4, 5, 6]);
+        let (start, end) = (5, 4);
+        assert!(index.overlapping(start..=end).is_empty());
+        assert!(IntervalIndex::new([]).stab(0).is_empty());
+    }
+
+    #[test]
+    fn find_every_overlapping_pair() {
+        let ranges = indexed();
+        let pairs = IntervalIndex::new(ranges.clone()).overlapping_pairs();
+        let mut expected = vec![];
+        for i in 0..ranges.len() {
+            for j in i + 1..ranges.len() {
+                let (a, b) = (&ranges[i], &ranges[j]);
+                if !a.is_empty() && !b.is_empty() && a.start() <= b.end() && b.start() <= a.end() {
+                    expected.push((i, j));
+                }
+            }
+        }
+        assert_eq!(pairs, expected);
+        assert_eq!(pairs, [(0, 2), (0, 3), (1, 4), (1, 5), (3, 4), (4, 5)]);
+    }
+}
@@ -0,0 +1,26 @@
+//! Code shared by the solutions of every year: finding, downloading and reading puzzle inputs,
+//! scanning them with positioned parse errors, grids, geometry, sets and trees of intervals, the
+//! `Solution` trait, checking and submitting answers, generating new days, drawing frames of a
+//! puzzle's state, benchmarking every day (with the `bench` feature), and the command line runner
+
+pub mod answers;
+pub mod batch;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod cli;
+pub mod client;
+pub mod config;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod parse_error;
+pub mod record;
+pub mod scaffold;
+pub mod scan;
+pub mod solution;
+pub mod submit;
+pub mod verify;
+pub mod visual;
+#[cfg(test)]
+mod test_util;
@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// An error found while parsing puzzle input.
+///
+/// Records where the offending text was found, the text itself, and a description of what
+/// was expected in its place.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    /// 1-based line number of the offending text, 0 if the line is not known
+    pub line: usize,
+    /// 1-based column (in chars) where the offending text starts
+    pub column: usize,
+    /// the text that could not be parsed, empty if the input ended early
+    pub text: String,
+    /// what was expected instead of `text`
+    pub expected: String,
+}
+
+impl ParseError {
+    /// create an error for `text`, positioned at the first column of `text`
+    pub fn new(text: &str, expected: impl Into<String>) -> Self {
+        Self {
+            line: 0,
+            column: 1,
+            text: text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// set the line number this error occurred on
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// move this error's column, which is relative to `inner`, so that it is relative to
+    /// `outer` instead. `inner` should be a slice of `outer`
+    pub fn locate(mut self, outer: &str, inner: &str) -> Self {
+        let outer_start = outer.as_ptr() as usize;
+        let inner_start = inner.as_ptr() as usize;
+        if inner_start >= outer_start && inner_start <= outer_start + outer.len() {
+            let offset = inner_start - outer_start;
+            self.column += outer[..offset].chars().count();
+        }
+        self
+    }
+
+    /// formats this error followed by the offending line of `input`, with the offending
+    /// text underlined
+    pub fn diagnostic(&self, input: &str) -> String {
+        let mut diag = self.to_string();
+        if let Some(src) = self.line.checked_sub(1).and_then(|i| input.lines().nth(i)) {
+            let width = self.text.chars().count().max(1);
+            diag.push_str(&format!("\n    {}\n    {}{}", src, " ".repeat(self.column - 1), "^".repeat(width)));
+        }
+        diag
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.line > 0 {
+            write!(f, "line {}, column {}: ", self.line, self.column)?;
+        } else {
+            write!(f, "column {}: ", self.column)?;
+        }
+        if self.text.is_empty() {
+            write!(f, "expected {}, found nothing", self.expected)
+        } else {
+            write!(f, "expected {}, found '{}'", self.expected, self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// parse `token` into a `T`, returning a `ParseError` positioned at the start of `token`
+pub fn parse_token<T: FromStr>(token: &str, expected: &str) -> Result<T, ParseError> {
+    token.parse::<T>().map_err(|_| ParseError::new(token, expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_error::{parse_token, ParseError};
+
+    #[test]
+    fn locate_token_within_line() {
+        let line = "22-65,x-66";
+        let token = &line[6..7];
+        let err = ParseError::new(token, "a section id").locate(line, token).on_line(3);
+        assert_eq!(err.line, 3);
+        assert_eq!(err.column, 7);
+        assert_eq!(err.to_string(), "line 3, column 7: expected a section id, found 'x'");
+    }
+
+    #[test]
+    fn locate_is_relative_to_inner_slice() {
+        let line = "move 1 from 99999999999999999999999 to 2";
+        let inner = &line[12..];
+        let token = &inner[..23];
+        let err = parse_token::<usize>(token, "a stack number").unwrap_err()
+            .locate(inner, token)
+            .locate(line, inner);
+        assert_eq!(err.column, 13);
+    }
+
+    #[test]
+    fn diagnostic_underlines_text() {
+        let input = "A Y\nB Q\n";
+        let line = input.lines().nth(1).unwrap();
+        let err = ParseError::new("Q", "one of X, Y, Z").locate(line, &line[2..]).on_line(2);
+        assert_eq!(err.diagnostic(input), "line 2, column 3: expected one of X, Y, Z, found 'Q'\n    B Q\n      ^");
+    }
+}
@@ -0,0 +1,235 @@
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use crate::solution::{Day, Part};
+
+/// The outcome of solving one part of a day, written as one line of JSON by the runner's
+/// `--json` mode.
+///
+/// A run that fails has no answer, the kind of `failure`, and an `error` describing it. Times are in nanoseconds, and
+/// the parse time is shared by the parts solved from the same parsed input
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// where the input came from: `real`, `example N`, a file path or `-` for stdin
+    pub input: String,
+    /// hex SHA-256 of the input text, if it could be read
+    pub input_hash: Option<String>,
+    pub answer: Option<String>,
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+    pub ok: bool,
+    pub failure: Option<Failure>,
+    pub error: Option<String>,
+}
+
+/// why a part has no answer
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Failure {
+    /// the input could not be read
+    Input,
+    Parse,
+    /// the input parsed, but the part has no answer for it
+    NoAnswer,
+    /// parsing or solving panicked
+    Panic,
+}
+
+impl Record {
+    /// a record of a part that could not be run because its input could not be read
+    pub fn failed(year: u16, day: u8, part: Part, input: &str, error: impl Into<String>) -> Self {
+        Self {
+            year,
+            day,
+            part: part_number(part),
+            input: input.to_string(),
+            input_hash: None,
+            answer: None,
+            parse_ns: None,
+            solve_ns: None,
+            ok: false,
+            failure: Some(Failure::Input),
+            error: Some(error.into()),
+        }
+    }
+
+    /// this record as a single line of JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a record always serializes")
+    }
+}
+
+fn part_number(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+fn nanos(d: Duration) -> u64 {
+    d.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+/// the message a panic was started with, if it was given one
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "no message".to_string());
+    format!("panicked: {}", message)
+}
+
+/// hex SHA-256 of `input`
+pub fn input_hash(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
+/// parse `text` once and solve each of `parts` from it, timing the parse and each part. `input`
+/// names where the text came from. A parse error fails every part, and a part without an answer
+/// fails on its own. A panic while parsing or solving is caught, and fails the parts it stopped
+pub fn run_parts(year: u16, day: &Day, parts: &[Part], input: &str, text: &str) -> Vec<Record> {
+    let hash = input_hash(text);
+    let start = Instant::now();
+    let parsed = match catch_unwind(|| day.parse(text)) {
+        Ok(Ok(parsed)) => Ok(parsed),
+        Ok(Err(e)) => Err((Failure::Parse, e.diagnostic(text))),
+        Err(payload) => Err((Failure::Panic, panic_message(payload))),
+    };
+    let parse_ns = nanos(start.elapsed());
+
+    parts.iter()
+        .map(|&part| {
+            let (answer, solve_ns, error) = match &parsed {
+                Ok(parsed) => {
+                    let start = Instant::now();
+                    let answer = catch_unwind(AssertUnwindSafe(|| parsed.solve(part)));
+                    let solve_ns = Some(nanos(start.elapsed()));
+                    match answer {
+                        Ok(Ok(answer)) => (Some(answer), solve_ns, None),
+                        Ok(Err(e)) => (None, solve_ns, Some((Failure::NoAnswer, format!("no answer: {}", e)))),
+                        Err(payload) => (None, solve_ns, Some((Failure::Panic, panic_message(payload)))),
+                    }
+                }
+                Err(e) => (None, None, Some(e.clone())),
+            };
+            Record {
+                year,
+                day: day.day,
+                part: part_number(part),
+                input: input.to_string(),
+                input_hash: Some(hash.clone()),
+                answer,
+                parse_ns: Some(parse_ns),
+                solve_ns,
+                ok: error.is_none(),
+                failure: error.as_ref().map(|&(failure, _)| failure),
+                error: error.map(|(_, error)| error),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_error::{parse_token, ParseError};
+    use crate::solution::{Day, Part, Solution};
+    use super::{input_hash, run_parts, Failure, Record};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type Answer1 = u32;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input.lines()
+                .enumerate()
+                .map(|(idx, l)| parse_token(l, "a number").map_err(|e| e.on_line(idx + 1)))
+                .collect()
+        }
+
+        fn part1(input: &Vec<u32>) -> u32 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Vec<u32>) -> usize {
+            input.len()
+        }
+    }
+
+    const SUM: Day = Day::new::<Sum>(3, "Sum");
+
+    /// a solution that panics solving part 2, or parsing an empty input
+    struct Panics;
+
+    impl Solution for Panics {
+        type Input = usize;
+        type Answer1 = usize;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            assert!(!input.is_empty(), "empty input");
+            Ok(input.len())
+        }
+
+        fn part1(input: &usize) -> usize {
+            *input
+        }
+
+        fn part2(input: &usize) -> usize {
+            panic!("part 2 of {}", input)
+        }
+    }
+
+    #[test]
+    fn hash_is_hex_sha256() {
+        assert_eq!(input_hash(""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+    }
+
+    #[test]
+    fn run_both_parts() {
+        let records = run_parts(2021, &SUM, &Part::BOTH, "real", "1\n2\n3\n");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].answer.as_deref(), Some("6"));
+        assert_eq!(records[1].answer.as_deref(), Some("3"));
+        assert!(records.iter().all(|r| r.ok && r.error.is_none() && r.solve_ns.is_some()));
+        assert_eq!(records[0].parse_ns, records[1].parse_ns);
+        assert_eq!(records[0].input_hash, Some(input_hash("1\n2\n3\n")));
+    }
+
+    #[test]
+    fn parse_error_fails_the_run() {
+        let records = run_parts(2021, &SUM, &[Part::Two], "-", "1\nx\n");
+        let record = &records[0];
+        assert!(!record.ok);
+        assert_eq!((record.part, record.answer.as_ref(), record.solve_ns), (2, None, None));
+        assert_eq!(record.failure, Some(Failure::Parse));
+        assert!(record.error.as_ref().unwrap().starts_with("line 2, column 1: expected a number"));
+    }
+
+    #[test]
+    fn record_as_json() {
+        let record = Record::failed(2022, 7, Part::One, "example 1", "no \"input\"");
+        assert_eq!(record.to_json(), concat!(
+            r#"{"year":2022,"day":7,"part":1,"input":"example 1","input_hash":null,"answer":null,"#,
+            r#""parse_ns":null,"solve_ns":null,"ok":false,"failure":"input","error":"no \"input\""}"#));
+    }
+
+    #[test]
+    fn panics_fail_only_the_parts_they_stop() {
+        let panics = Day::new::<Panics>(4, "Panics");
+        let records = run_parts(2021, &panics, &Part::BOTH, "real", "abc");
+        assert_eq!(records[0].answer.as_deref(), Some("3"));
+        assert!(!records[1].ok);
+        assert_eq!(records[1].error.as_deref(), Some("panicked: part 2 of 3"));
+        assert_eq!((records[0].failure, records[1].failure), (None, Some(Failure::Panic)));
+
+        let records = run_parts(2021, &panics, &Part::BOTH, "real", "");
+        assert!(records.iter().all(|r| r.error.as_deref() == Some("panicked: empty input")));
+    }
+}
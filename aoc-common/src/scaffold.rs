@@ -0,0 +1,277 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use crate::input::{InputKind, InputLocator};
+
+/// the module skeleton for a new day, see `module_source` for its placeholders
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs.in");
+
+/// A new day to add to a year's solutions
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NewDay {
+    pub year: u16,
+    pub day: u8,
+    pub title: String,
+    /// the answers to the example input, asserted by the generated test. They default to the
+    /// skeleton's answers of 0
+    pub answers: [String; 2],
+}
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// the title has no letters or digits to name the module after
+    Title(String),
+    /// the day is already in the registry
+    Registered(u8),
+    /// the module file already exists
+    Exists(PathBuf),
+    /// the `lib.rs` has no `pub mod` lines or `DAYS` list to add the day to
+    NoRegistry(PathBuf),
+    Io(PathBuf, std::io::Error),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::Title(title) => write!(f, "'{}' can't be used as a module name", title),
+            ScaffoldError::Registered(day) => write!(f, "day {} is already registered", day),
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::NoRegistry(path) => write!(f, "could not find the module list and DAYS in {}", path.display()),
+            ScaffoldError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
+}
+
+impl Error for ScaffoldError {}
+
+/// the words of `title`, split on anything that isn't a letter or digit
+fn words(title: &str) -> Vec<String> {
+    title.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_ascii_lowercase())
+        .collect()
+}
+
+impl NewDay {
+    /// the module name, like `d09_rope_bridge`
+    pub fn module_name(&self) -> Result<String, ScaffoldError> {
+        match words(&self.title) {
+            words if words.is_empty() => Err(ScaffoldError::Title(self.title.clone())),
+            words => Ok(format!("d{:02}_{}", self.day, words.join("_"))),
+        }
+    }
+
+    /// the name of the solution type, like `RopeBridge`
+    pub fn type_name(&self) -> String {
+        let name: String = words(&self.title).iter()
+            .map(|w| w[..1].to_ascii_uppercase() + &w[1..])
+            .collect();
+        // a type name can't start with a digit
+        match name.starts_with(|c: char| c.is_ascii_digit()) {
+            true => format!("Day{}", name),
+            false => name,
+        }
+    }
+
+    /// the source of the new module, from the template with its `{{year}}`, `{{day}}`,
+    /// `{{day02}}`, `{{title}}`, `{{module}}`, `{{type}}`, `{{answer1}}` and `{{answer2}}`
+    /// placeholders filled in
+    pub fn module_source(&self) -> Result<String, ScaffoldError> {
+        Ok(DAY_TEMPLATE
+            .replace("{{year}}", &self.year.to_string())
+            .replace("{{day02}}", &format!("{:02}", self.day))
+            .replace("{{day}}", &self.day.to_string())
+            .replace("{{title}}", &self.title)
+            .replace("{{module}}", &self.module_name()?)
+            .replace("{{type}}", &self.type_name())
+            .replace("{{answer1}}", &self.answers[0])
+            .replace("{{answer2}}", &self.answers[1]))
+    }
+
+    /// add the module declaration and the `DAYS` entry for this day to the source of a `lib.rs`,
+    /// keeping both in day order
+    pub fn register(&self, lib: &str, lib_path: &Path) -> Result<String, ScaffoldError> {
+        let module = self.module_name()?;
+        let no_registry = || ScaffoldError::NoRegistry(lib_path.to_path_buf());
+        let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+
+        let days_start = lines.iter().position(|l| l.starts_with("pub static DAYS")).ok_or_else(no_registry)?;
+        let days_end = days_start + lines[days_start..].iter().position(|l| l.starts_with("];")).ok_or_else(no_registry)?;
+        let entry_day = |line: &str| {
+            let (_, rest) = line.split_once(">(")?;
+            rest.split_once(',')?.0.trim().parse::<u8>().ok()
+        };
+        let mut insert_at = days_end;
+        for (i, line) in lines.iter().enumerate().take(days_end).skip(days_start + 1) {
+            match entry_day(line) {
+                Some(day) if day == self.day => return Err(ScaffoldError::Registered(self.day)),
+                Some(day) if day > self.day && insert_at == days_end => insert_at = i,
+                _ => (),
+            }
+        }
+        lines.insert(insert_at, format!(
+            "    Day::new::<{}::{}>({}, \"{}\"),",
+            module, self.type_name(), self.day, self.title.replace('"', "\\\"")));
+
+        let mods: Vec<usize> = lines.iter().enumerate()
+            .filter(|(_, l)| l.starts_with("pub mod d"))
+            .map(|(i, _)| i)
+            .collect();
+        let last_mod = *mods.last().ok_or_else(no_registry)?;
+        let declaration = format!("pub mod {};", module);
+        let mod_at = mods.iter().copied().find(|&i| lines[i] > declaration).unwrap_or(last_mod + 1);
+        lines.insert(mod_at, declaration);
+
+        Ok(lines.join("\n") + "\n")
+    }
+}
+
+/// the file name for the first example input of a day, `d07-test-input.txt` or `07-ex1.txt`,
+/// following the naming of the real input
+fn example_path(locator: &InputLocator, day: u8) -> PathBuf {
+    let real = locator.cache_path(day);
+    let decorated = real.file_name().is_some_and(|name| name.to_string_lossy().starts_with('d'));
+    let names = InputLocator::file_names(day, InputKind::Example(1));
+    locator.dir().join(if decorated { &names[0] } else { &names[3] })
+}
+
+fn write_new(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| std::io::Write::write_all(&mut file, contents.as_bytes()))
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => ScaffoldError::Exists(path.to_path_buf()),
+            _ => ScaffoldError::Io(path.to_path_buf(), e),
+        })
+}
+
+/// Add `new` to the solution crate in `crate_dir`: write its module into `src`, register it in
+/// `src/lib.rs`, and create an empty real input and example input in the input directory, unless
+/// they are already there. The empty real input is replaced by `fetch`.
+///
+/// Returns the paths created or changed
+pub fn scaffold(new: &NewDay, crate_dir: &Path, locator: &InputLocator) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let src = crate_dir.join("src");
+    let lib_path = src.join("lib.rs");
+    let lib = std::fs::read_to_string(&lib_path).map_err(|e| ScaffoldError::Io(lib_path.clone(), e))?;
+    let registered = new.register(&lib, &lib_path)?;
+    let module_path = src.join(format!("{}.rs", new.module_name()?));
+    write_new(&module_path, &new.module_source()?)?;
+    std::fs::write(&lib_path, registered).map_err(|e| ScaffoldError::Io(lib_path.clone(), e))?;
+
+    let mut changed = vec![module_path, lib_path];
+    for (kind, path) in [(InputKind::Real, locator.cache_path(new.day)), (InputKind::Example(1), example_path(locator, new.day))] {
+        if locator.path(new.day, kind).is_err() {
+            write_new(&path, "")?;
+            changed.push(path);
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::input::InputLocator;
+    use crate::test_util::scratch_dir;
+    use super::{scaffold, NewDay, ScaffoldError};
+
+    const LIB: &str = "pub mod d01_first;
+pub mod d09_ninth;
+
+use aoc_common::solution::Day;
+
+pub static DAYS: &[Day] = &[
+    Day::new::<d01_first::First>(1, \"First\"),
+    Day::new::<d09_ninth::Ninth>(9, \"Ninth\"),
+];
+";
+
+    fn new_day(day: u8, title: &str) -> NewDay {
+        NewDay { year: 2022, day, title: title.to_string(), answers: ["0".to_string(), "0".to_string()] }
+    }
+
+    #[test]
+    fn names_from_title() {
+        let new = new_day(10, "Cathode-Ray Tube");
+        assert_eq!(new.module_name().unwrap(), "d10_cathode_ray_tube");
+        assert_eq!(new.type_name(), "CathodeRayTube");
+        assert_eq!(new_day(3, "1202 Program Alarm").type_name(), "Day1202ProgramAlarm");
+        assert!(matches!(new_day(3, " -- ").module_name(), Err(ScaffoldError::Title(_))));
+    }
+
+    #[test]
+    fn module_from_template() {
+        let mut new = new_day(7, "No Space Left");
+        new.answers = ["95437".to_string(), "24933642".to_string()];
+        let source = new.module_source().unwrap();
+        assert!(source.starts_with("// Day 7 - No Space Left\n// https://adventofcode.com/2022/day/7\n"));
+        assert!(source.contains("/// Day 07 - No Space Left\npub struct NoSpaceLeft;"));
+        assert!(source.contains("use crate::d07_no_space_left::NoSpaceLeft;"));
+        assert!(source.contains("read_input(YEAR, 7, InputKind::Example(1))"));
+        assert!(source.contains("assert_eq!(NoSpaceLeft::part2(&parsed).to_string(), \"24933642\");"));
+        assert!(!source.contains("{{"));
+    }
+
+    #[test]
+    fn register_in_day_order() {
+        let lib = new_day(4, "Camp Cleanup").register(LIB, Path::new("lib.rs")).unwrap();
+        assert_eq!(lib, "pub mod d01_first;
+pub mod d04_camp_cleanup;
+pub mod d09_ninth;
+
+use aoc_common::solution::Day;
+
+pub static DAYS: &[Day] = &[
+    Day::new::<d01_first::First>(1, \"First\"),
+    Day::new::<d04_camp_cleanup::CampCleanup>(4, \"Camp Cleanup\"),
+    Day::new::<d09_ninth::Ninth>(9, \"Ninth\"),
+];
+");
+        let lib = new_day(12, "Last").register(LIB, Path::new("lib.rs")).unwrap();
+        assert!(lib.contains("pub mod d09_ninth;\npub mod d12_last;\n"));
+        assert!(lib.contains("(9, \"Ninth\"),\n    Day::new::<d12_last::Last>(12, \"Last\"),\n];"));
+
+        assert!(matches!(new_day(9, "Again").register(LIB, Path::new("lib.rs")), Err(ScaffoldError::Registered(9))));
+        assert!(matches!(new_day(2, "Two").register("fn main() {}\n", Path::new("lib.rs")), Err(ScaffoldError::NoRegistry(_))));
+    }
+
+    #[test]
+    fn scaffold_a_crate() {
+        let dir = scratch_dir("scaffold");
+        let crate_dir = dir.join("rust-2022");
+        std::fs::create_dir_all(crate_dir.join("src")).unwrap();
+        std::fs::write(crate_dir.join("src/lib.rs"), LIB).unwrap();
+        let locator = InputLocator::new(2022, dir.join("input"));
+        std::fs::create_dir_all(locator.dir()).unwrap();
+
+        let changed = scaffold(&new_day(4, "Camp Cleanup"), &crate_dir, &locator).unwrap();
+        let names: Vec<String> = changed.iter().map(|p| p.file_name().unwrap().to_string_lossy().to_string()).collect();
+        assert_eq!(names, vec!["d04_camp_cleanup.rs", "lib.rs", "d04-input.txt", "d04-test-input.txt"]);
+        assert!(std::fs::read_to_string(crate_dir.join("src/lib.rs")).unwrap().contains("pub mod d04_camp_cleanup;"));
+
+        // the module is never overwritten
+        std::fs::write(crate_dir.join("src/lib.rs"), LIB).unwrap();
+        let err = scaffold(&new_day(4, "Camp Cleanup"), &crate_dir, &locator).unwrap_err();
+        assert!(matches!(err, ScaffoldError::Exists(_)));
+        assert_eq!(std::fs::read_to_string(crate_dir.join("src/lib.rs")).unwrap(), LIB);
+    }
+
+    #[test]
+    fn undecorated_input_names() {
+        let dir = scratch_dir("scaffold-2020");
+        let crate_dir = dir.join("rust-2020");
+        std::fs::create_dir_all(crate_dir.join("src")).unwrap();
+        std::fs::write(crate_dir.join("src/lib.rs"), LIB).unwrap();
+        let locator = InputLocator::new(2020, dir.join("input"));
+        std::fs::create_dir_all(locator.dir()).unwrap();
+        std::fs::write(locator.dir().join("01-input.txt"), "1\n").unwrap();
+        std::fs::write(locator.dir().join("15-ex1.txt"), "0,3,6\n").unwrap();
+
+        let changed = scaffold(&new_day(15, "Rambunctious Recitation"), &crate_dir, &locator).unwrap();
+        assert_eq!(changed.last().unwrap().file_name().unwrap(), "15-input.txt");
+        assert_eq!(std::fs::read_to_string(locator.dir().join("15-ex1.txt")).unwrap(), "0,3,6\n");
+    }
+}
@@ -0,0 +1,337 @@
+use std::str::FromStr;
+use crate::parse_error::ParseError;
+
+/// A cursor over one line of puzzle input.
+///
+/// Each method consumes what it recognises from the front of the line, or returns a `ParseError`
+/// positioned at the text it could not recognise and leaves the line as it was
+#[derive(Debug, Clone)]
+pub struct Scanner<'a> {
+    /// the whole line, which errors are positioned in
+    line: &'a str,
+    /// what is left to scan, a slice of `line`
+    rest: &'a str,
+    /// 1-based line number, 0 if not known
+    line_no: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(line: &'a str) -> Self {
+        Self { line, rest: line, line_no: 0 }
+    }
+
+    /// set the line number errors are reported on
+    pub fn on_line(mut self, line_no: usize) -> Self {
+        self.line_no = line_no;
+        self
+    }
+
+    /// what is left to scan
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    /// an error for `text`, a slice of this scanner's line, which should have been `expected`
+    pub fn error(&self, text: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::new(text, expected).locate(self.line, text).on_line(self.line_no)
+    }
+
+    /// an error for whatever comes next, which should have been `expected`
+    pub fn error_here(&self, expected: impl Into<String>) -> ParseError {
+        self.error(self.next_token(), expected)
+    }
+
+    /// the text up to the end of the next word, for error messages
+    fn next_token(&self) -> &'a str {
+        let start = self.rest.len() - self.rest.trim_start().len();
+        let end = self.rest[start..].find(char::is_whitespace).map_or(self.rest.len(), |end| start + end);
+        &self.rest[..end]
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        taken
+    }
+
+    /// consume `literal` if the line continues with it
+    pub fn eat(&mut self, literal: &str) -> bool {
+        if self.rest.starts_with(literal) {
+            self.advance(literal.len());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// consume `literal`, which the line must continue with
+    pub fn literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.eat(literal) {
+            Ok(())
+        } else {
+            Err(self.error_here(format!("'{}'", literal)))
+        }
+    }
+
+    /// consume the longest run of chars matching `pred`, which may be empty
+    pub fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let len = self.rest.find(|c| !pred(c)).unwrap_or(self.rest.len());
+        self.advance(len)
+    }
+
+    pub fn skip_spaces(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// consume a run of chars matching `pred`, which must not be empty
+    pub fn take_while1(&mut self, pred: impl Fn(char) -> bool, expected: &str) -> Result<&'a str, ParseError> {
+        match self.take_while(pred) {
+            "" => Err(self.error_here(expected)),
+            taken => Ok(taken),
+        }
+    }
+
+    /// consume a run of chars up to the next space
+    pub fn word(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        self.take_while1(|c| !c.is_whitespace(), expected)
+    }
+
+    /// consume the rest of the line, which must not be empty
+    pub fn remainder(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        self.take_while1(|_| true, expected)
+    }
+
+    /// consume the text up to `delimiter`, and the delimiter
+    pub fn until(&mut self, delimiter: &str, expected: &str) -> Result<&'a str, ParseError> {
+        match self.rest.find(delimiter) {
+            Some(end) => {
+                let taken = self.advance(end);
+                self.advance(delimiter.len());
+                Ok(taken)
+            }
+            None => Err(self.error(self.rest, format!("{} followed by '{}'", expected, delimiter))),
+        }
+    }
+
+    /// consume a run of digits as a `T`
+    pub fn unsigned<T: FromStr>(&mut self, expected: &str) -> Result<T, ParseError> {
+        let len = self.rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(self.rest.len());
+        self.number(len, expected)
+    }
+
+    /// consume a run of digits with an optional `+` or `-` sign as a `T`
+    pub fn signed<T: FromStr>(&mut self, expected: &str) -> Result<T, ParseError> {
+        let sign = usize::from(self.rest.starts_with(['+', '-']));
+        let len = self.rest[sign..].find(|c: char| !c.is_ascii_digit()).map_or(self.rest.len(), |len| sign + len);
+        self.number(len, expected)
+    }
+
+    fn number<T: FromStr>(&mut self, len: usize, expected: &str) -> Result<T, ParseError> {
+        let digits = &self.rest[..len];
+        if !digits.ends_with(|c: char| c.is_ascii_digit()) {
+            return Err(self.error_here(expected));
+        }
+        let n = digits.parse::<T>().map_err(|_| self.error(digits, expected))?;
+        self.advance(len);
+        Ok(n)
+    }
+
+    /// consume the first of `choices` the line continues with, giving its value
+    pub fn one_of<T: Copy>(&mut self, choices: &[(&str, T)], expected: &str) -> Result<T, ParseError> {
+        choices.iter()
+            .find(|(literal, _)| self.eat(literal))
+            .map(|&(_, value)| value)
+            .ok_or_else(|| self.error_here(expected))
+    }
+
+    /// consume one or more items separated by `separator`
+    pub fn separated<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![item(self)?];
+        while self.eat(separator) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// check the whole line has been consumed
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(self.rest, "the end of the line"))
+        }
+    }
+}
+
+/// parse every line of `input` with `parse`, which must consume the whole line
+pub fn lines<'a, T>(
+    input: &'a str,
+    mut parse: impl FnMut(&mut Scanner<'a>) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input.lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            let mut scanner = Scanner::new(line).on_line(idx + 1);
+            let parsed = parse(&mut scanner)?;
+            scanner.end()?;
+            Ok(parsed)
+        })
+        .collect()
+}
+
+/// A run of lines of the input between blank lines
+#[derive(Debug, Clone, Copy)]
+pub struct Block<'a> {
+    /// the lines of the block, without the newline after the last one
+    pub text: &'a str,
+    /// the 1-based line number of the first line
+    pub first_line: usize,
+}
+
+impl<'a> Block<'a> {
+    /// a scanner over each line of the block
+    pub fn lines(&self) -> impl Iterator<Item = Scanner<'a>> {
+        let first_line = self.first_line;
+        self.text.lines()
+            .enumerate()
+            .map(move |(idx, line)| Scanner::new(line).on_line(first_line + idx))
+    }
+
+    /// an error for the block ending before something `expected`
+    pub fn ended_early(&self, expected: impl Into<String>) -> ParseError {
+        ParseError::new("", expected).on_line(self.first_line + self.text.lines().count())
+    }
+
+    /// Split the block into `key<separator>value` fields separated by spaces or newlines, with
+    /// a scanner over each value.
+    ///
+    /// `expected` describes a field, for the error when one has no separator
+    pub fn fields(&self, separator: char, expected: &str) -> Result<Vec<(&'a str, Scanner<'a>)>, ParseError> {
+        let mut fields = vec![];
+        for mut line in self.lines() {
+            loop {
+                line.skip_spaces();
+                if line.is_empty() {
+                    break;
+                }
+                let field = line.word(expected)?;
+                let (key, value) = field.split_once(separator)
+                    .ok_or_else(|| line.error(field, expected))?;
+                fields.push((key, Scanner { rest: value, ..line.clone() }));
+            }
+        }
+        Ok(fields)
+    }
+}
+
+/// the blocks of `input` that are separated by one or more blank lines
+pub fn blocks(input: &str) -> Vec<Block<'_>> {
+    let mut blocks = vec![];
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for (idx, line) in input.split_inclusive('\n').enumerate() {
+        let blank = line.trim().is_empty();
+        match (start, blank) {
+            (None, false) => start = Some((offset, idx + 1)),
+            (Some((from, first_line)), true) => {
+                blocks.push(Block { text: input[from..offset].trim_end_matches(['\n', '\r']), first_line });
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+    if let Some((from, first_line)) = start {
+        blocks.push(Block { text: input[from..].trim_end_matches(['\n', '\r']), first_line });
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scan::{blocks, lines, Scanner};
+
+    #[test]
+    fn scan_a_line() {
+        let mut s = Scanner::new("move 12 from -3 to +4").on_line(2);
+        s.literal("move ").unwrap();
+        assert_eq!(s.unsigned::<usize>("an amount"), Ok(12));
+        assert!(s.eat(" from "));
+        assert_eq!(s.signed::<i32>("a stack"), Ok(-3));
+        s.skip_spaces();
+        assert_eq!(s.word("a word"), Ok("to"));
+        s.skip_spaces();
+        assert_eq!(s.signed::<i32>("a stack"), Ok(4));
+        assert!(s.end().is_ok());
+    }
+
+    #[test]
+    fn errors_are_positioned_in_the_line() {
+        let mut s = Scanner::new("mem[8] = x1").on_line(3);
+        s.literal("mem[").unwrap();
+        let err = s.literal("]").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 5, "8]"));
+        s.unsigned::<u8>("an address").unwrap();
+        s.literal("] = ").unwrap();
+        let err = s.unsigned::<u64>("a value").unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 10: expected a value, found 'x1'");
+        assert_eq!(s.rest(), "x1");
+
+        let mut s = Scanner::new("300");
+        let err = s.unsigned::<u8>("a byte").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (1, "300"));
+        assert!(Scanner::new("-").signed::<i32>("a number").is_err());
+        assert_eq!(Scanner::new("ab").end().unwrap_err().text, "ab");
+    }
+
+    #[test]
+    fn choices_and_lists() {
+        let mut s = Scanner::new("* 76, 88, 96");
+        assert_eq!(s.one_of(&[("+", 1), ("*", 2)], "an operator"), Ok(2));
+        s.skip_spaces();
+        assert_eq!(s.separated(", ", |s| s.unsigned::<u32>("an item")), Ok(vec![76, 88, 96]));
+        assert!(s.is_empty());
+
+        let mut s = Scanner::new("light red bags contain 2 bags");
+        assert_eq!(s.until(" bags contain ", "a colour"), Ok("light red"));
+        assert_eq!(s.remainder("bags"), Ok("2 bags"));
+        assert!(s.remainder("bags").is_err());
+        assert_eq!(Scanner::new("- 1").one_of(&[("+", 1)], "an operator").unwrap_err().text, "-");
+    }
+
+    #[test]
+    fn parse_every_line() {
+        let parsed = lines("1,2\n3,4\n", |s| s.separated(",", |s| s.unsigned::<u8>("a number")));
+        assert_eq!(parsed, Ok(vec![vec![1, 2], vec![3, 4]]));
+        let err = lines("1,2\n3;4\n", |s| s.separated(",", |s| s.unsigned::<u8>("a number"))).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, ";4"));
+    }
+
+    #[test]
+    fn split_blocks_and_fields() {
+        let input = "\nbyr:1937 iyr:2017\ncid:147\n\n\nhgt:183cm\n";
+        let blocks = blocks(input);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!((blocks[0].text, blocks[0].first_line), ("byr:1937 iyr:2017\ncid:147", 2));
+        assert_eq!((blocks[1].text, blocks[1].first_line), ("hgt:183cm", 6));
+
+        let fields = blocks[0].fields(':', "a field").unwrap();
+        let keys: Vec<&str> = fields.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, vec!["byr", "iyr", "cid"]);
+        let (_, mut cid) = fields[2].clone();
+        let err = cid.literal("#").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 5));
+
+        let err = super::blocks("a:1\nb 2\n")[0].fields(':', "a field").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "b"));
+        assert_eq!(blocks[1].ended_early("a line").line, 7);
+    }
+}
@@ -0,0 +1,246 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::str::FromStr;
+use crate::input::{read_text, ReadError};
+use crate::parse_error::ParseError;
+use crate::visual::Frames;
+
+/// the two parts of a daily puzzle
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Part::One => f.write_str("1"),
+            Part::Two => f.write_str("2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(ParseError::new(s, "part 1 or 2")),
+        }
+    }
+}
+
+/// The answer to one part of a puzzle. Parts that have no answer for some inputs, e.g. because
+/// it would overflow, answer with a `Result` whose error says why
+pub trait Answer {
+    fn into_answer(self) -> Result<String, String>;
+}
+
+macro_rules! display_answers {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn into_answer(self) -> Result<String, String> {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+display_answers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, String);
+
+impl<T: Display, E: Display> Answer for Result<T, E> {
+    fn into_answer(self) -> Result<String, String> {
+        self.map(|answer| answer.to_string()).map_err(|e| e.to_string())
+    }
+}
+
+/// A solution to a single day's puzzle.
+///
+/// The puzzle input is parsed once into `Input`, and then each part is solved from
+/// that parsed input, returning a typed answer
+pub trait Solution {
+    /// the parsed form of the puzzle input
+    type Input;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    /// parse the raw puzzle input text
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Self::Answer1;
+
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    /// draw the state of the puzzle as `part` is solved, a frame for each step, for days with
+    /// something to watch. Returns false if the day has no visualisation
+    fn visualise(_input: &Self::Input, _part: Part, _frames: &mut dyn Frames) -> bool {
+        false
+    }
+
+    /// an analysis of the puzzle beyond its two answers, for days that have one, or `None`
+    fn report(_input: &Self::Input) -> Option<String> {
+        None
+    }
+}
+
+/// a parsed puzzle input that can be solved without knowing the concrete `Solution` type
+pub trait Solvable {
+    /// the answer to `part`, or why the input has none
+    fn solve(&self, part: Part) -> Result<String, String>;
+
+    /// see `Solution::visualise`
+    fn visualise(&self, part: Part, frames: &mut dyn Frames) -> bool;
+
+    /// see `Solution::report`
+    fn report(&self) -> Option<String>;
+}
+
+struct Parsed<S: Solution>(S::Input);
+
+impl<S: Solution> Solvable for Parsed<S> {
+    fn solve(&self, part: Part) -> Result<String, String> {
+        match part {
+            Part::One => S::part1(&self.0).into_answer(),
+            Part::Two => S::part2(&self.0).into_answer(),
+        }
+    }
+
+    fn visualise(&self, part: Part, frames: &mut dyn Frames) -> bool {
+        S::visualise(&self.0, part, frames)
+    }
+
+    fn report(&self) -> Option<String> {
+        S::report(&self.0)
+    }
+}
+
+fn parse<S: Solution + 'static>(input: &str) -> Result<Box<dyn Solvable>, ParseError> {
+    S::parse(input).map(|parsed| Box::new(Parsed::<S>(parsed)) as Box<dyn Solvable>)
+}
+
+/// why a day could not solve a part of its input
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SolveError {
+    Parse(ParseError),
+    /// the input parsed, but has no answer for the part
+    NoAnswer(String),
+}
+
+impl SolveError {
+    /// see `ParseError::diagnostic`
+    pub fn diagnostic(&self, input: &str) -> String {
+        match self {
+            SolveError::Parse(e) => e.diagnostic(input),
+            SolveError::NoAnswer(_) => self.to_string(),
+        }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Parse(e) => e.fmt(f),
+            SolveError::NoAnswer(why) => write!(f, "no answer: {}", why),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+/// A registered day, pairing a day number and puzzle title with its `Solution`
+#[derive(Copy, Clone)]
+pub struct Day {
+    pub day: u8,
+    pub title: &'static str,
+    parser: fn(&str) -> Result<Box<dyn Solvable>, ParseError>,
+}
+
+impl Day {
+    pub const fn new<S: Solution + 'static>(day: u8, title: &'static str) -> Self {
+        Self {
+            day,
+            title,
+            parser: parse::<S>,
+        }
+    }
+
+    /// parse the puzzle input, returning something that can solve either part
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Solvable>, ParseError> {
+        (self.parser)(input)
+    }
+
+    /// parse the puzzle input and solve the given part
+    pub fn solve(&self, input: &str, part: Part) -> Result<String, SolveError> {
+        self.parse(input).map_err(SolveError::Parse)?.solve(part).map_err(SolveError::NoAnswer)
+    }
+
+    /// read the puzzle input from `reader`, i.e. stdin or a file, which may be compressed, and
+    /// parse it
+    pub fn read(&self, reader: impl Read) -> Result<Box<dyn Solvable>, ReadError> {
+        let input = read_text(reader).map_err(ReadError::Io)?;
+        self.parse(&input).map_err(ReadError::Parse)
+    }
+}
+
+impl std::fmt::Debug for Day {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Day")
+            .field("day", &self.day)
+            .field("title", &self.title)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::ReadError;
+    use crate::parse_error::ParseError;
+    use crate::solution::{Day, Part, Solution, SolveError};
+
+    struct Count;
+
+    impl Solution for Count {
+        type Input = Vec<u32>;
+        type Answer1 = usize;
+        type Answer2 = Result<u32, &'static str>;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input.lines().map(|line| line.parse().map_err(|_| ParseError::new(line, "a number"))).collect()
+        }
+
+        fn part1(input: &Self::Input) -> usize {
+            input.len()
+        }
+
+        fn part2(input: &Self::Input) -> Result<u32, &'static str> {
+            input.iter().try_fold(0_u32, |sum, &n| sum.checked_add(n)).ok_or("the sum overflows")
+        }
+    }
+
+    #[test]
+    fn read_from_a_reader() {
+        let day = Day::new::<Count>(1, "Count");
+        let solvable = day.read("1\n2\n3\n".as_bytes()).unwrap();
+        assert_eq!((solvable.solve(Part::One), solvable.solve(Part::Two)), (Ok("3".to_string()), Ok("6".to_string())));
+        assert!(matches!(day.read("1\nx\n".as_bytes()), Err(ReadError::Parse(_))));
+        assert!(matches!(day.read([0xff].as_slice()), Err(ReadError::Io(_))));
+    }
+
+    #[test]
+    fn parts_without_an_answer() {
+        let day = Day::new::<Count>(1, "Count");
+        assert_eq!(day.solve("4294967295\n1", Part::One), Ok("2".to_string()));
+        let err = day.solve("4294967295\n1", Part::Two).unwrap_err();
+        assert_eq!(err, SolveError::NoAnswer("the sum overflows".to_string()));
+        assert_eq!(err.diagnostic("4294967295\n1"), "no answer: the sum overflows");
+        assert!(matches!(day.solve("x", Part::One), Err(SolveError::Parse(_))));
+    }
+}
@@ -0,0 +1,485 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::client::{Client, ClientError};
+use crate::parse_error::{parse_token, ParseError};
+use crate::solution::Part;
+
+/// name of the answers ledger, kept in each year's input directory
+pub const LEDGER_FILE: &str = "answer-ledger.tsv";
+
+lazy_static! {
+    static ref LEFT_TO_WAIT_RE: Regex = Regex::new(r"You have (?:(\d+)m ?)?(?:(\d+)s)? left to wait").unwrap();
+    static ref WAIT_MINUTES_RE: Regex = Regex::new(r"(?i)wait (one|\d+) minutes?").unwrap();
+}
+
+/// the server's verdict on a submitted answer
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// wrong, without a hint
+    Wrong,
+    /// an answer was submitted too recently, the answer was not checked
+    RateLimited,
+    /// the part is already solved or not unlocked yet, the answer was not checked
+    WrongLevel,
+}
+
+impl Outcome {
+    /// true if the answer is known to be wrong
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+
+    /// the name of this outcome in the ledger
+    fn token(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited => "rate-limited",
+            Outcome::WrongLevel => "wrong-level",
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Self> {
+        [Outcome::Correct, Outcome::TooHigh, Outcome::TooLow, Outcome::Wrong, Outcome::RateLimited, Outcome::WrongLevel]
+            .into_iter()
+            .find(|outcome| outcome.token() == token)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Outcome::Correct => "that's the right answer",
+            Outcome::TooHigh => "wrong, the answer is too high",
+            Outcome::TooLow => "wrong, the answer is too low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited => "an answer was submitted too recently",
+            Outcome::WrongLevel => "this part is already solved or is not unlocked yet",
+        })
+    }
+}
+
+/// the outcome of a submission, and how long the server asked us to wait before the next one
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Verdict {
+    pub outcome: Outcome,
+    pub wait: Option<Duration>,
+}
+
+impl Verdict {
+    /// read the verdict from the text of the server's response page
+    pub fn parse(body: &str) -> Option<Self> {
+        let outcome = if body.contains("That's the right answer") {
+            Outcome::Correct
+        } else if body.contains("answer is too high") {
+            Outcome::TooHigh
+        } else if body.contains("answer is too low") {
+            Outcome::TooLow
+        } else if body.contains("That's not the right answer") {
+            Outcome::Wrong
+        } else if body.contains("You gave an answer too recently") {
+            Outcome::RateLimited
+        } else if body.contains("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            return None;
+        };
+
+        let secs = |m: Option<regex::Match>| m.and_then(|m| m.as_str().parse::<u64>().ok()).unwrap_or(0);
+        let wait = if let Some(caps) = LEFT_TO_WAIT_RE.captures(body) {
+            Some(Duration::from_secs(secs(caps.get(1)) * 60 + secs(caps.get(2))))
+        } else {
+            WAIT_MINUTES_RE.captures(body).map(|caps| match &caps[1] {
+                "one" => Duration::from_secs(60),
+                minutes => Duration::from_secs(minutes.parse::<u64>().unwrap_or(1) * 60),
+            })
+        };
+        Some(Self { outcome, wait })
+    }
+}
+
+/// a single submitted answer
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// when the answer was submitted, in seconds since the unix epoch
+    pub time: u64,
+    /// how long the server asked us to wait before submitting again
+    pub wait: Duration,
+}
+
+impl Attempt {
+    fn parse(line: &str) -> Result<Self, ParseError> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 7 {
+            return Err(ParseError::new(line, "year, day, part, answer, outcome, time and wait separated by tabs"));
+        }
+        let located = |e: ParseError, field: &str| e.locate(line, field);
+        let field = |i: usize, expected: &str| parse_token::<u64>(fields[i], expected).map_err(|e| located(e, fields[i]));
+        Ok(Self {
+            year: parse_token(fields[0], "a year").map_err(|e| located(e, fields[0]))?,
+            day: parse_token(fields[1], "a day").map_err(|e| located(e, fields[1]))?,
+            part: fields[2].parse::<Part>().map_err(|e| located(e, fields[2]))?,
+            answer: unescape(fields[3]).map_err(|e| located(e, fields[3]))?,
+            outcome: Outcome::from_token(fields[4])
+                .ok_or_else(|| ParseError::new(fields[4], "a submission outcome").locate(line, fields[4]))?,
+            time: field(5, "a time in seconds")?,
+            wait: Duration::from_secs(field(6, "a wait in seconds")?),
+        })
+    }
+}
+
+impl Display for Attempt {
+    /// formats the attempt as a line of the ledger
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.year,
+            self.day,
+            self.part,
+            escape(&self.answer),
+            self.outcome.token(),
+            self.time,
+            self.wait.as_secs()
+        )
+    }
+}
+
+/// `answer` with backslashes, tabs and line breaks written as `\\`, `\t`, `\n` and `\r`, so a
+/// multi-line answer stays on one line of the ledger
+fn escape(answer: &str) -> String {
+    let mut escaped = String::with_capacity(answer.len());
+    for c in answer.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// the answer written by `escape` as `field`
+fn unescape(field: &str) -> Result<String, ParseError> {
+    let mut answer = String::with_capacity(field.len());
+    let mut chars = field.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c != '\\' {
+            answer.push(c);
+            continue;
+        }
+        let escaped = chars.next().map(|(_, e)| e);
+        answer.push(match escaped {
+            Some('\\') => '\\',
+            Some('t') => '\t',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            _ => {
+                let text = &field[i..i + 1 + escaped.map_or(0, char::len_utf8)];
+                return Err(ParseError::new(text, "one of \\\\, \\t, \\n or \\r").locate(field, text));
+            }
+        });
+    }
+    Ok(answer)
+}
+
+/// An error submitting an answer, or a refusal to submit it
+#[derive(Debug)]
+pub enum SubmitError {
+    /// the part was already solved with this answer
+    AlreadySolved(String),
+    /// the answer is already known to be wrong, from this earlier attempt
+    KnownWrong(Attempt),
+    /// the server asked us to wait this much longer before submitting again
+    Wait(Duration),
+    EmptyAnswer,
+    /// the server's response could not be understood
+    UnknownResponse(String),
+    Client(ClientError),
+    Ledger(PathBuf, String),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::AlreadySolved(answer) => write!(f, "already solved, the answer is {}", answer),
+            SubmitError::KnownWrong(attempt) => write!(
+                f,
+                "the answer is known to be wrong, {} was submitted before: {}",
+                attempt.answer,
+                attempt.outcome
+            ),
+            SubmitError::Wait(wait) => write!(f, "wait {}s before submitting again", wait.as_secs()),
+            SubmitError::EmptyAnswer => f.write_str("the answer is empty"),
+            SubmitError::UnknownResponse(body) => write!(f, "could not understand the response: {}", body),
+            SubmitError::Client(e) => e.fmt(f),
+            SubmitError::Ledger(path, e) => write!(f, "answers ledger {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl Error for SubmitError {}
+
+/// A record of every answer submitted, kept as tab separated lines in a file
+#[derive(Debug)]
+pub struct Ledger {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl Ledger {
+    /// open the ledger at `path`, which is created when the first attempt is recorded
+    pub fn open(path: &Path) -> Result<Self, SubmitError> {
+        let attempts = match std::fs::read_to_string(path) {
+            Ok(text) => text
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(idx, line)| Attempt::parse(line).map_err(|e| e.on_line(idx + 1)))
+                .collect::<Result<Vec<Attempt>, ParseError>>()
+                .map_err(|e| SubmitError::Ledger(path.to_path_buf(), e.to_string()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(SubmitError::Ledger(path.to_path_buf(), e.to_string())),
+        };
+        Ok(Self { path: path.to_path_buf(), attempts })
+    }
+
+    /// all attempts, oldest first
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// the correct answer to `part` of `day`, if it has been found
+    pub fn correct_answer(&self, year: u16, day: u8, part: Part) -> Option<&str> {
+        self.attempts
+            .iter()
+            .find(|a| (a.year, a.day, a.part) == (year, day, part) && a.outcome == Outcome::Correct)
+            .map(|a| a.answer.as_str())
+    }
+
+    /// check that `answer` is worth submitting at time `now`: the part isn't solved yet, the
+    /// answer isn't known to be wrong, and the server isn't asking us to wait
+    pub fn check(&self, year: u16, day: u8, part: Part, answer: &str, now: SystemTime) -> Result<(), SubmitError> {
+        if let Some(correct) = self.correct_answer(year, day, part) {
+            return Err(SubmitError::AlreadySolved(correct.to_string()));
+        }
+
+        let number = answer.parse::<i128>().ok();
+        let known_wrong = self
+            .attempts
+            .iter()
+            .filter(|a| (a.year, a.day, a.part) == (year, day, part) && a.outcome.is_wrong())
+            .find(|a| {
+                let bound = a.answer.parse::<i128>().ok();
+                a.answer == answer
+                    || match (a.outcome, number, bound) {
+                        (Outcome::TooHigh, Some(n), Some(high)) => n >= high,
+                        (Outcome::TooLow, Some(n), Some(low)) => n <= low,
+                        _ => false,
+                    }
+            });
+        if let Some(attempt) = known_wrong {
+            return Err(SubmitError::KnownWrong(attempt.clone()));
+        }
+
+        if let Some(last) = self.attempts.last() {
+            let resume = UNIX_EPOCH + Duration::from_secs(last.time) + last.wait;
+            if let Ok(remaining) = resume.duration_since(now) {
+                if !remaining.is_zero() {
+                    return Err(SubmitError::Wait(remaining));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// add `attempt` to the ledger and append it to the ledger file
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), SubmitError> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{}", attempt))
+            .map_err(|e| SubmitError::Ledger(self.path.clone(), e.to_string()))?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+/// Submit `answer` for `part` of `day`, recording the attempt in `ledger`. Answers that are
+/// known to be wrong, for parts that are already solved, or while the server has asked us to
+/// wait, are not submitted
+pub fn submit_answer(
+    client: &Client,
+    ledger: &mut Ledger,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> Result<Verdict, SubmitError> {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        return Err(SubmitError::EmptyAnswer);
+    }
+    let now = SystemTime::now();
+    ledger.check(year, day, part, answer, now)?;
+
+    let body = client.post_answer(year, day, part, answer).map_err(SubmitError::Client)?;
+    let verdict = Verdict::parse(&body).ok_or_else(|| SubmitError::UnknownResponse(body.trim().to_string()))?;
+    ledger.record(Attempt {
+        year,
+        day,
+        part,
+        answer: answer.to_string(),
+        outcome: verdict.outcome,
+        time: now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+        wait: verdict.wait.unwrap_or_default(),
+    })?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use crate::client::Client;
+    use crate::solution::Part;
+    use crate::submit::{submit_answer, Attempt, Ledger, Outcome, SubmitError, Verdict, LEDGER_FILE};
+    use crate::test_util::{scratch_dir, TestServer};
+
+    fn page(text: &str) -> String {
+        format!("<html><body><main><article><p>{}</p></article></main></body></html>", text)
+    }
+
+    fn attempt(answer: &str, outcome: Outcome, time: u64, wait: u64) -> Attempt {
+        Attempt {
+            year: 2022,
+            day: 1,
+            part: Part::One,
+            answer: answer.to_string(),
+            outcome,
+            time,
+            wait: Duration::from_secs(wait),
+        }
+    }
+
+    #[test]
+    fn parse_verdicts() {
+        let verdict = |text: &str| Verdict::parse(&page(text)).unwrap();
+        assert_eq!(verdict("That's the right answer!  You are one gold star closer.").outcome, Outcome::Correct);
+
+        let high = verdict("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.");
+        assert_eq!(high, Verdict { outcome: Outcome::TooHigh, wait: Some(Duration::from_secs(60)) });
+        let low = verdict("That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again.");
+        assert_eq!(low, Verdict { outcome: Outcome::TooLow, wait: Some(Duration::from_secs(300)) });
+        assert_eq!(verdict("That's not the right answer.").outcome, Outcome::Wrong);
+
+        let limited = verdict("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait.");
+        assert_eq!(limited, Verdict { outcome: Outcome::RateLimited, wait: Some(Duration::from_secs(272)) });
+        assert_eq!(verdict("You have 9s left to wait. You gave an answer too recently").wait, Some(Duration::from_secs(9)));
+        assert_eq!(verdict("You don't seem to be solving the right level.  Did you already complete it?").outcome, Outcome::WrongLevel);
+        assert!(Verdict::parse("<html>something else</html>").is_none());
+    }
+
+    #[test]
+    fn ledger_round_trip() {
+        let path = scratch_dir("ledger").join(LEDGER_FILE);
+        let mut ledger = Ledger::open(&path).unwrap();
+        assert!(ledger.attempts().is_empty());
+        ledger.record(attempt("500", Outcome::TooHigh, 1_670_000_000, 60)).unwrap();
+        ledger.record(attempt("69177", Outcome::Correct, 1_670_000_100, 0)).unwrap();
+
+        let reopened = Ledger::open(&path).unwrap();
+        assert_eq!(reopened.attempts(), ledger.attempts());
+        assert_eq!(reopened.correct_answer(2022, 1, Part::One), Some("69177"));
+        assert_eq!(reopened.correct_answer(2022, 1, Part::Two), None);
+
+        std::fs::write(&path, "2022\t1\t3\t5\twrong\t0\t0\n").unwrap();
+        assert!(matches!(Ledger::open(&path), Err(SubmitError::Ledger(..))));
+    }
+
+    #[test]
+    fn escape_answers_in_the_ledger() {
+        let path = scratch_dir("ledger-escapes").join(LEDGER_FILE);
+        let mut ledger = Ledger::open(&path).unwrap();
+        let answer = "##..\n#..#\ta\\tb\r";
+        ledger.record(attempt(answer, Outcome::Wrong, 1_670_000_000, 0)).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+        assert_eq!(Ledger::open(&path).unwrap().attempts(), ledger.attempts());
+        assert_eq!(ledger.attempts()[0].answer, answer);
+
+        let err = Attempt::parse("2022\t1\t1\ta\\x\twrong\t0\t0").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (11, "\\x"));
+        assert!(Attempt::parse("2022\t1\t1\ta\\\twrong\t0\t0").is_err());
+    }
+
+    #[test]
+    fn refuse_known_wrong_answers() {
+        let mut ledger = Ledger::open(&scratch_dir("known-wrong").join(LEDGER_FILE)).unwrap();
+        ledger.record(attempt("500", Outcome::TooHigh, 0, 0)).unwrap();
+        ledger.record(attempt("100", Outcome::TooLow, 0, 0)).unwrap();
+        ledger.record(attempt("abc", Outcome::Wrong, 0, 0)).unwrap();
+        let now = SystemTime::now();
+        let check = |answer: &str| ledger.check(2022, 1, Part::One, answer, now);
+
+        assert!(matches!(check("500"), Err(SubmitError::KnownWrong(a)) if a.answer == "500"));
+        assert!(matches!(check("501"), Err(SubmitError::KnownWrong(a)) if a.answer == "500"));
+        assert!(matches!(check("99"), Err(SubmitError::KnownWrong(a)) if a.answer == "100"));
+        assert!(matches!(check("abc"), Err(SubmitError::KnownWrong(_))));
+        assert!(check("250").is_ok());
+        assert!(ledger.check(2022, 1, Part::Two, "500", now).is_ok());
+    }
+
+    #[test]
+    fn refuse_while_waiting_or_solved() {
+        let mut ledger = Ledger::open(&scratch_dir("waiting").join(LEDGER_FILE)).unwrap();
+        ledger.record(attempt("7", Outcome::RateLimited, 1_000, 30)).unwrap();
+        let at = |secs: u64| UNIX_EPOCH + Duration::from_secs(secs);
+        assert!(matches!(ledger.check(2022, 1, Part::One, "8", at(1_010)), Err(SubmitError::Wait(w)) if w == Duration::from_secs(20)));
+        assert!(ledger.check(2022, 1, Part::One, "7", at(1_030)).is_ok());
+
+        ledger.record(attempt("8", Outcome::Correct, 1_040, 0)).unwrap();
+        assert!(matches!(ledger.check(2022, 1, Part::One, "9", at(2_000)), Err(SubmitError::AlreadySolved(a)) if a == "8"));
+    }
+
+    #[test]
+    fn submit_and_record() {
+        let server = TestServer::start(vec![
+            (200, vec![], page("That's not the right answer; your answer is too low.")),
+            (200, vec![], page("That's the right answer!")),
+        ]);
+        let state = scratch_dir("submit-client").join("last-request");
+        let client = Client::new(&server.base_url, Some("abc123".to_string())).with_request_interval(Duration::ZERO, &state);
+        let mut ledger = Ledger::open(&scratch_dir("submit").join(LEDGER_FILE)).unwrap();
+
+        let verdict = submit_answer(&client, &mut ledger, 2022, 4, Part::Two, "900\n").unwrap();
+        assert_eq!(verdict.outcome, Outcome::TooLow);
+        assert!(matches!(submit_answer(&client, &mut ledger, 2022, 4, Part::Two, "800"), Err(SubmitError::KnownWrong(_))));
+        let verdict = submit_answer(&client, &mut ledger, 2022, 4, Part::Two, "933").unwrap();
+        assert_eq!(verdict.outcome, Outcome::Correct);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!((requests[0].method.as_str(), requests[0].path.as_str()), ("POST", "/2022/day/4/answer"));
+        assert_eq!(requests[0].body, "level=2&answer=900");
+        assert_eq!(ledger.attempts().len(), 2);
+        assert_eq!(ledger.correct_answer(2022, 4, Part::Two), Some("933"));
+    }
+}
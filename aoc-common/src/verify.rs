@@ -0,0 +1,160 @@
+use std::fmt::{Display, Formatter};
+use crate::answers::Answers;
+use crate::input::{InputError, InputKind, InputLocator};
+use crate::solution::{Day, Part};
+
+/// the result of checking one answer against the answers file
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// there is no known answer, or no input to check it with
+    Missing,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+        })
+    }
+}
+
+/// what a solution produced for one part of an input
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Actual {
+    Answer(String),
+    NoInput,
+    ParseError(String),
+    /// the input parsed, but the part has no answer for it
+    NoAnswer(String),
+}
+
+impl Display for Actual {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Actual::Answer(answer) => f.write_str(answer),
+            Actual::NoInput => f.write_str("(no input)"),
+            Actual::ParseError(e) => write!(f, "(parse error: {})", e),
+            Actual::NoAnswer(e) => write!(f, "(no answer: {})", e),
+        }
+    }
+}
+
+/// One part of a day's solution run against an input, with the answer it is expected to give
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub kind: InputKind,
+    pub expected: Option<String>,
+    pub actual: Actual,
+}
+
+impl Check {
+    pub fn status(&self) -> Status {
+        match (&self.expected, &self.actual) {
+            (None, _) | (_, Actual::NoInput) => Status::Missing,
+            (Some(expected), Actual::Answer(actual)) if expected == actual => Status::Pass,
+            _ => Status::Fail,
+        }
+    }
+}
+
+/// Run every part of `days` against the real input, and every part with a known answer against
+/// its example inputs, and check the results against `answers`. Example parts without an answer
+/// are skipped, as a puzzle's example often only fits one of its parts.
+pub fn verify(days: &[Day], locator: &InputLocator, answers: &Answers) -> Vec<Check> {
+    let mut checks = vec![];
+    for day in days {
+        let mut kinds = vec![InputKind::Real];
+        kinds.extend(answers.kinds(day.day).into_iter().filter(|&k| k != InputKind::Real));
+        for kind in kinds {
+            let parts: Vec<Part> = Part::BOTH.into_iter()
+                .filter(|&part| kind == InputKind::Real || answers.get(day.day, part, kind).is_some())
+                .collect();
+            let actual: Vec<Actual> = match locator.read(day.day, kind) {
+                Ok(input) => match day.parse(&input) {
+                    Ok(parsed) => parts.iter()
+                        .map(|&part| parsed.solve(part).map_or_else(Actual::NoAnswer, Actual::Answer))
+                        .collect(),
+                    Err(e) => parts.iter().map(|_| Actual::ParseError(e.to_string())).collect(),
+                },
+                Err(InputError::NotFound { .. }) => parts.iter().map(|_| Actual::NoInput).collect(),
+                Err(e) => parts.iter().map(|_| Actual::ParseError(e.to_string())).collect(),
+            };
+            for (part, actual) in parts.into_iter().zip(actual) {
+                let expected = answers.get(day.day, part, kind).map(String::from);
+                checks.push(Check { day: day.day, part, kind, expected, actual });
+            }
+        }
+    }
+    checks
+}
+
+/// format `checks` as a table, one row per check, followed by a count of each status
+pub fn table(checks: &[Check]) -> String {
+    let header = ["day", "part", "input", "expected", "actual", "status"];
+    let mut rows: Vec<[String; 6]> = vec![header.map(String::from)];
+    for check in checks {
+        rows.push([
+            check.day.to_string(),
+            check.part.to_string(),
+            check.kind.to_string(),
+            check.expected.clone().unwrap_or_else(|| "-".to_string()),
+            check.actual.to_string(),
+            check.status().to_string(),
+        ]);
+    }
+    let mut widths = [0; 6];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut out = String::new();
+    for row in &rows {
+        let cells: Vec<String> = row.iter().zip(widths).map(|(cell, width)| format!("{:<width$}", cell)).collect();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+    let count = |status| checks.iter().filter(|c| c.status() == status).count();
+    out.push_str(&format!(
+        "{} passed, {} failed, {} missing\n",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Missing)
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::InputKind;
+    use crate::solution::Part;
+    use crate::verify::{table, Actual, Check, Status};
+
+    #[test]
+    fn check_status() {
+        let check = |expected: Option<&str>, actual| Check {
+            day: 1,
+            part: Part::One,
+            kind: InputKind::Real,
+            expected: expected.map(String::from),
+            actual,
+        };
+        assert_eq!(check(Some("5"), Actual::Answer("5".to_string())).status(), Status::Pass);
+        assert_eq!(check(Some("5"), Actual::Answer("6".to_string())).status(), Status::Fail);
+        assert_eq!(check(Some("5"), Actual::ParseError("bad".to_string())).status(), Status::Fail);
+        assert_eq!(check(Some("5"), Actual::NoAnswer("overflow".to_string())).status(), Status::Fail);
+        assert_eq!(check(Some("5"), Actual::NoInput).status(), Status::Missing);
+        assert_eq!(check(None, Actual::Answer("5".to_string())).status(), Status::Missing);
+
+        let table = table(&[check(Some("5"), Actual::Answer("6".to_string()))]);
+        assert_eq!(table, "day  part  input  expected  actual  status\n1    1     real   5         6       FAIL\n0 passed, 1 failed, 0 missing\n");
+    }
+
+}
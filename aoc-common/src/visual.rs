@@ -0,0 +1,487 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Write as _};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use crate::geometry::Point2;
+use crate::parse_error::ParseError;
+
+/// a colour, as red, green and blue
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(110, 110, 110);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(64, 160, 43);
+    pub const BLUE: Rgb = Rgb(38, 139, 210);
+    pub const YELLOW: Rgb = Rgb(230, 190, 40);
+
+    /// the colour `t` of the way from this colour to `to`, for `t` from 0 to 1
+    pub fn blend(self, to: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+        Rgb(mix(self.0, to.0), mix(self.1, to.1), mix(self.2, to.2))
+    }
+}
+
+/// a cell of a canvas: the glyph shown for it in a terminal, and its colour
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Rgb,
+}
+
+impl Cell {
+    pub const BLANK: Cell = Cell { glyph: ' ', colour: Rgb::BLACK };
+}
+
+/// One frame of a visualisation, a grid of cells with (0, 0) at the top left
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Canvas {
+    /// a canvas of blank cells
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { width, height, cells: vec![Cell::BLANK; width * height] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// draw `glyph` in `colour` at column `x` of row `y`. Nothing is drawn off the canvas
+    pub fn draw(&mut self, x: usize, y: usize, glyph: char, colour: Rgb) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = Cell { glyph, colour };
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Cell> {
+        (x < self.width && y < self.height).then(|| self.cells[y * self.width + x])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.width.max(1))
+    }
+}
+
+/// Maps puzzle coordinates, where +y is north, onto the cells of a canvas. When the points are
+/// spread wider than the largest canvas wanted, each cell covers a square of several points
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Viewport {
+    /// the west and north edges of the view
+    left: i64,
+    top: i64,
+    /// the points across each cell
+    scale: i64,
+    width: usize,
+    height: usize,
+}
+
+impl Viewport {
+    /// the view of every one of `points`, on a canvas at most `max_cells` cells across
+    pub fn fit(points: impl IntoIterator<Item = Point2>, max_cells: usize) -> Self {
+        let mut points = points.into_iter();
+        let first = points.next().unwrap_or(Point2::ORIGIN);
+        let (min, max) = points.fold((first, first), |(min, max), p| {
+            (Point2::new(min.x.min(p.x), min.y.min(p.y)), Point2::new(max.x.max(p.x), max.y.max(p.y)))
+        });
+        let span = (max.x - min.x).max(max.y - min.y) + 1;
+        let max_cells = max_cells.max(1) as i64;
+        let scale = (span + max_cells - 1) / max_cells;
+        Self {
+            left: min.x,
+            top: max.y,
+            scale,
+            width: ((max.x - min.x) / scale + 1) as usize,
+            height: ((max.y - min.y) / scale + 1) as usize,
+        }
+    }
+
+    /// a blank canvas the size of the view
+    pub fn canvas(&self) -> Canvas {
+        Canvas::new(self.width, self.height)
+    }
+
+    /// the column and row of the cell showing `p`, if it is in view
+    pub fn cell(&self, p: Point2) -> Option<(usize, usize)> {
+        let x = (p.x - self.left).div_euclid(self.scale);
+        let y = (self.top - p.y).div_euclid(self.scale);
+        ((0..self.width as i64).contains(&x) && (0..self.height as i64).contains(&y)).then_some((x as usize, y as usize))
+    }
+
+    /// draw `glyph` in `colour` on the cell showing `p`
+    pub fn draw(&self, canvas: &mut Canvas, p: Point2, glyph: char, colour: Rgb) {
+        if let Some((x, y)) = self.cell(p) {
+            canvas.draw(x, y, glyph, colour);
+        }
+    }
+}
+
+/// Receives the frames a simulation draws, one for each step it shows
+pub trait Frames {
+    fn frame(&mut self, canvas: &Canvas);
+}
+
+impl Frames for Vec<Canvas> {
+    fn frame(&mut self, canvas: &Canvas) {
+        self.push(canvas.clone());
+    }
+}
+
+/// the ways frames can be shown or saved
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    /// an animation in the terminal, drawn with ANSI colour codes
+    Ansi,
+    /// a PNG image for each frame
+    Png,
+    /// one animated GIF
+    Gif,
+    /// an SVG image for each frame
+    Svg,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Format::Ansi => "ansi",
+            Format::Png => "png",
+            Format::Gif => "gif",
+            Format::Svg => "svg",
+        })
+    }
+}
+
+impl FromStr for Format {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ansi" => Ok(Format::Ansi),
+            "png" => Ok(Format::Png),
+            "gif" => Ok(Format::Gif),
+            "svg" => Ok(Format::Svg),
+            _ => Err(ParseError::new(s, "one of ansi, png, gif, svg")),
+        }
+    }
+}
+
+/// the canvas as lines of text, coloured with ANSI escape codes
+pub fn ansi(canvas: &Canvas) -> String {
+    let mut text = String::new();
+    for row in canvas.rows() {
+        for cell in row {
+            let Rgb(r, g, b) = cell.colour;
+            write!(text, "\x1b[38;2;{};{};{}m{}", r, g, b, cell.glyph).unwrap();
+        }
+        text.push_str("\x1b[0m\n");
+    }
+    text
+}
+
+/// the canvas as an SVG image, each cell a square `scale` pixels across. Runs of cells of the
+/// same colour along a row are drawn as one rectangle
+pub fn svg(canvas: &Canvas, scale: usize) -> String {
+    let (width, height) = (canvas.width() * scale, canvas.height() * scale);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" shape-rendering=\"crispEdges\">\n",
+        width, height
+    );
+    writeln!(svg, "<rect width=\"{}\" height=\"{}\" fill=\"#000000\"/>", width, height).unwrap();
+    for (y, row) in canvas.rows().enumerate() {
+        let mut x = 0;
+        for run in row.chunk_by(|a, b| a.colour == b.colour) {
+            let Rgb(r, g, b) = run[0].colour;
+            if run[0].colour != Rgb::BLACK {
+                writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#{:02x}{:02x}{:02x}\"/>",
+                    x * scale, y * scale, run.len() * scale, scale, r, g, b
+                ).unwrap();
+            }
+            x += run.len();
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// the red, green and blue bytes of each pixel of the canvas, each cell a square `scale` pixels
+/// across
+fn pixels(canvas: &Canvas, scale: usize) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity(canvas.width() * canvas.height() * scale * scale * 3);
+    for row in canvas.rows().take(canvas.height()) {
+        let line: Vec<u8> = row.iter()
+            .flat_map(|cell| std::iter::repeat_n([cell.colour.0, cell.colour.1, cell.colour.2], scale))
+            .flatten()
+            .collect();
+        (0..scale).for_each(|_| pixels.extend_from_slice(&line));
+    }
+    pixels
+}
+
+/// write the canvas to `out` as a PNG image, each cell a square `scale` pixels across
+pub fn write_png(canvas: &Canvas, scale: usize, out: impl Write) -> std::io::Result<()> {
+    let mut encoder = png::Encoder::new(out, (canvas.width() * scale) as u32, (canvas.height() * scale) as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels(canvas, scale)))
+        .map_err(std::io::Error::other)
+}
+
+/// a GIF frame of the canvas, with a palette of its colours
+fn gif_frame(canvas: &Canvas, scale: usize, delay: Duration) -> gif::Frame<'static> {
+    let (width, height) = ((canvas.width() * scale) as u16, (canvas.height() * scale) as u16);
+    let pixels = pixels(canvas, scale);
+    let mut palette: HashMap<[u8; 3], u8> = HashMap::new();
+    let indexed: Option<Vec<u8>> = pixels.chunks(3)
+        .map(|p| {
+            let (rgb, next) = ([p[0], p[1], p[2]], palette.len());
+            match palette.get(&rgb) {
+                Some(&index) => Some(index),
+                None if next < 256 => Some(*palette.entry(rgb).or_insert(next as u8)),
+                None => None,
+            }
+        })
+        .collect();
+    let mut frame = match indexed {
+        Some(indexed) => {
+            let mut colours = vec![0; palette.len() * 3];
+            for (rgb, &index) in &palette {
+                colours[index as usize * 3..index as usize * 3 + 3].copy_from_slice(rgb);
+            }
+            let mut frame = gif::Frame::from_indexed_pixels(width, height, indexed, None);
+            frame.palette = Some(colours);
+            frame
+        }
+        // too many colours for one palette, so they are quantized
+        None => gif::Frame::from_rgb_speed(width, height, &pixels, 10),
+    };
+    frame.delay = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+    frame
+}
+
+/// a GIF being written, and the size of its frames in pixels
+type GifEncoder = (gif::Encoder<BufWriter<File>>, (usize, usize));
+
+enum Output {
+    /// each frame drawn over the last in a terminal
+    Terminal(Box<dyn Write>),
+    /// a numbered PNG or SVG file for each frame, in a directory
+    Images(PathBuf, Format),
+    /// an animated GIF, started at the first frame once its size is known
+    Gif(PathBuf, Option<GifEncoder>),
+}
+
+/// Shows or saves each frame as soon as it is drawn, so a long simulation is never kept in
+/// memory. The first error stops the output, and is returned by `finish`
+pub struct FrameWriter {
+    output: Output,
+    scale: usize,
+    delay: Duration,
+    frames: usize,
+    /// the height of the last frame drawn in the terminal
+    last_height: usize,
+    error: Option<std::io::Error>,
+}
+
+impl FrameWriter {
+    fn new(output: Output, scale: usize, delay: Duration) -> Self {
+        Self { output, scale: scale.max(1), delay, frames: 0, last_height: 0, error: None }
+    }
+
+    /// play the frames on `out`, a terminal, `delay` apart
+    pub fn terminal(out: impl Write + 'static, delay: Duration) -> Self {
+        Self::new(Output::Terminal(Box::new(out)), 1, delay)
+    }
+
+    /// write each frame to `dir` as a PNG or SVG image, `frame-00001.png` and so on, each cell a
+    /// square `scale` pixels across
+    pub fn images(dir: &Path, format: Format, scale: usize) -> Self {
+        Self::new(Output::Images(dir.to_path_buf(), format), scale, Duration::ZERO)
+    }
+
+    /// write the frames to `path` as an animated GIF that loops, showing each frame for `delay`
+    pub fn gif(path: &Path, scale: usize, delay: Duration) -> Self {
+        Self::new(Output::Gif(path.to_path_buf(), None), scale, delay)
+    }
+
+    fn write(&mut self, canvas: &Canvas) -> std::io::Result<()> {
+        match &mut self.output {
+            Output::Terminal(out) => {
+                if self.last_height > 0 {
+                    write!(out, "\x1b[{}A", self.last_height)?;
+                }
+                out.write_all(ansi(canvas).as_bytes())?;
+                out.flush()?;
+                self.last_height = canvas.height();
+                std::thread::sleep(self.delay);
+            }
+            Output::Images(dir, format) => {
+                if self.frames == 0 {
+                    std::fs::create_dir_all(&*dir)?;
+                }
+                let path = dir.join(format!("frame-{:05}.{}", self.frames + 1, format));
+                let mut out = BufWriter::new(File::create(path)?);
+                match format {
+                    Format::Svg => out.write_all(svg(canvas, self.scale).as_bytes())?,
+                    _ => write_png(canvas, self.scale, &mut out)?,
+                }
+                out.flush()?;
+            }
+            Output::Gif(path, encoder) => {
+                let (width, height) = (canvas.width() * self.scale, canvas.height() * self.scale);
+                if width > u16::MAX as usize || height > u16::MAX as usize {
+                    return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "frame too large for a GIF"));
+                }
+                let encoder = match encoder {
+                    Some((_, size)) if *size != (width, height) => {
+                        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "frames of a GIF must be the same size"));
+                    }
+                    Some((encoder, _)) => encoder,
+                    None => {
+                        let file = BufWriter::new(File::create(&*path)?);
+                        let mut new = gif::Encoder::new(file, width as u16, height as u16, &[]).map_err(std::io::Error::other)?;
+                        new.set_repeat(gif::Repeat::Infinite).map_err(std::io::Error::other)?;
+                        &mut encoder.insert((new, (width, height))).0
+                    }
+                };
+                encoder.write_frame(&gif_frame(canvas, self.scale, self.delay)).map_err(std::io::Error::other)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// finish the output, returning the number of frames written
+    pub fn finish(self) -> std::io::Result<usize> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        match self.output {
+            Output::Terminal(mut out) => out.flush()?,
+            // the trailer ends the GIF, so it is written before the file is flushed
+            Output::Gif(_, Some((encoder, _))) => encoder.into_inner()?.flush()?,
+            _ => (),
+        }
+        Ok(self.frames)
+    }
+}
+
+impl Frames for FrameWriter {
+    fn frame(&mut self, canvas: &Canvas) {
+        if self.error.is_none() {
+            match self.write(canvas) {
+                Ok(()) => self.frames += 1,
+                Err(e) => self.error = Some(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::geometry::Point2;
+    use crate::test_util::scratch_dir;
+    use crate::visual::{ansi, svg, write_png, Canvas, Cell, Format, FrameWriter, Frames, Rgb, Viewport};
+
+    fn checkerboard() -> Canvas {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.draw(0, 0, '#', Rgb::RED);
+        canvas.draw(1, 0, '#', Rgb::RED);
+        canvas.draw(2, 1, 'o', Rgb::WHITE);
+        canvas
+    }
+
+    #[test]
+    fn draw_on_the_canvas() {
+        let mut canvas = checkerboard();
+        canvas.draw(3, 0, 'x', Rgb::BLUE);
+        assert_eq!(canvas.get(1, 0), Some(Cell { glyph: '#', colour: Rgb::RED }));
+        assert_eq!(canvas.get(0, 1), Some(Cell::BLANK));
+        assert_eq!(canvas.get(3, 0), None);
+        assert_eq!(canvas.rows().count(), 2);
+        assert_eq!(Rgb::BLACK.blend(Rgb::WHITE, 0.5), Rgb(128, 128, 128));
+    }
+
+    #[test]
+    fn viewport_fits_the_points() {
+        let view = Viewport::fit([Point2::new(-2, 3), Point2::new(2, -1)], 10);
+        assert_eq!(view.canvas().width(), 5);
+        assert_eq!(view.canvas().height(), 5);
+        // north is up
+        assert_eq!(view.cell(Point2::new(-2, 3)), Some((0, 0)));
+        assert_eq!(view.cell(Point2::new(2, -1)), Some((4, 4)));
+        assert_eq!(view.cell(Point2::new(3, 0)), None);
+
+        let view = Viewport::fit([Point2::new(0, 0), Point2::new(99, 9)], 10);
+        assert_eq!((view.canvas().width(), view.canvas().height()), (10, 1));
+        assert_eq!(view.cell(Point2::new(55, 0)), Some((5, 0)));
+    }
+
+    #[test]
+    fn render_ansi_and_svg() {
+        let text = ansi(&checkerboard());
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.starts_with("\x1b[38;2;220;50;47m#\x1b[38;2;220;50;47m#\x1b[38;2;0;0;0m "));
+
+        let image = svg(&checkerboard(), 4);
+        assert!(image.contains("width=\"12\" height=\"8\""));
+        assert!(image.contains("<rect x=\"0\" y=\"0\" width=\"8\" height=\"4\" fill=\"#dc322f\"/>"));
+        assert!(image.contains("<rect x=\"8\" y=\"4\" width=\"4\" height=\"4\" fill=\"#ffffff\"/>"));
+    }
+
+    #[test]
+    fn write_a_png() {
+        let mut png = vec![];
+        write_png(&checkerboard(), 2, &mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+        let decoder = png::Decoder::new(png.as_slice());
+        let reader = decoder.read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (6, 4));
+    }
+
+    #[test]
+    fn write_frames_to_files() {
+        let dir = scratch_dir("frames");
+        let mut images = FrameWriter::images(&dir.join("svg"), Format::Svg, 2);
+        images.frame(&checkerboard());
+        images.frame(&Canvas::new(3, 2));
+        assert_eq!(images.finish().unwrap(), 2);
+        assert!(dir.join("svg/frame-00002.svg").is_file());
+
+        let mut gif = FrameWriter::gif(&dir.join("frames.gif"), 2, Duration::from_millis(100));
+        gif.frame(&checkerboard());
+        gif.frame(&Canvas::new(3, 2));
+        assert_eq!(gif.finish().unwrap(), 2);
+        let bytes = std::fs::read(dir.join("frames.gif")).unwrap();
+        assert!(bytes.starts_with(b"GIF89a"));
+        assert_eq!(bytes.last(), Some(&0x3b));
+
+        let mut gif = FrameWriter::gif(&dir.join("sizes.gif"), 2, Duration::ZERO);
+        gif.frame(&checkerboard());
+        gif.frame(&Canvas::new(4, 2));
+        assert!(gif.finish().is_err());
+    }
+
+    #[test]
+    fn parse_formats() {
+        assert_eq!("gif".parse::<Format>().unwrap(), Format::Gif);
+        assert!("jpeg".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_2020::DAYS;
+
+// every 2020 day's parser must return an error, not panic, on text that isn't a puzzle input
+fuzz_target!(|input: &str| {
+    for day in DAYS {
+        let _ = day.parse(input);
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_2022::DAYS;
+
+// every 2022 day's parser must return an error, not panic, on text that isn't a puzzle input
+fuzz_target!(|input: &str| {
+    for day in DAYS {
+        let _ = day.parse(input);
+    }
+});
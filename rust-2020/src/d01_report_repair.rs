@@ -0,0 +1,101 @@
+// Day 1 - Advent of Code
+// https://adventofcode.com/2020/day/1
+
+use aoc_common::parse_error::{parse_token, ParseError};
+use aoc_common::solution::Solution;
+
+/// parse the expense report entries, one integer per line, into a sorted list
+fn parse_entries(input: &str) -> Result<Vec<i32>, ParseError> {
+    let mut entries = input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_token::<i32>(line, "an expense entry").map_err(|e| e.on_line(idx + 1)))
+        .collect::<Result<Vec<i32>, ParseError>>()?;
+    entries.sort();
+    Ok(entries)
+}
+
+/// return all triplets of integers that add up to `target_sum`. ls must be a sorted list
+fn three_sum(ls: &[i32], target_sum: i32) -> Vec<(i32, i32, i32)> {
+    let mut triples: Vec<(i32, i32, i32)> = vec![];
+
+    for i in 0..ls.len().saturating_sub(1) {
+        let Some(target) = target_sum.checked_sub(ls[i]) else { continue };
+        let pairs = two_sum(&ls[i + 1..], target);
+        for pair in pairs {
+            triples.push((ls[i], pair.0, pair.1));
+        }
+    }
+    triples
+}
+
+/// find two entries in ls that sum to the `target_sum`. ls must be a sorted list
+fn two_sum(ls: &[i32], target_sum: i32) -> Vec<(i32, i32)> {
+    let mut results: Vec<(i32, i32)> = vec![];
+    for i in 0..ls.len().saturating_sub(1) {
+        // no entry can make up the difference to an entry that far from the sum
+        let Some(target) = target_sum.checked_sub(ls[i]) else { continue };
+        let sub = &ls[i + 1..];
+        if let Ok(n) = sub.binary_search(&target) {
+            results.push((ls[i], sub[n]));
+        }
+    }
+    results
+}
+
+/// Day 01 - Report Repair
+pub struct ReportRepair;
+
+impl Solution for ReportRepair {
+    type Input = Vec<i32>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_entries(input)
+    }
+
+    /// the product of the two entries that sum to 2020
+    fn part1(entries: &Vec<i32>) -> i64 {
+        two_sum(entries, 2020).iter()
+            .map(|&(a, b)| a as i64 * b as i64)
+            .product()
+    }
+
+    /// the product of the three entries that sum to 2020
+    fn part2(entries: &Vec<i32>) -> i64 {
+        three_sum(entries, 2020).iter()
+            .map(|&(a, b, c)| a as i64 * b as i64 * c as i64)
+            .product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use aoc_common::solution::Solution;
+    use crate::d01_report_repair::{parse_entries, three_sum, two_sum, ReportRepair};
+
+    #[test]
+    fn example_entries() {
+        let entries = parse_entries(&read_input(YEAR, 1, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(two_sum(&entries, 2020), vec![(299, 1721)]);
+        assert_eq!(three_sum(&entries, 2020), vec![(366, 675, 979)]);
+        assert_eq!(ReportRepair::part1(&entries), 514579);
+        assert_eq!(ReportRepair::part2(&entries), 241861950);
+    }
+
+    #[test]
+    fn bad_entry_is_located() {
+        let e = parse_entries("1721\n97x\n").unwrap_err();
+        assert_eq!(e.line, 2);
+    }
+
+    #[test]
+    fn extreme_entries_do_not_overflow() {
+        let entries = [i32::MIN, -5, 2025, i32::MAX];
+        assert_eq!(two_sum(&entries, 2020), vec![(-5, 2025)]);
+        assert!(three_sum(&entries, 2020).is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+// Day 2: Password Philosophy
+// https://adventofcode.com/2020/day/2
+
+use aoc_common::parse_error::ParseError;
+use aoc_common::scan::{self, Scanner};
+use aoc_common::solution::Solution;
+
+#[derive(Debug)]
+pub struct Policy {
+    min: usize,
+    max: usize,
+    chr: char,
+    pwd: String,
+}
+
+/// a 1-based position in the password
+fn position(line: &mut Scanner) -> Result<usize, ParseError> {
+    let digits = line.rest();
+    match line.unsigned("a position")? {
+        0 => Err(line.error(&digits[..1], "a position of at least 1")),
+        n => Ok(n),
+    }
+}
+
+/// parses a line of input, i,e:`12-13 n: nwnwdplnhfhlnnnntfn`, into a Policy struct
+fn parse_line(line: &mut Scanner) -> Result<Policy, ParseError> {
+    let min = position(line)?;
+    line.literal("-")?;
+    let max = position(line)?;
+    line.literal(" ")?;
+    let letter = line.take_while1(char::is_alphanumeric, "a letter")?;
+    let mut chars = letter.chars();
+    let (Some(chr), None) = (chars.next(), chars.next()) else {
+        return Err(line.error(letter, "a single letter"));
+    };
+    line.literal(": ")?;
+    let pwd = line.take_while1(char::is_alphanumeric, "a password")?.to_string();
+    Ok(Policy { min, max, chr, pwd })
+}
+
+fn parse_policies(input: &str) -> Result<Vec<Policy>, ParseError> {
+    scan::lines(input, parse_line)
+}
+
+/// the password has between min and max of the policy's character
+fn valid_policy(p: &Policy) -> bool {
+    let count = p.pwd.chars().filter(|c| *c == p.chr).count();
+    count >= p.min && count <= p.max
+}
+
+/// exactly one of the password's min and max positions has the policy's character
+fn valid_policy_position(p: &Policy) -> bool {
+    let c1 = p.pwd.chars().nth(p.min - 1);
+    let c2 = p.pwd.chars().nth(p.max - 1);
+    (Some(p.chr) == c1) ^ (Some(p.chr) == c2)
+}
+
+/// Day 02 - Password Philosophy
+pub struct PasswordPhilosophy;
+
+impl Solution for PasswordPhilosophy {
+    type Input = Vec<Policy>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_policies(input)
+    }
+
+    fn part1(policies: &Vec<Policy>) -> usize {
+        policies.iter().filter(|p| valid_policy(p)).count()
+    }
+
+    fn part2(policies: &Vec<Policy>) -> usize {
+        policies.iter().filter(|p| valid_policy_position(p)).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use aoc_common::solution::Solution;
+    use crate::d02_password_philosophy::{parse_policies, PasswordPhilosophy};
+
+    #[test]
+    fn example_policies() {
+        let policies = parse_policies(&read_input(YEAR, 2, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(PasswordPhilosophy::part1(&policies), 2);
+        assert_eq!(PasswordPhilosophy::part2(&policies), 1);
+    }
+
+    #[test]
+    fn zero_position_is_an_error() {
+        let e = parse_policies("1-3 a: abcde\n0-3 b: cdefg\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+    }
+
+    #[test]
+    fn malformed_policies() {
+        let e = parse_policies("1-3 ab: abcde").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (5, "ab"));
+        assert_eq!(parse_policies("1-3 a: abc de").unwrap_err().column, 11);
+        assert!(parse_policies("1 a: abcde").is_err());
+    }
+}
@@ -0,0 +1,69 @@
+// Day 3: Toboggan Trajectory
+// https://adventofcode.com/2020/day/3
+
+use aoc_common::grid::Grid;
+use aoc_common::parse_error::ParseError;
+use aoc_common::solution::Solution;
+
+/// count the trees hit going down the slope, `dr` rows and `dc` columns at a time. The map
+/// repeats endlessly to the right
+fn tree_count(slope: &Grid<bool>, dr: usize, dc: usize) -> usize {
+    (0..slope.height())
+        .step_by(dr)
+        .enumerate()
+        .filter(|&(step, r)| *slope.get_wrapping(r as isize, (step * dc) as isize))
+        .count()
+}
+
+/// parse the map into a grid that is true where there is a tree `#`, and false on the open
+/// squares `.`
+fn parse_map(input: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::parse(input, |c| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(ParseError::new(&c.to_string(), "an open square '.' or a tree '#'")),
+    })
+}
+
+/// Day 03 - Toboggan Trajectory
+pub struct TobogganTrajectory;
+
+impl Solution for TobogganTrajectory {
+    type Input = Grid<bool>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_map(input)
+    }
+
+    /// trees hit going right 3, down 1
+    fn part1(slope: &Grid<bool>) -> usize {
+        tree_count(slope, 1, 3)
+    }
+
+    /// the product of the trees hit on each of the slopes (rows, columns)
+    fn part2(slope: &Grid<bool>) -> usize {
+        [(1, 1), (1, 3), (1, 5), (1, 7), (2, 1)]
+            .iter()
+            .map(|&(dr, dc)| tree_count(slope, dr, dc))
+            .product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use aoc_common::solution::Solution;
+    use crate::d03_toboggan_trajectory::{parse_map, tree_count, TobogganTrajectory};
+
+    #[test]
+    fn example_slopes() {
+        let slope = parse_map(&read_input(YEAR, 3, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(tree_count(&slope, 1, 1), 2);
+        assert_eq!(tree_count(&slope, 2, 1), 2);
+        assert_eq!(TobogganTrajectory::part1(&slope), 7);
+        assert_eq!(TobogganTrajectory::part2(&slope), 336);
+    }
+}
@@ -0,0 +1,261 @@
+// Day 4: Passport Processing
+// https://adventofcode.com/2020/day/4
+
+use aoc_common::parse_error::ParseError;
+use aoc_common::scan::{blocks, Scanner};
+use aoc_common::solution::Solution;
+
+/// the fields every passport needs, `cid` is optional
+const REQUIRED_FIELDS: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+#[derive(Debug)]
+pub struct Passport {
+    byr: Option<String>,
+    iyr: Option<String>,
+    eyr: Option<String>,
+    hgt: Option<String>,
+    hcl: Option<String>,
+    ecl: Option<String>,
+    pid: Option<String>,
+    cid: Option<String>,
+    /// the names of every field given, valid or not
+    fields: Vec<String>,
+}
+
+impl Passport {
+    fn new() -> Self {
+        Passport {
+            byr: None,
+            iyr: None,
+            eyr: None,
+            hgt: None,
+            hcl: None,
+            ecl: None,
+            pid: None,
+            cid: None,
+            fields: vec![],
+        }
+    }
+
+    fn byr(&mut self, byr: Option<String>) -> &mut Passport {
+        self.byr = byr;
+        self
+    }
+
+    fn valid_byr(&self) -> bool {
+        self.byr
+            .as_ref()
+            .is_some_and(|year| (1920..=2002).contains(&year.parse::<i32>().unwrap()))
+    }
+
+    fn iyr(&mut self, iyr: Option<String>) -> &mut Passport {
+        self.iyr = iyr;
+        self
+    }
+
+    fn valid_iyr(&self) -> bool {
+        self.iyr
+            .as_ref()
+            .is_some_and(|year| (2010..=2020).contains(&year.parse::<i32>().unwrap()))
+    }
+
+    fn eyr(&mut self, eyr: Option<String>) -> &mut Passport {
+        self.eyr = eyr;
+        self
+    }
+
+    fn valid_eyr(&self) -> bool {
+        self.eyr
+            .as_ref()
+            .is_some_and(|year| (2020..=2030).contains(&year.parse::<i32>().unwrap()))
+    }
+
+    fn hgt(&mut self, hgt: Option<String>) -> &mut Passport {
+        self.hgt = hgt;
+        self
+    }
+
+    fn valid_hgt(&self) -> bool {
+        if let Some(hgt) = self.hgt.as_ref() {
+            if let Some(pos) = hgt.find("cm") {
+                hgt[0..pos].parse().is_ok_and(|cm: u32| (150..=193).contains(&cm))
+            } else if let Some(pos) = hgt.find("in") {
+                hgt[0..pos].parse().is_ok_and(|inches: u32| (59..=76).contains(&inches))
+            } else {
+                false
+            }
+        } else {
+            false
+        }
+    }
+
+    fn hcl(&mut self, hcl: Option<String>) -> &mut Passport {
+        self.hcl = hcl;
+        self
+    }
+
+    fn valid_hcl(&self) -> bool {
+        self.hcl.is_some()
+    }
+
+    fn ecl(&mut self, ecl: Option<String>) -> &mut Passport {
+        self.ecl = ecl;
+        self
+    }
+
+    fn valid_ecl(&self) -> bool {
+        self.ecl.is_some()
+    }
+
+    fn pid(&mut self, pid: Option<String>) -> &mut Passport {
+        self.pid = pid;
+        self
+    }
+
+    fn valid_pid(&self) -> bool {
+        self.pid.is_some()
+    }
+
+    fn cid(&mut self, cid: Option<String>) -> &mut Passport {
+        self.cid = cid;
+        self
+    }
+
+    /// passport has all the required fields, whatever their values. cid is optional
+    fn has_required_fields(&self) -> bool {
+        REQUIRED_FIELDS.iter().all(|name| self.fields.iter().any(|f| f == name))
+    }
+
+    /// passport is valid if all required fields are present, AND valid
+    /// cid field is ignored in this scenario
+    fn is_valid(&self) -> bool {
+        self.valid_byr()
+            && self.valid_ecl()
+            && self.valid_eyr()
+            && self.valid_hcl()
+            && self.valid_hgt()
+            && self.valid_iyr()
+            && self.valid_pid()
+    }
+}
+
+/// the value of the field `name`, if it has the format the field needs, i.e. four digits for a
+/// year. A value in any other format is left out
+fn well_formed(name: &str, mut value: Scanner) -> Option<String> {
+    let text = value.rest();
+    let digits = |value: &mut Scanner, count: usize| value.take_while(|c| c.is_ascii_digit()).len() == count;
+    let formed = match name {
+        "byr" | "iyr" | "eyr" => digits(&mut value, 4),
+        "hgt" => !value.take_while(|c| c.is_ascii_digit()).is_empty()
+            && value.one_of(&[("cm", ()), ("in", ())], "cm or in").is_ok(),
+        "hcl" => value.eat("#") && value.take_while(|c| matches!(c, '0'..='9' | 'a'..='f')).len() == 6,
+        "ecl" => {
+            let colours = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].map(|colour| (colour, ()));
+            value.one_of(&colours, "an eye colour").is_ok()
+        }
+        "pid" => digits(&mut value, 9),
+        _ => value.remainder("a value").is_ok(),
+    };
+    (formed && value.is_empty()).then(|| text.to_string())
+}
+
+/// parse the passports, which are separated by blank lines. A passport's `name:value` fields may
+/// be spread over several lines
+fn parse_passports(input: &str) -> Result<Vec<Passport>, ParseError> {
+    blocks(input).iter()
+        .map(|block| {
+            let mut passport = Passport::new();
+            for (name, value) in block.fields(':', "a field like 'byr:1937'")? {
+                if let Some(value) = well_formed(name, value) {
+                    let value = Some(value);
+                    match name {
+                        "byr" => passport.byr(value),
+                        "iyr" => passport.iyr(value),
+                        "eyr" => passport.eyr(value),
+                        "hgt" => passport.hgt(value),
+                        "hcl" => passport.hcl(value),
+                        "ecl" => passport.ecl(value),
+                        "pid" => passport.pid(value),
+                        "cid" => passport.cid(value),
+                        _ => &mut passport,
+                    };
+                }
+                passport.fields.push(name.to_string());
+            }
+            Ok(passport)
+        })
+        .collect()
+}
+
+/// Day 04 - Passport Processing
+pub struct PassportProcessing;
+
+impl Solution for PassportProcessing {
+    type Input = Vec<Passport>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_passports(input)
+    }
+
+    /// passports with all the required fields
+    fn part1(passports: &Vec<Passport>) -> usize {
+        passports.iter().filter(|p| p.has_required_fields()).count()
+    }
+
+    /// passports with all the required fields, and valid values in them
+    fn part2(passports: &Vec<Passport>) -> usize {
+        passports.iter().filter(|p| p.is_valid()).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use aoc_common::solution::Solution;
+    use aoc_common::scan::Scanner;
+    use crate::d04_passport_processing::{parse_passports, well_formed, PassportProcessing};
+
+    #[test]
+    fn example_required_fields() {
+        let passports = parse_passports(&read_input(YEAR, 4, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(passports.len(), 4);
+        assert_eq!(PassportProcessing::part1(&passports), 2);
+        assert_eq!(PassportProcessing::part2(&passports), 2);
+    }
+
+    #[test]
+    fn example_field_values() {
+        let invalid = parse_passports(&read_input(YEAR, 4, InputKind::Example(2)).unwrap()).unwrap();
+        assert_eq!(PassportProcessing::part1(&invalid), 4);
+        assert_eq!(PassportProcessing::part2(&invalid), 0);
+        let valid = parse_passports(&read_input(YEAR, 4, InputKind::Example(3)).unwrap()).unwrap();
+        assert_eq!(PassportProcessing::part2(&valid), 4);
+    }
+
+    #[test]
+    fn well_formed_values() {
+        let formed = |name, value| well_formed(name, Scanner::new(value)).is_some();
+        assert!(formed("byr", "2002") && !formed("byr", "20021"));
+        assert!(formed("hgt", "190in") && !formed("hgt", "190"));
+        assert!(formed("hcl", "#123abc") && !formed("hcl", "#123abz") && !formed("hcl", "123abc"));
+        assert!(formed("ecl", "brn") && !formed("ecl", "wat"));
+        assert!(formed("pid", "000000001") && !formed("pid", "0123456789"));
+        assert!(formed("cid", "147") && !formed("cid", ""));
+    }
+
+    #[test]
+    fn oversized_height_is_invalid() {
+        let passports = parse_passports("byr:1937 iyr:2017 eyr:2020 hgt:99999999999cm hcl:#fffffd ecl:gry pid:860033327\n").unwrap();
+        assert_eq!(PassportProcessing::part1(&passports), 1);
+        assert_eq!(PassportProcessing::part2(&passports), 0);
+    }
+
+    #[test]
+    fn field_without_value_is_an_error() {
+        let e = parse_passports("byr:1937\n\niyr:2017 cid\n").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 10));
+    }
+}
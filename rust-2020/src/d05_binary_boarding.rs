@@ -0,0 +1,141 @@
+// Day 5: Binary Boarding
+// https://adventofcode.com/2020/day/5
+
+use aoc_common::parse_error::ParseError;
+use aoc_common::solution::Solution;
+
+fn compute_row(s: &str) -> Result<u32, ParseError> {
+    s.char_indices()
+        .try_fold((0, 127), |(lo, hi), (i, c)| {
+            let mid = (lo + hi) / 2;
+            match c {
+                'F' => Ok((lo, mid)),
+                'B' => Ok((mid + 1, hi)),
+                _ => Err(ParseError::new(&s[i..i + c.len_utf8()], "a row character F or B")
+                    .locate(s, &s[i..])),
+            }
+        })
+        .map(|(lo, _hi)| lo)
+}
+
+fn compute_col(s: &str) -> Result<u32, ParseError> {
+    s.char_indices()
+        .try_fold((0, 7), |(lo, hi), (i, c)| {
+            let mid = (lo + hi) / 2;
+            match c {
+                'L' => Ok((lo, mid)),
+                'R' => Ok((mid + 1, hi)),
+                _ => Err(ParseError::new(&s[i..i + c.len_utf8()], "a column character L or R")
+                    .locate(s, &s[i..])),
+            }
+        })
+        .map(|(lo, _hi)| lo)
+}
+
+/// parse a boarding pass, i.e. `FBFBBFFRLR`, into its seat id
+fn parse_seat_id(line: &str) -> Result<u32, ParseError> {
+    let col_start_idx = line
+        .find(['R', 'L'])
+        .ok_or_else(|| ParseError::new(line, "a boarding pass like 'FBFBBFFRLR'"))?;
+    let (row_str, col_str) = line.split_at(col_start_idx);
+    let row = compute_row(row_str)?;
+    let col = compute_col(col_str).map_err(|e| e.locate(line, col_str))?;
+    Ok(seat_id(row, col))
+}
+
+fn seat_id(row: u32, col: u32) -> u32 {
+    row * 8 + col
+}
+
+fn parse_seat_ids(input: &str) -> Result<Vec<u32>, ParseError> {
+    let mut seats = input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_seat_id(line).map_err(|e| e.on_line(idx + 1)))
+        .collect::<Result<Vec<u32>, ParseError>>()?;
+    seats.sort();
+    Ok(seats)
+}
+
+/// Day 05 - Binary Boarding
+pub struct BinaryBoarding;
+
+impl Solution for BinaryBoarding {
+    /// the sorted seat ids of the boarding passes
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_seat_ids(input)
+    }
+
+    /// the highest seat id
+    fn part1(seats: &Vec<u32>) -> u32 {
+        *seats.last().expect("at least one boarding pass")
+    }
+
+    /// the one seat missing from the middle of the list
+    fn part2(seats: &Vec<u32>) -> u32 {
+        seats.windows(2)
+            .find(|w| w[0] + 1 != w[1])
+            .map(|w| w[0] + 1)
+            .expect("a missing seat between two boarding passes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use aoc_common::solution::Solution;
+    use super::compute_col;
+    use super::compute_row;
+    use super::{parse_seat_id, parse_seat_ids, seat_id, BinaryBoarding};
+
+    #[test]
+    fn compute_row_test() {
+        assert_eq!(compute_row("FBFBBF").unwrap(), 44);
+    }
+
+    #[test]
+    fn compute_col_test() {
+        assert_eq!(compute_col("RLR").unwrap(), 5);
+    }
+
+    #[test]
+    fn compute_col_test_all_r() {
+        assert_eq!(compute_col("RRR").unwrap(), 7);
+    }
+
+    #[test]
+    fn compute_col_test_all_l() {
+        assert_eq!(compute_col("LLL").unwrap(), 0);
+    }
+
+    #[test]
+    fn seat_id_test() {
+        assert_eq!(seat_id(44, 5), 357);
+    }
+
+    #[test]
+    fn parse_seat_id_test() {
+        assert_eq!(parse_seat_id("FBFBBFFRLR").unwrap(), 357);
+        assert_eq!(parse_seat_id("FBFXBFFRLR").unwrap_err().column, 4);
+        assert_eq!(parse_seat_id("FBFBBFFRXR").unwrap_err().column, 9);
+    }
+
+    #[test]
+    fn example_passes() {
+        let seats = parse_seat_ids(&read_input(YEAR, 5, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(seats, vec![119, 357, 567, 820]);
+        assert_eq!(BinaryBoarding::part1(&seats), 820);
+    }
+
+    #[test]
+    fn find_missing_seat() {
+        let seats = parse_seat_ids("FFFFFFBLRR\nFFFFFFBLLL\nFFFFFFBLLR\n").unwrap();
+        assert_eq!(seats, vec![8, 9, 11]);
+        assert_eq!(BinaryBoarding::part2(&seats), 10);
+    }
+}
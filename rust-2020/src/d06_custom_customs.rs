@@ -0,0 +1,87 @@
+// Day 6: Custom Customs
+// https://adventofcode.com/2020/day/6
+
+use std::collections::HashSet;
+use aoc_common::parse_error::ParseError;
+use aoc_common::solution::Solution;
+
+/// the questions one person answered "yes" to
+type Answers = HashSet<char>;
+
+/// parse the groups of answers, which are separated by blank lines. Each line of a group is one
+/// person's answers, a letter a to z for each question
+fn parse_groups(input: &str) -> Result<Vec<Vec<Answers>>, ParseError> {
+    let mut groups = vec![vec![]];
+    for (idx, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            groups.push(vec![]);
+            continue;
+        }
+        if let Some((i, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            let token = &line[i..i + c.len_utf8()];
+            return Err(ParseError::new(token, "a question from a to z").locate(line, token).on_line(idx + 1));
+        }
+        groups.last_mut().unwrap().push(line.chars().collect());
+    }
+    groups.retain(|group| !group.is_empty());
+    Ok(groups)
+}
+
+/// the questions anyone in the group answered "yes" to
+fn anyone(group: &[Answers]) -> Answers {
+    group.iter().flatten().copied().collect()
+}
+
+/// the questions everyone in the group answered "yes" to
+fn everyone(group: &[Answers]) -> Answers {
+    match group.split_first() {
+        Some((first, rest)) => rest.iter()
+            .fold(first.clone(), |acc, answers| &acc & answers),
+        None => Answers::new(),
+    }
+}
+
+/// Day 06 - Custom Customs
+pub struct CustomCustoms;
+
+impl Solution for CustomCustoms {
+    type Input = Vec<Vec<Answers>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_groups(input)
+    }
+
+    /// sum of the questions anyone in each group answered "yes" to
+    fn part1(groups: &Vec<Vec<Answers>>) -> usize {
+        groups.iter().map(|g| anyone(g).len()).sum()
+    }
+
+    /// sum of the questions everyone in each group answered "yes" to
+    fn part2(groups: &Vec<Vec<Answers>>) -> usize {
+        groups.iter().map(|g| everyone(g).len()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use aoc_common::solution::Solution;
+    use crate::d06_custom_customs::{parse_groups, CustomCustoms};
+
+    #[test]
+    fn example_groups() {
+        let groups = parse_groups(&read_input(YEAR, 6, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(groups.len(), 5);
+        assert_eq!(CustomCustoms::part1(&groups), 11);
+        assert_eq!(CustomCustoms::part2(&groups), 6);
+    }
+
+    #[test]
+    fn bad_answer_is_located() {
+        let e = parse_groups("abc\n\naB\n").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 2));
+    }
+}
@@ -0,0 +1,210 @@
+// Day 7 - Handy Haversacks
+// https://adventofcode.com/2020/day/7
+
+use aoc_common::parse_error::ParseError;
+use aoc_common::scan::{self, Scanner};
+use aoc_common::solution::Solution;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Clone)]
+pub struct Bag {
+    // number of bags contained, = 0 if this bag is a container bag
+    amount: i32,
+    // name of the container bag, or name of the bag being contained
+    name: String,
+}
+
+impl Bag {
+    fn new(amount: i32, name: String) -> Self {
+        Bag { amount, name }
+    }
+}
+
+// Bags are equal if their names are equal
+impl PartialEq for Bag {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Bag {}
+
+// only the Bag.name is hashed
+impl Hash for Bag {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state)
+    }
+}
+
+/// the container bag at the start of a rule, up to `bags contain`
+fn parse_container_bag(line: &mut Scanner) -> Result<Bag, ParseError> {
+    let name = line.until(" bags contain ", "a bag color")?;
+    Ok(Bag::new(0, name.to_string()))
+}
+
+/// the bags a rule's container holds, like `1 bright white bag, 2 muted yellow bags.`
+fn parse_contained_bags(line: &mut Scanner) -> Result<Option<Vec<Bag>>, ParseError> {
+    if line.eat("no other bags.") {
+        return Ok(None);
+    }
+    let bags = line.separated(", ", |line| {
+        let amount = line.unsigned("a bag amount")?;
+        line.literal(" ")?;
+        let name = line.until(" bag", "a bag color")?;
+        line.eat("s");
+        Ok(Bag::new(amount, name.to_string()))
+    })?;
+    line.literal(".")?;
+    Ok(Some(bags))
+}
+
+/// a container bag and the bags it contains, `None` if it contains no other bags
+type Rule = (Bag, Option<Vec<Bag>>);
+
+/// parse a rule into its container bag and the bags it contains
+fn parse_rule(line: &mut Scanner) -> Result<Rule, ParseError> {
+    let container = parse_container_bag(line)?;
+    Ok((container, parse_contained_bags(line)?))
+}
+
+fn parse_rules(input: &str) -> Result<Vec<Rule>, ParseError> {
+    scan::lines(input, parse_rule)
+}
+
+// compute count of unique bag colors can eventually contain at least one shiny gold bag
+fn containers_of_shiny_gold(rules: &[Rule]) -> usize {
+    // a HashMap that maps a contained bag name, to a set of bags that contain them
+    let mut bag_map: HashMap<&str, HashSet<&Bag>> = HashMap::new();
+    for (container, contained) in rules {
+        if let Some(contained_bags) = contained {
+            for bag in contained_bags {
+                bag_map.entry(&bag.name).or_default().insert(container);
+            }
+        }
+    }
+
+    // now determine the count of bags that can contain at least one 'shiny gold' bag
+    let mut bags_to_visit = bag_map
+        .get("shiny gold")
+        .map_or_else(Vec::new, |bags| bags.iter().copied().collect::<Vec<&Bag>>());
+    let mut containing_bags: HashSet<&str> = bags_to_visit.iter().map(|&b| &*b.name).collect();
+    while let Some(next) = bags_to_visit.pop() {
+        if let Some(next_bags) = bag_map.get(&*next.name) {
+            for b in next_bags {
+                containing_bags.insert(&*b.name);
+                bags_to_visit.push(b);
+            }
+        }
+    }
+    containing_bags.len()
+}
+
+// map each container bag, with the total amount it directly contains, to the bags it contains
+fn contents_map(rules: &[Rule]) -> HashMap<Bag, Vec<Bag>> {
+    let mut bag_map = HashMap::new();
+    for (container, contained) in rules {
+        if let Some(contained_bags) = contained {
+            let contained_sum = contained_bags.iter().map(|b| b.amount).sum();
+            bag_map.insert(Bag::new(contained_sum, container.name.clone()), contained_bags.clone());
+        }
+    }
+    bag_map
+}
+
+// recursively count the amount of bags contained within `current` bag
+fn sum_bag(current: &Bag, map: &HashMap<Bag, Vec<Bag>>) -> usize {
+    let mut sum = 0;
+    if let Some(vec) = map.get(current) {
+        for next_bag in vec.iter() {
+            sum += next_bag.amount as usize + next_bag.amount as usize * sum_bag(next_bag, map);
+        }
+    }
+    sum
+}
+
+/// Day 07 - Handy Haversacks
+pub struct HandyHaversacks;
+
+impl Solution for HandyHaversacks {
+    type Input = Vec<Rule>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_rules(input)
+    }
+
+    /// how many bag colors can eventually contain at least one shiny gold bag
+    fn part1(rules: &Vec<Rule>) -> usize {
+        containers_of_shiny_gold(rules)
+    }
+
+    /// how many individual bags are required inside your shiny gold bag
+    fn part2(rules: &Vec<Rule>) -> usize {
+        sum_bag(&Bag::new(0, "shiny gold".to_string()), &contents_map(rules))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use aoc_common::solution::Solution;
+    use aoc_common::scan::Scanner;
+    use crate::d07_handy_haversacks::{parse_contained_bags, parse_container_bag, parse_rules, HandyHaversacks};
+
+    #[test]
+    fn can_parse_containing_bag_name() {
+        let mut line = Scanner::new("light red bags contain 1 bright white bag, 2 muted yellow bags.");
+        let bag = parse_container_bag(&mut line).unwrap();
+        assert_eq!(bag.name, "light red");
+        assert_eq!(bag.amount, 0);
+    }
+
+    #[test]
+    fn can_parse_two_contained_bags() {
+        let mut line = Scanner::new("1 bright white bag, 2 muted yellow bags.");
+        let bags = parse_contained_bags(&mut line).unwrap();
+        assert!(bags.is_some());
+        assert_eq!(bags.as_ref().unwrap()[0].amount, 1);
+        assert_eq!(bags.as_ref().unwrap()[0].name, "bright white");
+        assert_eq!(bags.as_ref().unwrap()[1].amount, 2);
+        assert_eq!(bags.as_ref().unwrap()[1].name, "muted yellow");
+    }
+
+    #[test]
+    fn can_parse_no_contained_bags() {
+        let mut line = Scanner::new("no other bags.");
+        let bags = parse_contained_bags(&mut line).unwrap();
+        assert!(bags.is_none());
+    }
+
+    #[test]
+    fn malformed_rules() {
+        let err = parse_rules("light red bags contain 1 bright white bag, two muted yellow bags.").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (44, "two"));
+        assert!(parse_rules("light red bags hold no other bags.").is_err());
+        assert!(parse_rules("light red bags contain 1 bright white bag").is_err());
+    }
+
+    #[test]
+    fn example_rules() {
+        let rules = parse_rules(&read_input(YEAR, 7, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(HandyHaversacks::part1(&rules), 4);
+        assert_eq!(HandyHaversacks::part2(&rules), 32);
+    }
+
+    #[test]
+    fn nested_example_rules() {
+        let rules = parse_rules(&read_input(YEAR, 7, InputKind::Example(2)).unwrap()).unwrap();
+        assert_eq!(HandyHaversacks::part1(&rules), 0);
+        assert_eq!(HandyHaversacks::part2(&rules), 126);
+    }
+
+    #[test]
+    fn bad_rule_is_located() {
+        let e = parse_rules("faded blue bags contain no other bags.\n\n").unwrap_err();
+        assert_eq!(e.line, 2);
+    }
+}
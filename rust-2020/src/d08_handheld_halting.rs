@@ -0,0 +1,202 @@
+// Day 8 - Handheld Halting
+// https://adventofcode.com/2020/day/8
+
+use aoc_common::parse_error::ParseError;
+use aoc_common::scan::{self, Scanner};
+use aoc_common::solution::Solution;
+
+/// All the possible Instructions
+#[derive(Debug, Copy, Clone)]
+pub enum Ins {
+    Acc(i32),
+    Jmp(i32),
+    Nop(i32),
+}
+
+impl Ins {
+    /// swaps a NOP to a JMP and a JMP to a NOP. ACC returns itself
+    fn swap(ins: &Ins) -> Self {
+        match ins {
+            Ins::Nop(amt) => Ins::Jmp(*amt),
+            Ins::Jmp(amt) => Ins::Nop(*amt),
+            Ins::Acc(_amt) => *ins,
+        }
+    }
+
+    /// returns true if `ins` is an ACC instruction
+    fn is_acc(ins: &Ins) -> bool {
+        matches!(ins, Ins::Acc(_))
+    }
+}
+
+/// the index of the instruction `amt` away from `cidx`. A jump to before the first instruction
+/// leaves the program, just like running past the last one
+fn jump(cidx: usize, amt: i32) -> usize {
+    cidx.checked_add_signed(amt as isize).unwrap_or(usize::MAX)
+}
+
+/// parse a single line of input, like `jmp -4`, into an `Ins`truction
+fn parse_line(line: &mut Scanner) -> Result<Ins, ParseError> {
+    let ins = line.word("an instruction")?;
+    let ins: fn(i32) -> Ins = match ins {
+        "acc" => Ins::Acc,
+        "jmp" => Ins::Jmp,
+        "nop" => Ins::Nop,
+        _ => return Err(line.error(ins, "one of acc, jmp, nop")),
+    };
+    line.literal(" ")?;
+    // the amount always has a sign
+    if !line.rest().starts_with(['+', '-']) {
+        return Err(line.error_here("an instruction amount like +1"));
+    }
+    Ok(ins(line.signed("an instruction amount")?))
+}
+
+/// parse the input into a Vector of `Ins`tructions
+fn parse_program(input: &str) -> Result<Vec<Ins>, ParseError> {
+    scan::lines(input, parse_line)
+}
+
+// if the program terminates, returns: (true, final_value_of_accumulator, Vec_of_ins_indices_run)
+// if the program does NOT terminate (i.e. it loops), returns:
+//      (false, value_of_accumulator_before_executing_loop_ins, Vec_of_ins_run)
+fn will_terminate(ins: &[Ins]) -> (bool, i64, Vec<usize>) {
+    let mut acc = 0;
+    let mut cidx = 0;
+    // is a list of indices into `ins` of only NOP and JMP commands
+    let mut ins_hist: Vec<usize> = Vec::with_capacity(ins.len());
+    let mut visited = vec![false; ins.len()];
+    loop {
+        if cidx >= ins.len() {
+            // program does terminate
+            return (true, acc, ins_hist);
+        }
+        if visited[cidx] {
+            // program loops
+            return (false, acc, ins_hist);
+        }
+
+        visited[cidx] = true;
+        if !Ins::is_acc(&ins[cidx]) {
+            ins_hist.push(cidx);
+        }
+        match ins[cidx] {
+            Ins::Acc(amt) => {
+                acc += i64::from(amt);
+                cidx += 1;
+            }
+            Ins::Jmp(amt) => {
+                cidx = jump(cidx, amt);
+            }
+            Ins::Nop(_amt) => {
+                cidx += 1;
+            }
+        }
+    }
+}
+
+/// if the program given by `ins` terminates, `Some(i64)` is returned containing the final
+/// accumulator value. If the program doesn't terminate, `None` is returned
+fn terminates(ins: &[Ins]) -> Option<i64> {
+    let mut acc = 0;
+    let mut cidx = 0;
+    let mut visited = vec![false; ins.len()];
+    loop {
+        if cidx >= ins.len() {
+            // program terminates
+            return Some(acc);
+        }
+        if visited[cidx] {
+            // program has a loop
+            return None;
+        }
+
+        visited[cidx] = true;
+        match ins[cidx] {
+            Ins::Acc(amt) => {
+                acc += i64::from(amt);
+                cidx += 1;
+            }
+            Ins::Jmp(amt) => {
+                cidx = jump(cidx, amt);
+            }
+            Ins::Nop(_amt) => {
+                cidx += 1;
+            }
+        }
+    }
+}
+
+/// swap the JMP and NOP instructions run before the loop, the last run first, until the program
+/// terminates. Returns the final accumulator value of the repaired program
+fn repair(ins: &[Ins]) -> Option<i64> {
+    let (term, acc, ins_hist) = will_terminate(ins);
+    if term {
+        return Some(acc);
+    }
+    let mut ins = ins.to_vec();
+    ins_hist.iter().rev().find_map(|&idx| {
+        let original = ins[idx];
+        ins[idx] = Ins::swap(&original);
+        let acc = terminates(&ins);
+        ins[idx] = original;
+        acc
+    })
+}
+
+/// Day 08 - Handheld Halting
+pub struct HandheldHalting;
+
+impl Solution for HandheldHalting {
+    type Input = Vec<Ins>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_program(input)
+    }
+
+    /// Immediately before any instruction is executed a second time, what value is in the accumulator?
+    fn part1(ins: &Vec<Ins>) -> i64 {
+        will_terminate(ins).1
+    }
+
+    /// the accumulator after the program terminates, once one JMP or NOP is swapped
+    fn part2(ins: &Vec<Ins>) -> i64 {
+        repair(ins).expect("swapping a JMP or NOP instruction to stop the loop")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use aoc_common::solution::Solution;
+    use crate::d08_handheld_halting::{parse_program, terminates, will_terminate, HandheldHalting};
+
+    #[test]
+    fn example_program() {
+        let ins = parse_program(&read_input(YEAR, 8, InputKind::Example(1)).unwrap()).unwrap();
+        let (term, acc, hist) = will_terminate(&ins);
+        assert!(!term);
+        assert_eq!(acc, 5);
+        assert_eq!(hist, vec![0, 2, 7, 4]);
+        assert_eq!(HandheldHalting::part1(&ins), 5);
+        assert_eq!(HandheldHalting::part2(&ins), 8);
+    }
+
+    #[test]
+    fn jump_out_of_the_program() {
+        assert_eq!(terminates(&parse_program("acc +1\njmp -5\n").unwrap()), Some(1));
+        let ins = parse_program("acc +2147483647\nacc +2147483647\njmp -2147483647\n").unwrap();
+        assert_eq!(HandheldHalting::part1(&ins), 4294967294);
+    }
+
+    #[test]
+    fn parse_instructions() {
+        assert!(parse_program("jmp -4").is_ok());
+        assert_eq!(parse_program("mul +4").unwrap_err().text, "mul");
+        assert_eq!(parse_program("acc 4").unwrap_err().column, 5);
+        assert!(parse_program("acc +4 +5").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+// Advent of Code - Day 9 - Encoding Error
+// https://adventofcode.com/2020/day/9
+
+use num_traits::PrimInt;
+use aoc_common::parse_error::{parse_token, ParseError};
+use aoc_common::solution::Solution;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// the number of previous numbers each number must be a sum of two of
+const PREAMBLE: usize = 25;
+
+/// parse the input into a Vector of integers
+fn parse_numbers(input: &str) -> Result<Vec<i64>, ParseError> {
+    input.lines()
+        .enumerate()
+        .map(|(idx, l)| parse_token::<i64>(l, "an integer").map_err(|e| e.on_line(idx + 1)))
+        .collect::<Result<Vec<i64>, ParseError>>()
+}
+
+/// return all pairs of integers from the `v` that sum up to `sum`
+/// v - the vector of integers
+/// sum - the target sum
+fn two_sum<T>(v: &[T], sum: T) -> Vec<(T, T)>
+where
+    T: PrimInt + Hash,
+{
+    let mut imap = HashMap::new();
+    let mut res = Vec::new();
+    for i in v {
+        // a number too far from `sum` can't be part of a pair for it
+        if let Some(target) = sum.checked_sub(i) {
+            if imap.contains_key(&target) {
+                res.push((*i, target));
+            }
+        }
+        imap.entry(*i).or_insert(*i);
+    }
+    res
+}
+
+fn valid_pair<T: PrimInt>(pair: &(T, T)) -> bool {
+    let (p1, p2) = pair;
+    *p1 != *p2
+}
+
+/// the first number after the preamble that is not the sum of two different numbers among the
+/// `preamble` numbers before it
+fn first_invalid(nums: &[i64], preamble: usize) -> Option<i64> {
+    (preamble..nums.len())
+        .find(|&n| {
+            two_sum(&nums[(n - preamble)..n], nums[n])
+                .iter()
+                .all(|pair| !valid_pair(pair))
+        })
+        .map(|n| nums[n])
+}
+
+/// returns a vector of sorted integers, that are a contiguous slice from `nums` that
+/// sum up to `target`
+/// This is gonna use a brute force approach
+fn contiguous_sum(nums: &[i64], target: i64) -> Option<Vec<i64>> {
+    for window_size in 2..nums.len() {
+        for window in nums.windows(window_size) {
+            let mut slice = window.to_owned();
+            slice.sort_unstable();
+            // don't bother summing a slice that contains an element >= target
+            if *slice.last().unwrap() < target && slice.iter().try_fold(0_i64, |acc, &n| acc.checked_add(n)) == Some(target) {
+                return Some(slice);
+            }
+        }
+    }
+    None
+}
+
+/// the sum of the smallest and largest numbers in the contiguous range that sums to the first
+/// invalid number
+fn encryption_weakness(nums: &[i64], preamble: usize) -> Option<i64> {
+    let target = first_invalid(nums, preamble)?;
+    let slice = contiguous_sum(nums, target)?;
+    Some(slice.first().unwrap() + slice.last().unwrap())
+}
+
+/// Day 09 - Encoding Error
+pub struct EncodingError;
+
+impl Solution for EncodingError {
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_numbers(input)
+    }
+
+    /// the first number that is not the sum of two of the 25 numbers before it
+    fn part1(nums: &Vec<i64>) -> i64 {
+        first_invalid(nums, PREAMBLE).expect("a number that is not the sum of two before it")
+    }
+
+    fn part2(nums: &Vec<i64>) -> i64 {
+        encryption_weakness(nums, PREAMBLE).expect("a contiguous range summing to the invalid number")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use crate::d09_encoding_error::{contiguous_sum, encryption_weakness, first_invalid, parse_numbers};
+
+    #[test]
+    fn example_numbers() {
+        let nums = parse_numbers(&read_input(YEAR, 9, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(first_invalid(&nums, 5), Some(127));
+        assert_eq!(contiguous_sum(&nums, 127), Some(vec![15, 25, 40, 47]));
+        assert_eq!(encryption_weakness(&nums, 5), Some(62));
+    }
+
+    #[test]
+    fn sum_of_the_same_number_twice_is_invalid() {
+        assert_eq!(first_invalid(&[1, 5, 2, 10], 3), Some(10));
+        assert_eq!(first_invalid(&[1, 5, 2, 7], 3), None);
+    }
+
+    #[test]
+    fn extreme_numbers_do_not_overflow() {
+        assert_eq!(first_invalid(&[i64::MIN, 1, i64::MAX], 2), Some(i64::MAX));
+        assert_eq!(contiguous_sum(&[i64::MIN, -1, 1, 2], 3), Some(vec![1, 2]));
+    }
+}
@@ -0,0 +1,221 @@
+// Advent of Code - Day 11 - Seating System
+// https://adventofcode.com/2020/day/11
+
+use aoc_common::grid::{Grid, Pos, DIRECTIONS8};
+use aoc_common::parse_error::ParseError;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use aoc_common::solution::{Part, Solution};
+use aoc_common::visual::{Canvas, Frames, Rgb};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Seat {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+impl Display for Seat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Seat::Floor => write!(f, "."),
+            Seat::Empty => write!(f, "L"),
+            Seat::Occupied => write!(f, "#"),
+        }
+    }
+}
+
+impl TryFrom<char> for Seat {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Seat::Floor),
+            'L' => Ok(Seat::Empty),
+            '#' => Ok(Seat::Occupied),
+            _ => Err(ParseError::new(&value.to_string(), "one of '.', 'L', '#'")),
+        }
+    }
+}
+
+type Seats = Grid<Seat>;
+
+/// returns the total number of occupied seats, that are adjacent to the seat at `pos`
+fn adjacent_occupied_count(seats: &Seats, pos: Pos) -> usize {
+    seats
+        .neighbours8(pos)
+        .filter(|&p| seats[p] == Seat::Occupied)
+        .count()
+}
+
+/// returns the total number of occupied seats that can be "seen" from the seat at `pos`. Looking
+/// in each direction, the first seat seen is the visible one, floor is looked over
+fn visible_occupied_count(seats: &Seats, pos: Pos) -> usize {
+    DIRECTIONS8
+        .iter()
+        .filter(|&&step| {
+            seats
+                .ray(pos, step)
+                .map(|p| seats[p])
+                .find(|&seat| seat != Seat::Floor)
+                == Some(Seat::Occupied)
+        })
+        .count()
+}
+
+/// returns total number of seats that are occupied
+fn occupied_seat_count(seats: &Seats) -> usize {
+    seats.iter().filter(|s| **s == Seat::Occupied).count()
+}
+
+/// apply the seating rules to every seat at once until no seat changes. A seat is taken when
+/// `count` of its occupied neighbours is 0, and left when it reaches `tolerance`.
+/// `after_round` sees the seats after each round
+fn settle(seats: &mut Seats, count: fn(&Seats, Pos) -> usize, tolerance: usize, mut after_round: impl FnMut(&Seats)) {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        let mut next = seats.clone();
+        for pos in seats.positions() {
+            match seats[pos] {
+                Seat::Empty if count(seats, pos) == 0 => {
+                    next[pos] = Seat::Occupied;
+                    changed = true;
+                }
+                Seat::Occupied if count(seats, pos) >= tolerance => {
+                    next[pos] = Seat::Empty;
+                    changed = true;
+                }
+                _ => (),
+            }
+        }
+        *seats = next;
+        after_round(seats);
+    }
+}
+
+/// the counting rule and tolerance people use to choose their seats in each part
+fn rules(part: Part) -> (fn(&Seats, Pos) -> usize, usize) {
+    match part {
+        Part::One => (adjacent_occupied_count, 4),
+        Part::Two => (visible_occupied_count, 5),
+    }
+}
+
+/// draw the seats, with empty seats in green and occupied ones in red
+fn draw_seats(seats: &Seats) -> Canvas {
+    let mut canvas = Canvas::new(seats.width(), seats.height());
+    for (row, col) in seats.positions() {
+        let (glyph, colour) = match seats[(row, col)] {
+            Seat::Floor => ('.', Rgb::GREY),
+            Seat::Empty => ('L', Rgb::GREEN),
+            Seat::Occupied => ('#', Rgb::RED),
+        };
+        canvas.draw(col, row, glyph, colour);
+    }
+    canvas
+}
+
+/// Day 11 - Seating System
+pub struct SeatingSystem;
+
+impl Solution for SeatingSystem {
+    type Input = Seats;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse::<Seats>()
+    }
+
+    /// occupied seats once people only look at their adjacent seats
+    fn part1(seats: &Seats) -> usize {
+        let mut seats = seats.clone();
+        let (count, tolerance) = rules(Part::One);
+        settle(&mut seats, count, tolerance, |_| ());
+        occupied_seat_count(&seats)
+    }
+
+    /// occupied seats once people look at the first seat they can see in each direction
+    fn part2(seats: &Seats) -> usize {
+        let mut seats = seats.clone();
+        let (count, tolerance) = rules(Part::Two);
+        settle(&mut seats, count, tolerance, |_| ());
+        occupied_seat_count(&seats)
+    }
+
+    /// the seats filling and emptying, a frame for each round until they settle
+    fn visualise(seats: &Seats, part: Part, frames: &mut dyn Frames) -> bool {
+        let mut seats = seats.clone();
+        frames.frame(&draw_seats(&seats));
+        let (count, tolerance) = rules(part);
+        settle(&mut seats, count, tolerance, |seats| frames.frame(&draw_seats(seats)));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use aoc_common::solution::{Part, Solution};
+    use aoc_common::visual::Canvas;
+    use crate::d11_seating_system::{adjacent_occupied_count, occupied_seat_count, settle, visible_occupied_count, Seats, SeatingSystem};
+
+    #[test]
+    fn count_adjacent_occupied_seats() {
+        let seats: Seats = "#L#\n.##\nL#L".parse().unwrap();
+        assert_eq!(adjacent_occupied_count(&seats, (0, 0)), 1);
+        assert_eq!(adjacent_occupied_count(&seats, (1, 1)), 4);
+        assert_eq!(adjacent_occupied_count(&seats, (2, 2)), 3);
+    }
+
+    #[test]
+    fn count_visible_occupied_seats() {
+        let seats: Seats = ".......#.\n...#.....\n.#.......\n.........\n..#L....#\n....#....\n.........\n#........\n...#.....".parse().unwrap();
+        assert_eq!(visible_occupied_count(&seats, (4, 3)), 8);
+        let seats: Seats = ".##.##.\n#.#.#.#\n##...##\n...L...\n##...##\n#.#.#.#\n.##.##.".parse().unwrap();
+        assert_eq!(visible_occupied_count(&seats, (3, 3)), 0);
+        let seats: Seats = ".............\n.L.L.#.#.#.#.\n.............".parse().unwrap();
+        assert_eq!(visible_occupied_count(&seats, (1, 1)), 0);
+    }
+
+    #[test]
+    fn settle_the_example() {
+        let mut seats: Seats = read_input(YEAR, 11, InputKind::Example(2)).unwrap().parse().unwrap();
+        settle(&mut seats, adjacent_occupied_count, 4, |_| ());
+        assert_eq!(occupied_seat_count(&seats), 37);
+        let mut seats: Seats = read_input(YEAR, 11, InputKind::Example(2)).unwrap().parse().unwrap();
+        settle(&mut seats, visible_occupied_count, 5, |_| ());
+        assert_eq!(occupied_seat_count(&seats), 26);
+    }
+
+    #[test]
+    fn draw_every_round() {
+        let seats = SeatingSystem::parse(&read_input(YEAR, 11, InputKind::Example(2)).unwrap()).unwrap();
+        let mut frames: Vec<Canvas> = Vec::new();
+        assert!(SeatingSystem::visualise(&seats, Part::One, &mut frames));
+        // the starting seats, five rounds that change them, and the round that finds them settled
+        assert_eq!(frames.len(), 7);
+        let occupied = frames[6].rows().flatten().filter(|cell| cell.glyph == '#').count();
+        assert_eq!(occupied, 37);
+        assert_eq!(frames[5], frames[6]);
+    }
+
+    #[test]
+    fn example_parts() {
+        let seats = SeatingSystem::parse(&read_input(YEAR, 11, InputKind::Example(2)).unwrap()).unwrap();
+        assert_eq!(SeatingSystem::part1(&seats), 37);
+        assert_eq!(SeatingSystem::part2(&seats), 26);
+    }
+
+    #[test]
+    fn invalid_seat_is_a_parse_error() {
+        assert_eq!("L.#L".parse::<Seats>().unwrap().width(), 4);
+        let err = "L.#L\nL.xL".parse::<Seats>().unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "x");
+        assert!("L.#L\nL.#".parse::<Seats>().is_err());
+    }
+}
@@ -0,0 +1,403 @@
+// Advent of Code - Day 12 - Rain Risk
+// https://adventofcode.com/2020/day/12
+
+use aoc_common::geometry::{quarter_turns, Direction, Point2};
+use aoc_common::parse_error::{parse_token, ParseError};
+use aoc_common::solution::{Part, Solution};
+use aoc_common::visual::{Frames, Rgb, Viewport};
+use std::convert::TryFrom;
+use NavInstr::{Left, Right, North, South, East, West, Forward};
+
+/// Navigation Instruction
+#[derive(Debug, PartialEq)]
+pub enum NavInstr {
+    North(u32),
+    South(u32),
+    East(u32),
+    West(u32),
+    Left(u32),
+    Right(u32),
+    Forward(u32),
+}
+
+impl TryFrom<&str> for NavInstr {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let nidx = s.find(char::is_numeric)
+            .ok_or_else(|| ParseError::new(s, "a nav instruction with an integer amount"))?;
+        let (command, amount_str) = s.split_at(nidx);
+        let amount = parse_token::<u32>(amount_str, "a nav instruction amount")
+            .map_err(|e| e.locate(s, amount_str))?;
+
+        match command {
+            "N" => Ok(NavInstr::North(amount)),
+            "S" => Ok(NavInstr::South(amount)),
+            "E" => Ok(NavInstr::East(amount)),
+            "W" => Ok(NavInstr::West(amount)),
+            "L" | "R" if quarter_turns(amount as i64).is_none() =>
+                Err(ParseError::new(amount_str, "a turn of a multiple of 90 degrees").locate(s, amount_str)),
+            "L" => Ok(NavInstr::Left(amount)),
+            "R" => Ok(NavInstr::Right(amount)),
+            "F" => Ok(NavInstr::Forward(amount)),
+            _ => Err(ParseError::new(command, "one of N, S, E, W, L, R, F"))
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Ship {
+    // ships current heading
+    heading: Direction,
+    // ships current position, +x is East and +y is North
+    pos: Point2,
+}
+
+impl Ship {
+    fn new() -> Self {
+        Self {
+            heading: Direction::East,
+            pos: Point2::ORIGIN,
+        }
+    }
+
+    /// turn ship left or right by some amount, onto a new heading
+    fn turn_ship(&mut self, ni: &NavInstr) {
+        let degrees = match ni {
+            Left(amount) => *amount as i64,
+            Right(amount) => -(*amount as i64),
+            instr => panic!("unknown turn instruction {:?}", instr)
+        };
+        let turns = quarter_turns(degrees).expect("turns are parsed as multiples of 90 degrees");
+        self.heading = self.heading.rotate(turns);
+    }
+
+    /// move ship in the specified direction
+    fn move_ship(&mut self, ni: &NavInstr) {
+        let (dir, amt) = match ni {
+            North(amt) => (Direction::North, amt),
+            South(amt) => (Direction::South, amt),
+            East(amt) => (Direction::East, amt),
+            West(amt) => (Direction::West, amt),
+            Forward(amt) => (self.heading, amt),
+            turn => panic!("invalid move instruction {:?}", turn),
+        };
+        self.pos += dir.vec() * *amt as i64;
+    }
+
+    /// returns the manhattan distance between the ships current position and its origin
+    fn manhattan_distance(&self) -> u64 {
+        self.pos.manhattan(Point2::ORIGIN) as u64
+    }
+}
+
+#[derive(Debug)]
+struct Entity {
+    // entitys current position, +x is East and +y is North
+    pos: Point2,
+}
+
+impl Entity {
+    fn new(ew: i64, ns: i64) -> Self {
+        Self {
+            pos: Point2::new(ew, ns),
+        }
+    }
+
+    /// turn (rotate) this entity according to the given Navigation Instruction,
+    /// around the `other` entities current position
+    fn nav_turn(&mut self, ni: &NavInstr, other: &Entity) {
+        let degrees = match ni {
+            Left(amount) => *amount as i64,
+            Right(amount) => -(*amount as i64),
+            instr => panic!("unknown turn instruction {:?}", instr)
+        };
+        let turns = quarter_turns(degrees).expect("turns are parsed as multiples of 90 degrees");
+        self.pos = self.pos.rotate_about(other.pos, turns);
+    }
+
+    /// move entity in the specified direction
+    fn nav_move(&mut self, ni: &NavInstr) {
+        let (dir, amt) = match ni {
+            North(amt) => (Direction::North, amt),
+            South(amt) => (Direction::South, amt),
+            East(amt) => (Direction::East, amt),
+            West(amt) => (Direction::West, amt),
+            other => panic!("invalid move instruction {:?}", other),
+        };
+        self.pos += dir.vec() * *amt as i64;
+    }
+
+    /// move the entity forward by some amt, in the direction of the given entity
+    fn forward(&mut self, amt: u32, other: &Entity) {
+        self.pos += (other.pos - self.pos) * amt as i64;
+    }
+
+    /// returns the manhattan distance between the entity's current position and the origin
+    fn manhattan_distance(&self) -> u64 {
+        self.pos.manhattan(Point2::ORIGIN) as u64
+    }
+}
+
+/// parse input into a Vector of nav instructions
+fn parse_input(input: &str) -> Result<Vec<NavInstr>, ParseError> {
+    input.lines()
+        .enumerate()
+        .map(|(idx, line)| NavInstr::try_from(line).map_err(|e| e.on_line(idx + 1)))
+        .collect::<Result<Vec<NavInstr>, ParseError>>()
+}
+
+/// steer the ship itself by the instructions, calling `step` with the ship after each one
+fn steer_ship(nis: &[NavInstr], mut step: impl FnMut(&Ship)) -> Ship {
+    let mut ship = Ship::new();
+    for ni in nis {
+        match ni {
+            Right(_) | Left(_) => ship.turn_ship(ni),
+            _ => ship.move_ship(ni),
+        }
+        step(&ship);
+    }
+    ship
+}
+
+/// steer the waypoint by the instructions, moving the ship towards it, and call `step` with the
+/// ship and waypoint after each one
+fn steer_waypoint(nis: &[NavInstr], mut step: impl FnMut(&Entity, &Entity)) -> Entity {
+    let mut ship = Entity::new(0, 0);
+    let mut wp = Entity::new(10, 1);
+
+    for ni in nis {
+        match ni {
+            Right(_) | Left(_) => {
+                wp.nav_turn(ni, &ship);
+            },
+            North(_) | South(_) | East(_) | West(_) => {
+                wp.nav_move(ni);
+            },
+            Forward(amt) => {
+                let wp_offset = wp.pos - ship.pos;
+                ship.forward(*amt, &wp);
+                wp.pos = ship.pos + wp_offset;
+            },
+        }
+        step(&ship, &wp);
+    }
+    ship
+}
+
+/// the glyph of a ship pointing towards `heading`
+fn heading_glyph(heading: Direction) -> char {
+    match heading {
+        Direction::North => '^',
+        Direction::East => '>',
+        Direction::South => 'v',
+        Direction::West => '<',
+    }
+}
+
+/// draw the ship after each instruction, over the trail of where it has been and with the
+/// waypoint it steers towards in part two. A first pass finds the view that holds them all
+fn draw_voyage(nis: &[NavInstr], part: Part, frames: &mut dyn Frames) {
+    // the ship and, when there is one, the waypoint after each instruction
+    let mut states = vec![(Point2::ORIGIN, None, Direction::East)];
+    match part {
+        Part::One => {
+            steer_ship(nis, |ship| states.push((ship.pos, None, ship.heading)));
+        }
+        Part::Two => {
+            states[0].1 = Some(Point2::new(10, 1));
+            steer_waypoint(nis, |ship, wp| states.push((ship.pos, Some(wp.pos), Direction::East)));
+        }
+    }
+    let view = Viewport::fit(states.iter().flat_map(|&(ship, wp, _)| std::iter::once(ship).chain(wp)), 100);
+
+    for (i, &(ship, wp, heading)) in states.iter().enumerate() {
+        let mut canvas = view.canvas();
+        for &(trail, _, _) in &states[..i] {
+            view.draw(&mut canvas, trail, '.', Rgb::BLUE);
+        }
+        view.draw(&mut canvas, Point2::ORIGIN, 's', Rgb::GREY);
+        if let Some(wp) = wp {
+            view.draw(&mut canvas, wp, '+', Rgb::YELLOW);
+        }
+        let glyph = if wp.is_some() { '@' } else { heading_glyph(heading) };
+        view.draw(&mut canvas, ship, glyph, Rgb::WHITE);
+        frames.frame(&canvas);
+    }
+}
+
+/// Day 12 - Rain Risk
+pub struct RainRisk;
+
+impl Solution for RainRisk {
+    type Input = Vec<NavInstr>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    /// the instructions steer the ship itself
+    fn part1(nis: &Vec<NavInstr>) -> u64 {
+        steer_ship(nis, |_| ()).manhattan_distance()
+    }
+
+    /// the instructions move a waypoint relative to the ship, and the ship moves towards it
+    fn part2(nis: &Vec<NavInstr>) -> u64 {
+        steer_waypoint(nis, |_, _| ()).manhattan_distance()
+    }
+
+    /// the ship sailing through the instructions, and its waypoint for part two
+    fn visualise(nis: &Vec<NavInstr>, part: Part, frames: &mut dyn Frames) -> bool {
+        draw_voyage(nis, part, frames);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use crate::d12_rain_risk::{draw_voyage, parse_input, Entity, NavInstr, RainRisk, Ship};
+    use aoc_common::geometry::{Direction, Point2};
+    use aoc_common::solution::{Part, Solution};
+    use aoc_common::visual::{Canvas, Viewport};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn example_instructions() {
+        let nis = parse_input(&read_input(YEAR, 12, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(RainRisk::part1(&nis), 25);
+        assert_eq!(RainRisk::part2(&nis), 286);
+    }
+
+    #[test]
+    fn draw_every_instruction() {
+        let nis = parse_input(&read_input(YEAR, 12, InputKind::Example(1)).unwrap()).unwrap();
+        let mut frames: Vec<Canvas> = Vec::new();
+        draw_voyage(&nis, Part::One, &mut frames);
+        // the start, then a frame for each of the five instructions
+        assert_eq!(frames.len(), 6);
+        // the ship sails from the origin to (17, -8), heading south
+        let view = Viewport::fit([Point2::ORIGIN, Point2::new(17, 3), Point2::new(17, -8)], 100);
+        let (x, y) = view.cell(Point2::new(17, -8)).unwrap();
+        assert_eq!(frames[5].get(x, y).unwrap().glyph, 'v');
+        let mut frames: Vec<Canvas> = Vec::new();
+        draw_voyage(&nis, Part::Two, &mut frames);
+        assert_eq!(frames.len(), 6);
+        assert_eq!(frames[5].rows().flatten().filter(|cell| cell.glyph == '+').count(), 1);
+    }
+
+    #[test]
+    fn parse_nav_instruction() {
+        let ni = NavInstr::try_from("F145");
+        assert_eq!(ni.unwrap(), NavInstr::Forward(145));
+    }
+
+    #[test]
+    fn invalid_nav_instruction() {
+        let err = NavInstr::try_from("Z12").unwrap_err();
+        assert_eq!(err.text, "Z");
+        let err = NavInstr::try_from("F1x").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (2, "1x"));
+        assert!(NavInstr::try_from("F").is_err());
+        let err = NavInstr::try_from("R100").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (2, "100"));
+        let err = NavInstr::try_from("L45").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (2, "45"));
+    }
+
+    #[test]
+    fn turn_ship_right() {
+        let mut ship = Ship::new();
+        ship.turn_ship(&NavInstr::Right(180));
+        assert_eq!(ship.heading, Direction::West);
+    }
+
+    #[test]
+    fn turn_ship_right_270() {
+        let mut ship = Ship::new();
+        ship.turn_ship(&NavInstr::Right(270));
+        assert_eq!(ship.heading, Direction::North);
+    }
+
+    #[test]
+    fn turn_ship_left_180() {
+        let mut ship = Ship::new();
+        ship.turn_ship(&NavInstr::Left(180));
+        assert_eq!(ship.heading, Direction::West);
+    }
+
+    #[test]
+    fn turn_ship_left_90() {
+        let mut ship = Ship::new();
+        ship.turn_ship(&NavInstr::Left(90));
+        assert_eq!(ship.heading, Direction::North);
+    }
+
+    #[test]
+    fn turn_ship_left_270() {
+        let mut ship = Ship::new();
+        ship.turn_ship(&NavInstr::Left(270));
+        assert_eq!(ship.heading, Direction::South);
+    }
+
+    #[test]
+    fn move_ship_forward() {
+        let mut ship = Ship::new();
+        ship.move_ship(&NavInstr::Forward(10));
+        ship.move_ship(&NavInstr::North(3));
+        ship.turn_ship(&NavInstr::Right(90));
+        ship.move_ship(&NavInstr::Forward(11));
+        assert_eq!(ship.pos, Point2::new(10, -8));
+        assert_eq!(ship.manhattan_distance(), 18);
+    }
+
+    #[test]
+    fn rotate_entity_right_90() {
+        let mut wp = Entity::new(180, 42);
+        wp.nav_turn(&NavInstr::Right(90), &Entity::new(170, 38));
+        assert_eq!(wp.pos, Point2::new(174, 28));
+    }
+
+    #[test]
+    fn rotate_entity_left_90() {
+        let mut wp = Entity::new(10, 0);
+        wp.nav_turn(&NavInstr::Left(90), &Entity::new(0, 0));
+        assert_eq!(wp.pos, Point2::new(0, 10));
+    }
+
+    #[test]
+    fn rotate_entity_270() {
+        let mut wp = Entity::new(13, -7);
+        wp.nav_turn(&NavInstr::Left(270), &Entity::new(3, 3));
+        assert_eq!(wp.pos, Point2::new(-7, -7));
+        wp.nav_turn(&NavInstr::Right(270), &Entity::new(3, 3));
+        assert_eq!(wp.pos, Point2::new(13, -7));
+    }
+
+    #[test]
+    fn forward_4() {
+        let wp = Entity::new(4, 4);
+        let mut ship = Entity::new(2, 2);
+        ship.forward(4, &wp);
+        assert_eq!(ship.pos, Point2::new(10, 10));
+    }
+
+    #[test]
+    fn forward_1_west_north() {
+        let wp = Entity::new(-2, 4);
+        let mut ship = Entity::new(2, 2);
+        ship.forward(1, &wp);
+        assert_eq!(ship.pos, Point2::new(-2, 4));
+    }
+
+    #[test]
+    fn forward_1_west_south() {
+        let wp = Entity::new(-4, -4);
+        let mut ship = Entity::new(2, 2);
+        ship.forward(3, &wp);
+        assert_eq!(ship.pos, Point2::new(-16, -16));
+    }
+}
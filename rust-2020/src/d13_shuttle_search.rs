@@ -0,0 +1,171 @@
+// Advent of Code - Day 13 - Shuttle Search
+// https://adventofcode.com/2020/day/13
+
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+use aoc_common::parse_error::{parse_token, ParseError};
+use aoc_common::solution::Solution;
+
+type BusIds = Vec<Option<u32>>;
+
+/// Parse a comma separated list of bus ids, where an `x` is a bus that is out of service. There
+/// must be at least one bus in service
+fn parse_bus_ids(line: &str) -> Result<BusIds, ParseError> {
+    let mut bus_ids = vec![];
+    for s in line.split(',') {
+        let id = match s {
+            "x" => None,
+            _ => match parse_token::<u32>(s, "a bus id or 'x'").map_err(|e| e.locate(line, s))? {
+                0 => return Err(ParseError::new(s, "a bus id greater than 0").locate(line, s)),
+                id => Some(id),
+            },
+        };
+        bus_ids.push(id);
+    }
+    if bus_ids.iter().all(Option::is_none) {
+        return Err(ParseError::new(line, "at least one bus in service"));
+    }
+    Ok(bus_ids)
+}
+
+/// the bus notes: the earliest timestamp you could depart, and the bus ids
+#[derive(Debug)]
+pub struct Notes {
+    timestamp: u64,
+    bus_ids: BusIds,
+}
+
+fn parse_notes(input: &str) -> Result<Notes, ParseError> {
+    let mut lines = input.lines();
+    let timestamp = match lines.next() {
+        Some(line) => parse_token::<u64>(line, "a timestamp").map_err(|e| e.on_line(1))?,
+        None => return Err(ParseError::new("", "a timestamp").on_line(1)),
+    };
+    let bus_ids = match lines.next() {
+        Some(line) => parse_bus_ids(line).map_err(|e| e.on_line(2))?,
+        None => return Err(ParseError::new("", "a line of bus ids").on_line(2)),
+    };
+    Ok(Notes { timestamp, bus_ids })
+}
+
+/// the first bus to depart at or after `timestamp`, and how many minutes to wait for it
+fn earliest_bus(timestamp: u64, bids: &BusIds) -> Option<(u64, u64)> {
+    bids.iter()
+        .flatten()
+        .map(|&id| {
+            let id = id as u64;
+            (id, (id - timestamp % id) % id)
+        })
+        .min_by_key(|&(_id, wait)| wait)
+}
+
+/// `a` modulo `m`, between 0 and `m`
+fn modulo(a: &BigInt, m: &BigInt) -> BigInt {
+    ((a % m) + m) % m
+}
+
+/// the inverse of `a` modulo `m`, found with the extended Euclidean algorithm. There is only an
+/// inverse when `a` and `m` are coprime
+fn inverse(a: &BigInt, m: &BigInt) -> Option<BigInt> {
+    let (mut old_r, mut r) = (modulo(a, m), m.clone());
+    let (mut old_s, mut s) = (BigInt::one(), BigInt::zero());
+    while !r.is_zero() {
+        let q = &old_r / &r;
+        (old_r, r) = (r.clone(), old_r - &q * &r);
+        (old_s, s) = (s.clone(), old_s - &q * &s);
+    }
+    old_r.is_one().then(|| modulo(&old_s, m))
+}
+
+/// The earliest timestamp where each bus departs as many minutes after it as its offset in the
+/// list. Solves `x = -offset (mod id)` for every bus with the chinese remainder theorem, which
+/// needs the ids to be pairwise coprime, and fails if they aren't
+fn chinese_remainder(bids: &BusIds) -> Result<BigInt, String> {
+    let buses: Vec<(BigInt, BigInt)> = bids.iter()
+        .enumerate()
+        .filter_map(|(offset, id)| id.map(|id| (BigInt::from(id), BigInt::from(offset))))
+        .collect();
+    let n: BigInt = buses.iter().map(|(id, _offset)| id).product();
+
+    let sum: BigInt = buses.iter()
+        .map(|(id, offset)| {
+            let n_u = &n / id;
+            // there is only an inverse if `id` is coprime to the product of the other ids
+            let inverse = inverse(&n_u, id).ok_or_else(|| format!("bus id {} is not coprime to the other ids", id))?;
+            Ok(modulo(&-offset, id) * n_u * inverse)
+        })
+        .sum::<Result<BigInt, String>>()?;
+    Ok(sum % n)
+}
+
+/// Day 13 - Shuttle Search
+pub struct ShuttleSearch;
+
+impl Solution for ShuttleSearch {
+    type Input = Notes;
+    type Answer1 = u64;
+    type Answer2 = Result<BigInt, String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_notes(input)
+    }
+
+    /// the id of the earliest bus you can take, multiplied by the minutes to wait for it
+    fn part1(notes: &Notes) -> u64 {
+        let (id, wait) = earliest_bus(notes.timestamp, &notes.bus_ids).expect("at least one bus in service");
+        id * wait
+    }
+
+    fn part2(notes: &Notes) -> Result<BigInt, String> {
+        chinese_remainder(&notes.bus_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use num_bigint::BigInt;
+    use aoc_common::solution::Solution;
+    use crate::d13_shuttle_search::{chinese_remainder, earliest_bus, inverse, parse_bus_ids, parse_notes, ShuttleSearch};
+
+    #[test]
+    fn example_notes() {
+        let notes = parse_notes(&read_input(YEAR, 13, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(earliest_bus(notes.timestamp, &notes.bus_ids), Some((59, 5)));
+        assert_eq!(ShuttleSearch::part1(&notes), 295);
+        assert_eq!(ShuttleSearch::part2(&notes), Ok(BigInt::from(1068781)));
+    }
+
+    #[test]
+    fn example_bus_lists() {
+        let earliest = |line| chinese_remainder(&parse_bus_ids(line).unwrap()).unwrap();
+        assert_eq!(earliest("17,x,13,19"), BigInt::from(3417));
+        assert_eq!(earliest("67,7,59,61"), BigInt::from(754018));
+        assert_eq!(earliest("1789,37,47,1889"), BigInt::from(1202161486));
+    }
+
+    #[test]
+    fn invalid_notes() {
+        assert_eq!(parse_notes("939\n7,0,x\n").unwrap_err().line, 2);
+        assert_eq!(parse_notes("93x\n7,13\n").unwrap_err().line, 1);
+        assert!(parse_notes("939\n").is_err());
+        assert!(parse_notes("939\nx,x\n").is_err());
+    }
+
+    #[test]
+    fn ids_that_are_not_coprime() {
+        // part 1 has an answer whatever the ids, but part 2 needs them to be pairwise coprime
+        let notes = parse_notes("939\n6,x,35,4\n").unwrap();
+        assert_eq!(ShuttleSearch::part1(&notes), 4);
+        assert_eq!(ShuttleSearch::part2(&notes), Err("bus id 6 is not coprime to the other ids".to_string()));
+    }
+
+    #[test]
+    fn inverses() {
+        let inverse = |a: i32, m: i32| inverse(&BigInt::from(a), &BigInt::from(m));
+        assert_eq!(inverse(3, 7), Some(BigInt::from(5)));
+        assert_eq!(inverse(-4, 7), Some(BigInt::from(5)));
+        assert_eq!(inverse(4, 6), None);
+    }
+}
@@ -0,0 +1,194 @@
+// Advent of Code - Day 14 - Docking Data
+// https://adventofcode.com/2020/day/14
+
+use std::collections::HashMap;
+use aoc_common::parse_error::ParseError;
+use aoc_common::scan::Scanner;
+use aoc_common::solution::Solution;
+
+/// a line of the initialization program
+#[derive(Debug)]
+pub enum Instr {
+    /// a new bitmask, padded to 64 bits
+    Mask(String),
+    /// write a value to a memory address
+    Mem(usize, u64),
+}
+
+fn parse_mask(mask: &str) -> Result<String, ParseError> {
+    let ms = mask
+        .strip_prefix("mask = ")
+        .ok_or_else(|| ParseError::new(mask, "a line like 'mask = X01X'"))?;
+    if ms.len() != 36 || !ms.chars().all(|c| matches!(c, 'X' | '0' | '1')) {
+        return Err(ParseError::new(ms, "a 36 bit mask of X, 0 and 1").locate(mask, ms));
+    }
+    Ok(String::from("0000000000000000000000000000") + ms)
+}
+
+fn parse_mem(line: &str) -> Result<(usize, u64), ParseError> {
+    let mut line = Scanner::new(line);
+    line.literal("mem[")?;
+    let index = line.unsigned("a memory address")?;
+    line.literal("] = ")?;
+    let amount = line.unsigned("a 36 bit value")?;
+    line.end()?;
+    Ok((index, amount))
+}
+
+fn merge(mask: &str, value: u64) -> usize {
+    let value = format!("{:064b}", value);
+    let merged: String = value.chars().zip(mask.chars()).map(|(v, m)| {
+        if m == 'X' {
+            v
+        } else {
+            m
+        }
+    })
+        .collect();
+    usize::from_str_radix(&merged, 2).unwrap()
+}
+
+/// the most floating bits a version 2 mask can have, as each one doubles the addresses written
+const MAX_FLOATING_BITS: usize = 16;
+
+/// the addresses written by a write to `address` through the version 2 decoder `mask`. A 1 bit in
+/// the mask sets the address bit, and an X bit floats, taking both values. Fails if the mask has
+/// more than `MAX_FLOATING_BITS` floating bits
+fn addresses(mask: &str, address: usize) -> Result<Vec<usize>, String> {
+    let floating = mask.matches('X').count();
+    if floating > MAX_FLOATING_BITS {
+        return Err(format!("a mask with {} floating bits writes too many addresses", floating));
+    }
+    let ones = usize::from_str_radix(&mask.replace('X', "0"), 2).unwrap();
+    let base = address | ones;
+    Ok(mask.chars().rev().enumerate()
+        .filter(|&(_bit, m)| m == 'X')
+        .fold(vec![base], |addrs, (bit, _m)| {
+            addrs.into_iter()
+                .flat_map(|a| [a & !(1 << bit), a | (1 << bit)])
+                .collect()
+        }))
+}
+
+fn parse_program(input: &str) -> Result<Vec<Instr>, ParseError> {
+    input.lines()
+        .enumerate()
+        .map(|(idx, l)| {
+            let instr = if l.starts_with("mask") {
+                parse_mask(l).map(Instr::Mask)
+            } else if l.starts_with("mem") {
+                parse_mem(l).map(|(index, amt)| Instr::Mem(index, amt))
+            } else {
+                Err(ParseError::new(l, "a mask or mem line"))
+            };
+            instr.map_err(|e| e.on_line(idx + 1))
+        })
+        .collect()
+}
+
+/// Day 14 - Docking Data
+pub struct DockingData;
+
+impl Solution for DockingData {
+    type Input = Vec<Instr>;
+    type Answer1 = usize;
+    type Answer2 = Result<u64, String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_program(input)
+    }
+
+    /// the sum of memory, when the mask applies to the values written
+    fn part1(program: &Vec<Instr>) -> usize {
+        let mut map: HashMap<usize, usize> = HashMap::new();
+        let mut cur_mask = "0".repeat(64);
+        for instr in program {
+            match instr {
+                Instr::Mask(mask) => cur_mask = mask.clone(),
+                Instr::Mem(index, amt) => {
+                    map.insert(*index, merge(&cur_mask, *amt));
+                }
+            }
+        }
+        map.values().sum()
+    }
+
+    /// the sum of memory, when the mask applies to the addresses written
+    fn part2(program: &Vec<Instr>) -> Result<u64, String> {
+        let mut map: HashMap<usize, u64> = HashMap::new();
+        let mut cur_mask = "0".repeat(64);
+        for instr in program {
+            match instr {
+                Instr::Mask(mask) => cur_mask = mask.clone(),
+                Instr::Mem(index, amt) => {
+                    for address in addresses(&cur_mask, *index)? {
+                        map.insert(address, *amt);
+                    }
+                }
+            }
+        }
+        Ok(map.values().sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use aoc_common::solution::Solution;
+    use crate::d14_docking_data::{addresses, merge, parse_mask, parse_mem, parse_program, DockingData};
+
+    #[test]
+    fn basic_mask() {
+        let mask_raw = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X";
+        let mask = parse_mask(mask_raw).unwrap();
+
+        let (index, amt) = parse_mem("mem[8] = 11").unwrap();
+        assert_eq!(index, 8);
+        assert_eq!(amt, 11);
+
+        assert_eq!(merge(&mask, 11), 73);
+    }
+
+    #[test]
+    fn invalid_mem_line() {
+        let err = parse_mem("mem[8] = x").unwrap_err();
+        assert_eq!(err.column, 10);
+        let err = parse_mem("mem[8] 11").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (6, "]"));
+        let err = parse_mem("mem[8] = 99999999999999999999").unwrap_err();
+        assert_eq!(err.column, 10);
+        assert!(parse_mask("mask = X1").is_err());
+    }
+
+    #[test]
+    fn example_value_masks() {
+        let program = parse_program(&read_input(YEAR, 14, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(DockingData::part1(&program), 165);
+    }
+
+    #[test]
+    fn example_address_masks() {
+        let mask = parse_mask("mask = 000000000000000000000000000000X1001X").unwrap();
+        let mut addrs = addresses(&mask, 42).unwrap();
+        addrs.sort();
+        assert_eq!(addrs, vec![26, 27, 58, 59]);
+
+        let program = parse_program(&read_input(YEAR, 14, InputKind::Example(2)).unwrap()).unwrap();
+        assert_eq!(DockingData::part2(&program), Ok(208));
+    }
+
+    #[test]
+    fn too_many_floating_bits() {
+        // the value mask example floats 34 bits, which would write 2^34 addresses
+        let program = parse_program(&read_input(YEAR, 14, InputKind::Example(1)).unwrap()).unwrap();
+        let err = DockingData::part2(&program).unwrap_err();
+        assert_eq!(err, "a mask with 34 floating bits writes too many addresses");
+    }
+
+    #[test]
+    fn unknown_line_is_an_error() {
+        let err = parse_program("mask = 000000000000000000000000000000X1001X\nmem[42] = 100\nmov 1\n").unwrap_err();
+        assert_eq!(err.line, 3);
+    }
+}
@@ -0,0 +1,69 @@
+pub mod d01_report_repair;
+pub mod d02_password_philosophy;
+pub mod d03_toboggan_trajectory;
+pub mod d04_passport_processing;
+pub mod d05_binary_boarding;
+pub mod d06_custom_customs;
+pub mod d07_handy_haversacks;
+pub mod d08_handheld_halting;
+pub mod d09_encoding_error;
+pub mod d11_seating_system;
+pub mod d12_rain_risk;
+pub mod d13_shuttle_search;
+pub mod d14_docking_data;
+
+use aoc_common::solution::Day;
+
+/// the puzzle year these solutions are for
+pub const YEAR: u16 = 2020;
+
+/// all days with a solution, in day order
+pub static DAYS: &[Day] = &[
+    Day::new::<d01_report_repair::ReportRepair>(1, "Report Repair"),
+    Day::new::<d02_password_philosophy::PasswordPhilosophy>(2, "Password Philosophy"),
+    Day::new::<d03_toboggan_trajectory::TobogganTrajectory>(3, "Toboggan Trajectory"),
+    Day::new::<d04_passport_processing::PassportProcessing>(4, "Passport Processing"),
+    Day::new::<d05_binary_boarding::BinaryBoarding>(5, "Binary Boarding"),
+    Day::new::<d06_custom_customs::CustomCustoms>(6, "Custom Customs"),
+    Day::new::<d07_handy_haversacks::HandyHaversacks>(7, "Handy Haversacks"),
+    Day::new::<d08_handheld_halting::HandheldHalting>(8, "Handheld Halting"),
+    Day::new::<d09_encoding_error::EncodingError>(9, "Encoding Error"),
+    Day::new::<d11_seating_system::SeatingSystem>(11, "Seating System"),
+    Day::new::<d12_rain_risk::RainRisk>(12, "Rain Risk"),
+    Day::new::<d13_shuttle_search::ShuttleSearch>(13, "Shuttle Search"),
+    Day::new::<d14_docking_data::DockingData>(14, "Docking Data"),
+];
+
+/// returns the registered solution for `day`, if there is one
+pub fn find_day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::answers::{Answers, ANSWERS_FILE};
+    use aoc_common::input::InputLocator;
+    use aoc_common::verify::{table, verify, Check, Status};
+    use crate::{DAYS, YEAR, find_day};
+
+    #[test]
+    fn days_are_registered_in_order() {
+        assert!(DAYS.windows(2).all(|w| w[0].day < w[1].day));
+    }
+
+    #[test]
+    fn find_registered_day() {
+        assert_eq!(find_day(13).unwrap().title, "Shuttle Search");
+        assert!(find_day(10).is_none());
+    }
+
+    #[test]
+    fn registered_days_give_the_known_answers() {
+        let locator = InputLocator::find(YEAR).unwrap();
+        let answers = Answers::load(&locator.dir().join(ANSWERS_FILE)).unwrap();
+        let checks = verify(DAYS, &locator, &answers);
+        let failed: Vec<&Check> = checks.iter().filter(|c| c.status() == Status::Fail).collect();
+        assert!(failed.is_empty(), "\n{}", table(&checks));
+        assert!(checks.iter().any(|c| c.status() == Status::Pass));
+    }
+}
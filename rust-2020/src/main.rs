@@ -1,3 +1,8 @@
-fn main() {
-    println!("Advent Of Code solutions are in the src/bin directory");
+// Command line runner for the 2020 solutions, see `aoc_common::cli` for the commands
+
+use std::process::ExitCode;
+use rust_2020::{DAYS, YEAR};
+
+fn main() -> ExitCode {
+    aoc_common::cli::main(YEAR, DAYS)
 }
@@ -0,0 +1,429 @@
+//! Property tests for the 2020 days. Parsing must never panic, whatever the text, and every
+//! generated puzzle is printed as input text, parsed back and solved, and must give the same
+//! answers as a simple reference solution written from the puzzle description
+
+use std::collections::{HashMap, HashSet};
+use proptest::collection::{hash_set, vec};
+use proptest::option::weighted;
+use proptest::prelude::*;
+use aoc_common::solution::Part;
+use rust_2020::{find_day, DAYS};
+
+/// solve `part` of `day` for `input`, which must parse
+fn solve(day: u8, input: &str, part: Part) -> String {
+    find_day(day).unwrap()
+        .solve(input, part)
+        .unwrap_or_else(|e| panic!("{}", e.diagnostic(input)))
+}
+
+fn lines<T: ToString>(items: &[T]) -> String {
+    items.iter().map(T::to_string).collect::<Vec<_>>().join("\n")
+}
+
+/// trees hit going down `trees`, `right` and `down` squares at a time
+fn trees_hit(trees: &[Vec<bool>], right: usize, down: usize) -> usize {
+    (0..trees.len()).step_by(down)
+        .enumerate()
+        .filter(|&(step, row)| trees[row][(step * right) % trees[row].len()])
+        .count()
+}
+
+/// candidate values for each passport field, some valid and some not
+const PASSPORT_FIELDS: [(&str, &[&str]); 8] = [
+    ("byr", &["1920", "2002", "1919", "2003", "19a0", "200"]),
+    ("iyr", &["2010", "2020", "2009", "2021"]),
+    ("eyr", &["2020", "2030", "2019", "2031"]),
+    ("hgt", &["150cm", "193cm", "149cm", "194cm", "59in", "76in", "58in", "77in", "190", "99999999999cm"]),
+    ("hcl", &["#123abc", "#123abz", "123abc", "#12345"]),
+    ("ecl", &["amb", "blu", "brn", "gry", "grn", "hzl", "oth", "wat"]),
+    ("pid", &["000000001", "0123456789", "12345678a"]),
+    ("cid", &["147", "x"]),
+];
+
+/// whether `value` is a valid value of the passport field `name`
+fn valid_field(name: &str, value: &str) -> bool {
+    let digits = |s: &str, count: usize| s.len() == count && s.chars().all(|c| c.is_ascii_digit());
+    let year = |lo: u32, hi: u32| digits(value, 4) && (lo..=hi).contains(&value.parse().unwrap());
+    let height = |unit: &str, lo: u64, hi: u64| value.strip_suffix(unit)
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()) && n.parse().is_ok_and(|n| (lo..=hi).contains(&n)));
+    match name {
+        "byr" => year(1920, 2002),
+        "iyr" => year(2010, 2020),
+        "eyr" => year(2020, 2030),
+        "hgt" => height("cm", 150, 193) || height("in", 59, 76),
+        "hcl" => value.strip_prefix('#').is_some_and(|hex| hex.len() == 6 && hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'))),
+        "ecl" => ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&value),
+        "pid" => digits(value, 9),
+        _ => true,
+    }
+}
+
+/// the boarding pass for a seat id
+fn boarding_pass(id: u32) -> String {
+    let row = (0..7).rev().map(|bit| if (id >> 3) & (1 << bit) != 0 { 'B' } else { 'F' });
+    let col = (0..3).rev().map(|bit| if id & (1 << bit) != 0 { 'R' } else { 'L' });
+    row.chain(col).collect()
+}
+
+const BAG_NAMES: [&str; 8] = ["shiny gold", "light red", "dark orange", "bright white", "muted yellow", "faded blue", "dotted black", "vibrant plum"];
+
+/// run a boot code program, returning whether it ended and the accumulator when it ended or
+/// before an instruction was run twice
+fn run(program: &[(usize, i32)]) -> (bool, i64) {
+    let (mut pc, mut acc, mut seen) = (0_i64, 0, HashSet::new());
+    while (0..program.len() as i64).contains(&pc) {
+        if !seen.insert(pc) {
+            return (false, acc);
+        }
+        match program[pc as usize] {
+            (0, n) => { acc += i64::from(n); pc += 1 }
+            (1, n) => pc += i64::from(n),
+            _ => pc += 1,
+        }
+    }
+    (true, acc)
+}
+
+/// the seats once everyone has settled, where people leave when `tolerance` of the seats they
+/// look at are taken, and `far` people look past the floor
+fn settle(seats: &[Vec<char>], tolerance: usize, far: bool) -> usize {
+    let (h, w) = (seats.len() as i32, seats[0].len() as i32);
+    let mut seats = seats.to_vec();
+    loop {
+        let occupied = |seats: &[Vec<char>], x: i32, y: i32| {
+            let mut count = 0;
+            for (dx, dy) in [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)] {
+                let (mut cx, mut cy) = (x + dx, y + dy);
+                while (0..w).contains(&cx) && (0..h).contains(&cy) {
+                    match seats[cy as usize][cx as usize] {
+                        '#' => { count += 1; break }
+                        'L' => break,
+                        _ if !far => break,
+                        _ => (cx, cy) = (cx + dx, cy + dy),
+                    }
+                }
+            }
+            count
+        };
+        let mut next = seats.clone();
+        for y in 0..h {
+            for x in 0..w {
+                let seat = &mut next[y as usize][x as usize];
+                match (*seat, occupied(&seats, x, y)) {
+                    ('L', 0) => *seat = '#',
+                    ('#', n) if n >= tolerance => *seat = 'L',
+                    _ => (),
+                }
+            }
+        }
+        if next == seats {
+            return seats.iter().flatten().filter(|&&c| c == '#').count();
+        }
+        seats = next;
+    }
+}
+
+/// the manhattan distance sailed, moving the ship itself when `waypoint` is `None`
+fn sail(instructions: &[(char, i64)], waypoint: Option<(i64, i64)>) -> i64 {
+    let (mut ship, mut heading) = ((0, 0), waypoint.unwrap_or((1, 0)));
+    for &(action, n) in instructions {
+        let step = match action {
+            'N' => (0, n),
+            'S' => (0, -n),
+            'E' => (n, 0),
+            'W' => (-n, 0),
+            _ => (0, 0),
+        };
+        match action {
+            'L' | 'R' => {
+                let quarters = if action == 'L' { n / 90 } else { 4 - n / 90 };
+                for _ in 0..quarters {
+                    heading = (-heading.1, heading.0);
+                }
+            }
+            'F' => ship = (ship.0 + heading.0 * n, ship.1 + heading.1 * n),
+            _ if waypoint.is_some() => heading = (heading.0 + step.0, heading.1 + step.1),
+            _ => ship = (ship.0 + step.0, ship.1 + step.1),
+        }
+    }
+    ship.0.abs() + ship.1.abs()
+}
+
+fn program_masks() -> impl Strategy<Value = Vec<(String, Vec<(u64, u64)>)>> {
+    let mask = (vec(prop_oneof![Just('0'), Just('1')], 36), vec(0..36usize, 0..7))
+        .prop_map(|(mut bits, floating)| {
+            floating.into_iter().for_each(|i| bits[i] = 'X');
+            bits.into_iter().collect::<String>()
+        });
+    vec((mask, vec((0..64u64, 0..1u64 << 36), 1..5)), 1..5)
+}
+
+proptest! {
+    #[test]
+    fn parsing_never_panics(text in "[0-9a-zA-Z :,\\-+#\\[\\]=.\n]{0,200}") {
+        for day in DAYS {
+            if let Err(e) = day.parse(&text) {
+                prop_assert!(e.line <= text.lines().count() + 1, "day {}: {} is past the end", day.day, e);
+                prop_assert!(e.column >= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn report_repair(others in hash_set(1..2020i32, 0..10), a in 1..1010i32, (b, c) in (1..600i32, 600..1000i32)) {
+        let mut entries: HashSet<i32> = others;
+        entries.extend([a, 2020 - a, b, c, 2020 - b - c]);
+        let entries: Vec<i32> = entries.into_iter().collect();
+        let n = entries.len();
+        let pairs: Vec<i64> = (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .filter(|&(i, j)| entries[i] + entries[j] == 2020)
+            .map(|(i, j)| i64::from(entries[i]) * i64::from(entries[j]))
+            .collect();
+        let triples: Vec<i64> = (0..n)
+            .flat_map(|i| (i + 1..n).flat_map(move |j| (j + 1..n).map(move |k| (i, j, k))))
+            .filter(|&(i, j, k)| entries[i] + entries[j] + entries[k] == 2020)
+            .map(|(i, j, k)| i64::from(entries[i]) * i64::from(entries[j]) * i64::from(entries[k]))
+            .collect();
+        prop_assume!(pairs.len() == 1 && triples.len() == 1);
+        let input = lines(&entries);
+        prop_assert_eq!(solve(1, &input, Part::One), pairs[0].to_string());
+        prop_assert_eq!(solve(1, &input, Part::Two), triples[0].to_string());
+    }
+
+    #[test]
+    fn password_philosophy(policies in vec((1..5usize, 0..5usize, proptest::char::range('a', 'e'), "[a-e]{1,10}"), 1..30)) {
+        let input = lines(&policies.iter().map(|(min, extra, c, pwd)| format!("{}-{} {}: {}", min, min + extra, c, pwd)).collect::<Vec<_>>());
+        let counted = policies.iter()
+            .filter(|(min, extra, c, pwd)| (*min..=min + extra).contains(&pwd.matches(*c).count()))
+            .count();
+        let positioned = policies.iter()
+            .filter(|(min, extra, c, pwd)| {
+                let at = |pos: usize| pwd.chars().nth(pos - 1) == Some(*c);
+                at(*min) != at(min + extra)
+            })
+            .count();
+        prop_assert_eq!(solve(2, &input, Part::One), counted.to_string());
+        prop_assert_eq!(solve(2, &input, Part::Two), positioned.to_string());
+    }
+
+    #[test]
+    fn toboggan_trajectory(trees in (1..12usize, 1..12usize).prop_flat_map(|(w, h)| vec(vec(any::<bool>(), w), h))) {
+        let input = lines(&trees.iter().map(|row| row.iter().map(|&t| if t { '#' } else { '.' }).collect::<String>()).collect::<Vec<_>>());
+        let product: usize = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)].iter().map(|&(r, d)| trees_hit(&trees, r, d)).product();
+        prop_assert_eq!(solve(3, &input, Part::One), trees_hit(&trees, 3, 1).to_string());
+        prop_assert_eq!(solve(3, &input, Part::Two), product.to_string());
+    }
+
+    #[test]
+    fn passport_processing(passports in vec((vec(weighted(0.8, 0..10usize), 8), Just((0..8).collect::<Vec<usize>>()).prop_shuffle(), vec(any::<bool>(), 8)), 1..12)) {
+        // passports without fields are left out, they would merge into the blank lines
+        let passports: Vec<Vec<(&str, &str, bool)>> = passports.iter()
+            .map(|(values, order, newlines)| order.iter()
+                .filter_map(|&f| values[f].map(|v| {
+                    let (name, candidates) = PASSPORT_FIELDS[f];
+                    (name, candidates[v % candidates.len()], newlines[f])
+                }))
+                .collect::<Vec<_>>())
+            .filter(|fields| !fields.is_empty())
+            .collect();
+        prop_assume!(!passports.is_empty());
+        let input = passports.iter()
+            .map(|fields| fields.iter()
+                .enumerate()
+                .map(|(i, (name, value, newline))| {
+                    let separator = if i == 0 { "" } else if *newline { "\n" } else { " " };
+                    format!("{}{}:{}", separator, name, value)
+                })
+                .collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let required = |fields: &Vec<(&str, &str, bool)>| PASSPORT_FIELDS[..7].iter().all(|(name, _)| fields.iter().any(|f| f.0 == *name));
+        let complete = passports.iter().filter(|p| required(p)).count();
+        let valid = passports.iter().filter(|p| required(p) && p.iter().all(|(name, value, _)| valid_field(name, value))).count();
+        prop_assert_eq!(solve(4, &input, Part::One), complete.to_string());
+        prop_assert_eq!(solve(4, &input, Part::Two), valid.to_string());
+    }
+
+    #[test]
+    fn binary_boarding(seats in (1..800u32, 3..100u32).prop_flat_map(|(first, count)| (Just(first), Just(count), 1..count - 1))
+        .prop_flat_map(|(first, count, missing)| {
+            let ids: Vec<u32> = (first..first + count).filter(|&id| id != first + missing).collect();
+            (Just(ids).prop_shuffle(), Just(first + missing))
+        })) {
+        let (ids, missing) = seats;
+        let input = lines(&ids.iter().map(|&id| boarding_pass(id)).collect::<Vec<_>>());
+        prop_assert_eq!(solve(5, &input, Part::One), ids.iter().max().unwrap().to_string());
+        prop_assert_eq!(solve(5, &input, Part::Two), missing.to_string());
+    }
+
+    #[test]
+    fn custom_customs(groups in vec(vec("[a-z]{1,8}", 1..5), 1..10)) {
+        let input = groups.iter().map(|group| group.join("\n")).collect::<Vec<_>>().join("\n\n");
+        let answers = |person: &String| person.chars().collect::<HashSet<char>>();
+        let anyone: usize = groups.iter().map(|g| g.iter().flat_map(|p| p.chars()).collect::<HashSet<_>>().len()).sum();
+        let everyone: usize = groups.iter()
+            .map(|g| g.iter().map(answers).reduce(|all, person| &all & &person).unwrap().len())
+            .sum();
+        prop_assert_eq!(solve(6, &input, Part::One), anyone.to_string());
+        prop_assert_eq!(solve(6, &input, Part::Two), everyone.to_string());
+    }
+
+    #[test]
+    fn handy_haversacks(
+        count in 2..=BAG_NAMES.len(),
+        names in Just(BAG_NAMES.to_vec()).prop_shuffle(),
+        contents in vec(vec(weighted(0.3, 1..5usize), BAG_NAMES.len()), BAG_NAMES.len()),
+    ) {
+        // a bag only contains bags after it, so no bag contains itself
+        let contents: Vec<Vec<(usize, usize)>> = (0..count)
+            .map(|i| (i + 1..count).filter_map(|j| contents[i][j].map(|n| (n, j))).collect())
+            .collect();
+        let rules: Vec<String> = (0..count)
+            .map(|i| {
+                let inner = match contents[i].as_slice() {
+                    [] => "no other bags".to_string(),
+                    bags => bags.iter()
+                        .map(|&(n, j)| format!("{} {} bag{}", n, names[j], if n == 1 { "" } else { "s" }))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                };
+                format!("{} bags contain {}.", names[i], inner)
+            })
+            .collect();
+        let input = lines(&rules);
+
+        fn holds_gold(bag: usize, names: &[&str], contents: &[Vec<(usize, usize)>]) -> bool {
+            contents[bag].iter().any(|&(_, j)| names[j] == "shiny gold" || holds_gold(j, names, contents))
+        }
+        fn inside(bag: usize, contents: &[Vec<(usize, usize)>]) -> usize {
+            contents[bag].iter().map(|&(n, j)| n * (1 + inside(j, contents))).sum()
+        }
+        let holders = (0..count).filter(|&i| holds_gold(i, &names, &contents)).count();
+        let inside_gold = (0..count).find(|&i| names[i] == "shiny gold").map_or(0, |gold| inside(gold, &contents));
+        prop_assert_eq!(solve(7, &input, Part::One), holders.to_string());
+        prop_assert_eq!(solve(7, &input, Part::Two), inside_gold.to_string());
+    }
+
+    #[test]
+    fn handheld_halting(program in vec((0..3usize, -5..6i32), 2..15)) {
+        let input = lines(&program.iter().map(|&(op, n)| format!("{} {:+}", ["acc", "jmp", "nop"][op], n)).collect::<Vec<_>>());
+        let (ended, acc) = run(&program);
+        let repaired: Vec<i64> = (0..program.len())
+            .filter(|&i| program[i].0 != 0)
+            .filter_map(|i| {
+                let mut swapped = program.clone();
+                swapped[i].0 = 3 - swapped[i].0;
+                match run(&swapped) {
+                    (true, acc) => Some(acc),
+                    _ => None,
+                }
+            })
+            .collect();
+        prop_assume!(ended || repaired.len() == 1);
+        prop_assert_eq!(solve(8, &input, Part::One), acc.to_string());
+        prop_assert_eq!(solve(8, &input, Part::Two), if ended { acc } else { repaired[0] }.to_string());
+    }
+
+    #[test]
+    fn encoding_error(
+        preamble in hash_set(1..1000i64, 25),
+        sums in vec((0..25usize, 1..25usize), 0..10),
+        (start, len) in (0..30usize, 2..6usize),
+        trailing in vec(1..3000i64, 0..5),
+    ) {
+        let mut nums: Vec<i64> = preamble.into_iter().collect();
+        // valid numbers, each the sum of two different numbers among the 25 before it
+        for (i, j) in sums {
+            let window = &nums[nums.len() - 25..];
+            let (a, b) = (window[i], window[(i + j) % 25]);
+            prop_assume!(a != b);
+            nums.push(a + b);
+        }
+        let start = start % (nums.len() - len);
+        let invalid: i64 = nums[start..start + len].iter().sum();
+        let window = &nums[nums.len() - 25..];
+        prop_assume!(!window.iter().any(|&a| window.iter().any(|&b| a != b && a + b == invalid)));
+        nums.push(invalid);
+        nums.extend(trailing);
+
+        let ranges: Vec<&[i64]> = (2..nums.len())
+            .flat_map(|len| nums.windows(len))
+            .filter(|range| range.iter().sum::<i64>() == invalid)
+            .collect();
+        prop_assume!(ranges.len() == 1);
+        let weakness = ranges[0].iter().min().unwrap() + ranges[0].iter().max().unwrap();
+        let input = lines(&nums);
+        prop_assert_eq!(solve(9, &input, Part::One), invalid.to_string());
+        prop_assert_eq!(solve(9, &input, Part::Two), weakness.to_string());
+    }
+
+    #[test]
+    fn seating_system(seats in (1..8usize, 1..8usize).prop_flat_map(|(w, h)| vec(vec(prop_oneof![3 => Just('L'), 1 => Just('.')], w), h))) {
+        let input = lines(&seats.iter().map(|row| row.iter().collect::<String>()).collect::<Vec<_>>());
+        prop_assert_eq!(solve(11, &input, Part::One), settle(&seats, 4, false).to_string());
+        prop_assert_eq!(solve(11, &input, Part::Two), settle(&seats, 5, true).to_string());
+    }
+
+    #[test]
+    fn rain_risk(instructions in vec(prop_oneof![
+        (prop_oneof![Just('N'), Just('S'), Just('E'), Just('W'), Just('F')], 0..100i64),
+        (prop_oneof![Just('L'), Just('R')], prop_oneof![Just(90i64), Just(180), Just(270)]),
+    ], 1..30)) {
+        let input = lines(&instructions.iter().map(|(action, n)| format!("{}{}", action, n)).collect::<Vec<_>>());
+        prop_assert_eq!(solve(12, &input, Part::One), sail(&instructions, None).to_string());
+        prop_assert_eq!(solve(12, &input, Part::Two), sail(&instructions, Some((10, 1))).to_string());
+    }
+
+    #[test]
+    fn shuttle_search(
+        timestamp in 1..1000u64,
+        slots in vec(any::<bool>(), 1..8),
+        primes in Just(vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43]).prop_shuffle(),
+    ) {
+        // at most four buses, with distinct prime ids
+        let mut primes = primes.into_iter().take(4);
+        let buses: Vec<Option<u64>> = slots.iter().map(|&bus| if bus { primes.next() } else { None }).collect();
+        prop_assume!(buses.iter().any(Option::is_some));
+        let ids = buses.iter().map(|bus| bus.map_or("x".to_string(), |id| id.to_string())).collect::<Vec<_>>().join(",");
+        let input = format!("{}\n{}\n", timestamp, ids);
+
+        let departing = |t: u64| buses.iter().flatten().filter(move |&&id| t.is_multiple_of(id)).copied().collect::<Vec<_>>();
+        let first = (timestamp..).find(|&t| !departing(t).is_empty()).unwrap();
+        prop_assume!(departing(first).len() == 1);
+        let in_order = (0..).find(|&t| buses.iter().enumerate().all(|(offset, bus)| bus.is_none_or(|id| (t + offset as u64).is_multiple_of(id)))).unwrap();
+        prop_assert_eq!(solve(13, &input, Part::One), (departing(first)[0] * (first - timestamp)).to_string());
+        prop_assert_eq!(solve(13, &input, Part::Two), in_order.to_string());
+    }
+
+    #[test]
+    fn docking_data(program in program_masks()) {
+        let input = program.iter()
+            .flat_map(|(mask, writes)| std::iter::once(format!("mask = {}", mask))
+                .chain(writes.iter().map(|(address, value)| format!("mem[{}] = {}", address, value))))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let (mut values, mut addresses) = (HashMap::new(), HashMap::new());
+        for (mask, writes) in &program {
+            let bits: Vec<(usize, char)> = mask.chars().rev().enumerate().collect();
+            for &(address, value) in writes {
+                let masked = bits.iter().fold(value, |v, &(i, m)| match m {
+                    '0' => v & !(1 << i),
+                    '1' => v | (1 << i),
+                    _ => v,
+                });
+                values.insert(address, masked);
+                let ones = bits.iter().fold(address, |a, &(i, m)| if m == '1' { a | (1 << i) } else { a });
+                let floating: Vec<usize> = bits.iter().filter(|(_, m)| *m == 'X').map(|&(i, _)| i).collect();
+                for choice in 0..1u64 << floating.len() {
+                    let written = floating.iter().enumerate().fold(ones, |a, (n, &i)| {
+                        if choice & (1 << n) != 0 { a | (1 << i) } else { a & !(1 << i) }
+                    });
+                    addresses.insert(written, value);
+                }
+            }
+        }
+        prop_assert_eq!(solve(14, &input, Part::One), values.values().sum::<u64>().to_string());
+        prop_assert_eq!(solve(14, &input, Part::Two), addresses.values().sum::<u64>().to_string());
+    }
+}
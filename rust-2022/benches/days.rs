@@ -0,0 +1,18 @@
+// Benchmarks parsing and each part separately for every registered day
+//
+// run all days with `cargo bench`, or some of them with `cargo bench -- day07`. Each day uses
+// its real input, or its first example input if the real one isn't in the input directory.
+//
+// Criterion compares every run with the previous one. To compare against a fixed baseline, save
+// one with `cargo bench -- --save-baseline <name>` and then run `cargo bench -- --baseline <name>`.
+// Baselines are kept in `target/criterion`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rust_2022::{DAYS, YEAR};
+
+fn bench_days(c: &mut Criterion) {
+    aoc_common::bench::bench_days(c, DAYS, YEAR);
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
@@ -0,0 +1,8 @@
+// Command line runner for the 2022 solutions, see `aoc_common::cli` for the commands
+
+use std::process::ExitCode;
+use rust_2022::{DAYS, YEAR};
+
+fn main() -> ExitCode {
+    aoc_common::cli::main(YEAR, DAYS)
+}
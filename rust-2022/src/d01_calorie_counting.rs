@@ -1,64 +1,202 @@
-use std::fs::File;
-use std::path::Path;
-use std::io::{BufRead, BufReader};
-
-fn count_calories_p1(file: &Path) -> Vec<i32> {
-    let f = File::open(file).expect("input file can be found");
-    let reader = BufReader::new(f);
-
-    let mut sum = 0_i32;
-    let mut sums = Vec::new();
-    for line in reader.lines() {
-        match line {
-            Ok(l) if l.is_empty() => {
-                sums.push(sum);
-                sum = 0;
-            },
-            Ok(l) =>
-                sum += l.parse::<i32>().expect("valid i32"),
-            Err(e) =>
-                panic!("error reading input file {}", e)
+use std::cmp::Reverse;
+use std::str::FromStr;
+use aoc_common::parse_error::ParseError;
+use aoc_common::scan::blocks;
+use aoc_common::solution::Solution;
+
+/// The calories of the food items each elf carries. Elves are numbered from 0 in the order they
+/// appear in the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inventory {
+    /// the calories of each item, for each elf
+    items: Vec<Vec<u32>>,
+    /// the total calories carried by each elf
+    totals: Vec<u64>,
+}
+
+impl FromStr for Inventory {
+    type Err = ParseError;
+
+    /// parse the calories of each item, one per line, with the elves separated by blank lines
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let items = blocks(input).iter()
+            .map(|block| {
+                block.lines()
+                    .map(|mut line| {
+                        let calories = line.unsigned::<u32>("a calorie amount")?;
+                        line.end()?;
+                        Ok(calories)
+                    })
+                    .collect::<Result<Vec<u32>, ParseError>>()
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        if items.is_empty() {
+            return Err(ParseError::new(input, "the calories carried by at least one elf"));
         }
+        let totals = items.iter().map(|elf| elf.iter().map(|&c| c as u64).sum()).collect();
+        Ok(Self { items, totals })
+    }
+}
+
+impl Inventory {
+    /// the number of elves
+    pub fn len(&self) -> usize {
+        self.totals.len()
+    }
+
+    /// true if there are no elves, which parsing never returns
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
     }
 
-    // find max element, retaining the index of that element
-    let max = sums
-        .iter()
-        .enumerate()
-        .max_by(|&(_x_idx, x_val), &(_y_idx, y_val)| x_val.cmp(y_val))
-        .expect("iterator should have at least one element");
-    println!("elf with max calories is {} with calories = {}", max.0 + 1, max.1);
+    /// the total calories carried by each elf
+    pub fn totals(&self) -> &[u64] {
+        &self.totals
+    }
 
+    /// the total calories carried by `elf`
+    pub fn total(&self, elf: usize) -> Option<u64> {
+        self.totals.get(elf).copied()
+    }
 
-    sums
+    /// the number of food items `elf` carries
+    pub fn item_count(&self, elf: usize) -> Option<usize> {
+        self.items.get(elf).map(Vec::len)
+    }
+
+    /// the `n` largest totals, largest first. Only the top `n` are sorted, the rest are just
+    /// partitioned off
+    pub fn top(&self, n: usize) -> Vec<u64> {
+        let mut totals = self.totals.clone();
+        if n < totals.len() {
+            totals.select_nth_unstable_by_key(n, |&total| Reverse(total));
+            totals.truncate(n);
+        }
+        totals.sort_unstable_by_key(|&total| Reverse(total));
+        totals
+    }
+
+    /// the rank of `elf` by the calories it carries, 1 for the most. Elves carrying the same
+    /// total share a rank
+    pub fn rank(&self, elf: usize) -> Option<usize> {
+        let total = self.total(elf)?;
+        Some(1 + self.totals.iter().filter(|&&other| other > total).count())
+    }
+
+    /// the median total, the mean of the middle two when there is an even number of elves
+    pub fn median(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut totals = self.totals.clone();
+        let (below, &mut middle, _) = totals.select_nth_unstable(self.len() / 2);
+        if self.len() % 2 == 1 {
+            Some(middle as f64)
+        } else {
+            // the other middle total is the largest of the ones below
+            let other = below.iter().max().copied().unwrap_or(middle);
+            Some((other + middle) as f64 / 2.0)
+        }
+    }
+
+    /// the total that `p` percent of the elves carry no more than, by the nearest rank method
+    pub fn percentile(&self, p: f64) -> Option<u64> {
+        if self.is_empty() || !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+        let rank = ((p / 100.0 * self.len() as f64).ceil() as usize).max(1);
+        let mut totals = self.totals.clone();
+        Some(*totals.select_nth_unstable(rank - 1).1)
+    }
 }
 
-fn find_top_three(calories: &mut [i32]) -> i32 {
-    calories.sort_by(|x, y| y.cmp(x));
-    let top3: Vec<&i32> = calories.iter().take(3).collect();
-    dbg!(&top3);
-    let top3_sum: i32 = top3.into_iter().sum();
-    println!("sum of max 3 calories = {}", &top3_sum);
-    top3_sum
+/// Day 01 - Calorie Counting
+pub struct CalorieCounting;
+
+impl Solution for CalorieCounting {
+    type Input = Inventory;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse::<Inventory>()
+    }
+
+    /// find the elf carrying the most calories
+    fn part1(inventory: &Inventory) -> u64 {
+        inventory.top(1).iter().sum()
+    }
+
+    /// the calories carried by the three elves carrying the most
+    fn part2(inventory: &Inventory) -> u64 {
+        inventory.top(3).iter().sum()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
-    use crate::d01_calorie_counting::{count_calories_p1, find_top_three};
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use crate::d01_calorie_counting::{CalorieCounting, Inventory};
+    use aoc_common::solution::Solution;
+
+    fn example() -> Inventory {
+        read_input(YEAR, 1, InputKind::Example(1)).unwrap().parse().unwrap()
+    }
 
     #[test]
     fn test_part1() {
-        let path = Path::new("../input-2022/d01-input.txt");
-        let sums = count_calories_p1(path);
-        assert_eq!(sums[0], 33480);
+        let inventory = example();
+        assert_eq!(inventory.totals(), [6000, 4000, 11000, 24000, 10000]);
+        assert_eq!(CalorieCounting::part1(&inventory), 24000);
     }
 
     #[test]
     fn test_part2() {
-        let path = Path::new("../input-2022/d01-input.txt");
-        let mut sums:Vec<i32> = count_calories_p1(path);
-        let sum = find_top_three(&mut sums);
-        assert_eq!(sum, 207456);
+        assert_eq!(CalorieCounting::part2(&example()), 45000);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn top_and_rank() {
+        let inventory = example();
+        assert_eq!(inventory.top(2), [24000, 11000]);
+        assert_eq!(inventory.top(9), [24000, 11000, 10000, 6000, 4000]);
+        assert!(inventory.top(0).is_empty());
+        assert_eq!(inventory.rank(3), Some(1));
+        assert_eq!(inventory.rank(1), Some(5));
+        assert_eq!(inventory.rank(5), None);
+        let tied: Inventory = "5\n\n7\n\n5".parse().unwrap();
+        assert_eq!((tied.rank(0), tied.rank(1), tied.rank(2)), (Some(2), Some(1), Some(2)));
+    }
+
+    #[test]
+    fn item_counts_and_stats() {
+        let inventory = example();
+        assert_eq!(inventory.item_count(0), Some(3));
+        assert_eq!(inventory.item_count(1), Some(1));
+        assert_eq!(inventory.item_count(5), None);
+        assert_eq!(inventory.median(), Some(10000.0));
+        assert_eq!("1\n\n4".parse::<Inventory>().unwrap().median(), Some(2.5));
+        assert_eq!(inventory.percentile(0.0), Some(4000));
+        assert_eq!(inventory.percentile(40.0), Some(6000));
+        assert_eq!(inventory.percentile(100.0), Some(24000));
+        assert_eq!(inventory.percentile(101.0), None);
+    }
+
+    #[test]
+    fn last_elf_is_counted_without_a_blank_line() {
+        let inventory: Inventory = "1000\n2000\n\n3000".parse().unwrap();
+        assert_eq!(inventory.totals(), [3000, 3000]);
+        // extra blank lines, even with spaces on them, don't add empty elves
+        let inventory: Inventory = "1000\n\n \n\n2000\n\n".parse().unwrap();
+        assert_eq!(inventory.totals(), [1000, 2000]);
+    }
+
+    #[test]
+    fn invalid_calories_are_parse_errors() {
+        let err = "1000\n\n2x00".parse::<Inventory>().unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 2, "x00"));
+        assert!("".parse::<Inventory>().is_err());
+        assert!("-5".parse::<Inventory>().is_err());
+    }
+}
@@ -1,15 +1,19 @@
-use std::fmt::{Debug, Display, Formatter};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::fmt::{Display, Formatter};
+use aoc_common::parse_error::ParseError;
+use aoc_common::scan::{lines, Scanner};
+use aoc_common::solution::Solution;
 
-#[derive(Debug, Eq, PartialEq)]
-enum Outcome {
+/// the result of a round, for the player
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Outcome {
     Loss,
     Draw,
     Win,
 }
+
 impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
     pub const fn score(&self) -> i32 {
         match *self {
             Outcome::Loss => 0,
@@ -18,160 +22,538 @@ impl Outcome {
         }
     }
 }
-impl From<&str> for Outcome {
-    fn from(s: &str) -> Self {
-        match s {
-            "X" => Outcome::Loss,
-            "Y" => Outcome::Draw,
-            "Z" => Outcome::Win,
-            _ => panic!("invalid input string for Outcome: {}", &s),
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Outcome::Loss => f.write_str("loss"),
+            Outcome::Draw => f.write_str("draw"),
+            Outcome::Win => f.write_str("win"),
         }
     }
 }
 
+/// a weapon of a `Game`, numbered from 0 in the order the game lists its weapons
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Hand(pub usize);
 
+impl Hand {
+    /// playing a hand scores its position in the game's weapons, starting at 1
+    pub const fn score(&self) -> i32 {
+        self.0 as i32 + 1
+    }
+}
 
-#[derive(Debug, Eq, PartialEq)]
-enum Hand {
-    Rock,
-    Paper,
-    Scissors,
+/// A hand game like rock paper scissors, defined by its weapons and which weapons beat which
+#[derive(Debug, Clone)]
+pub struct Game {
+    weapons: Vec<String>,
+    /// `beats[a][b]` is true when weapon `a` beats weapon `b`
+    beats: Vec<Vec<bool>>,
 }
 
-impl Hand {
-    pub const fn score(&self) -> i32 {
-        match *self {
-            Hand::Rock => 1,
-            Hand::Paper => 2,
-            Hand::Scissors => 3,
+impl Game {
+    /// a game of `weapons` where the first weapon of each of the `beats` pairs beats the second.
+    /// Pairs that aren't listed are a draw
+    pub fn new(weapons: &[&str], beats: &[(&str, &str)]) -> Result<Self, String> {
+        let index = |name: &str| weapons.iter()
+            .position(|&weapon| weapon == name)
+            .ok_or_else(|| format!("'{}' is not one of the weapons", name));
+        let mut relation = vec![vec![false; weapons.len()]; weapons.len()];
+        for &(winner, loser) in beats {
+            let (w, l) = (index(winner)?, index(loser)?);
+            if w == l || relation[l][w] {
+                return Err(format!("'{}' and '{}' can't beat each other", winner, loser));
+            }
+            relation[w][l] = true;
         }
+        Ok(Self { weapons: weapons.iter().map(|w| w.to_string()).collect(), beats: relation })
     }
-}
 
-impl From<&str> for Hand {
-    fn from(s: &str) -> Self {
-        match s {
-            "A" | "X" => Hand::Rock,
-            "B" | "Y" => Hand::Paper,
-            "C" | "Z" => Hand::Scissors,
-            _ => panic!("invalid input string for Hand: {}", &s),
+    /// a fair game of an odd number of weapons, where each weapon beats the weapons an odd
+    /// number of places before it in the list, wrapping around, and loses to the rest
+    pub fn cyclic(weapons: &[&str]) -> Result<Self, String> {
+        let n = weapons.len();
+        if n.is_multiple_of(2) {
+            return Err(format!("a cyclic game needs an odd number of weapons, not {}", n));
         }
+        let beats = (0..n)
+            .flat_map(|w| (0..n).filter(move |&l| (w + n - l) % n % 2 == 1).map(move |l| (weapons[w], weapons[l])))
+            .collect::<Vec<_>>();
+        Self::new(weapons, &beats)
     }
-}
 
-impl Display for Hand {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match *self {
-            Hand::Rock => f.write_str("ROCK"),
-            Hand::Paper => f.write_str("PAPER"),
-            Hand::Scissors => f.write_str("SCISSORS"),
+    /// rock beats scissors, scissors beats paper and paper beats rock
+    pub fn rock_paper_scissors() -> Self {
+        Self::cyclic(&["rock", "paper", "scissors"]).expect("three weapons is odd")
+    }
+
+    /// rock paper scissors, with lizard and spock
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::cyclic(&["rock", "paper", "scissors", "spock", "lizard"]).expect("five weapons is odd")
+    }
+
+    /// every hand that can be played, in order
+    pub fn hands(&self) -> impl Iterator<Item = Hand> {
+        (0..self.weapons.len()).map(Hand)
+    }
+
+    pub fn name(&self, hand: Hand) -> &str {
+        &self.weapons[hand.0]
+    }
+
+    /// the hand for the weapon called `name`
+    pub fn hand(&self, name: &str) -> Option<Hand> {
+        self.weapons.iter().position(|weapon| weapon == name).map(Hand)
+    }
+
+    /// the outcome for the player of a round of `player` against `opponent`
+    pub fn outcome(&self, opponent: Hand, player: Hand) -> Outcome {
+        match (self.beats[player.0][opponent.0], self.beats[opponent.0][player.0]) {
+            (true, _) => Outcome::Win,
+            (_, true) => Outcome::Loss,
+            _ => Outcome::Draw,
         }
     }
+
+    /// the hand to play against `opponent` for the round to end in `outcome`, the highest scoring
+    /// if there is more than one, or `None` if there is none
+    pub fn hand_for(&self, opponent: Hand, outcome: Outcome) -> Option<Hand> {
+        self.hands()
+            .filter(|&player| self.outcome(opponent, player) == outcome)
+            .max_by_key(Hand::score)
+    }
+
+    /// the player's score for a round: the score of their hand and of the outcome
+    pub fn round_score(&self, opponent: Hand, player: Hand) -> i32 {
+        self.outcome(opponent, player).score() + player.score()
+    }
+}
+
+/// How the letters of the strategy guide are read. The first column is the opponent's hand. The
+/// second column is read both as the hand the player should play (part 1) and as the outcome the
+/// round should end in (part 2)
+#[derive(Debug, Clone)]
+pub struct Decoding {
+    pub opponent: Vec<(char, Hand)>,
+    pub player: Vec<(char, Hand)>,
+    pub outcome: Vec<(char, Outcome)>,
 }
 
-/// compute the score for a round of rock paper scissors
-fn round_score(opponent: &Hand, player: &Hand) -> i32 {
-    let round_score = match (opponent, player) {
-        (Hand::Rock, Hand::Paper) => Outcome::Win.score(),
-        (Hand::Rock, Hand::Scissors) => Outcome::Loss.score(),
-        (Hand::Paper, Hand::Rock) => Outcome::Loss.score(),
-        (Hand::Paper, Hand::Scissors) => Outcome::Win.score(),
-        (Hand::Scissors, Hand::Rock) => Outcome::Win.score(),
-        (Hand::Scissors, Hand::Paper) => Outcome::Loss.score(),
-        _ => Outcome::Draw.score()
-    };
-    round_score + player.score()
-}
-
-/// determine what hand you should play given your opponents hand and a desired outcome
-fn determine_hand(opponent: &Hand, outcome: &Outcome) -> Hand {
-    match (opponent, outcome) {
-        (Hand::Rock, Outcome::Loss) => Hand::Scissors,
-        (Hand::Rock, Outcome::Draw) => Hand::Rock,
-        (Hand::Rock, Outcome::Win) => Hand::Paper,
-        (Hand::Paper, Outcome::Loss) => Hand::Rock,
-        (Hand::Paper, Outcome::Draw) => Hand::Paper,
-        (Hand::Paper, Outcome::Win) => Hand::Scissors,
-        (Hand::Scissors, Outcome::Loss) => Hand::Paper,
-        (Hand::Scissors, Outcome::Draw) => Hand::Scissors,
-        (Hand::Scissors, Outcome::Win) => Hand::Rock,
-    }
-}
-
-fn total_score_part1(file: &Path) -> i32 {
-    let f = File::open(file).expect("input file can be found");
-    let reader = BufReader::new(f);
-
-    let mut total_score: i32 = 0;
-    for line in reader.lines() {
-        if let Ok(l) = line {
-            let mut split = l.split_ascii_whitespace();
-            let opponent = Hand::from(split.next().expect("valid input line"));
-            let player = Hand::from(split.next().expect("valid input line"));
-            total_score += round_score(&opponent, &player);
+impl Decoding {
+    /// the puzzle's reading of a guide for `game`. The first column's letters are the weapons in
+    /// order from A, and the second column's the weapons in order ending at Z, so X, Y, Z for
+    /// three weapons. X, Y and Z are also a loss, a draw and a win. Fails for a game of more than
+    /// 26 weapons, as there aren't enough letters
+    pub fn standard(game: &Game) -> Result<Self, String> {
+        let n = game.hands().count();
+        if n > 26 {
+            return Err(format!("a guide's columns have letters for at most 26 weapons, not {}", n));
         }
+        let letters = |first: u8| game.hands().map(move |hand| ((first + hand.0 as u8) as char, hand));
+        Ok(Self {
+            opponent: letters(b'A').collect(),
+            player: letters(b'Z' + 1 - n as u8).collect(),
+            outcome: ['X', 'Y', 'Z'].into_iter().zip(Outcome::ALL).collect(),
+        })
+    }
+
+    /// the letters of the first column, and the letters of the second column that can be read
+    /// both as a hand and as an outcome
+    fn letters(&self) -> (Vec<char>, Vec<char>) {
+        let first = self.opponent.iter().map(|&(c, _)| c).collect();
+        let second = self.player.iter()
+            .map(|&(c, _)| c)
+            .filter(|&c| Self::decode(&self.outcome, c).is_some())
+            .collect();
+        (first, second)
+    }
+
+    /// the meaning of every letter, like `A=rock B=paper C=scissors, X=rock Y=paper Z=scissors,
+    /// X=loss Y=draw Z=win`
+    pub fn describe(&self, game: &Game) -> String {
+        let hands = |column: &[(char, Hand)]| column.iter()
+            .map(|&(c, hand)| format!("{}={}", c, game.name(hand)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let outcomes = self.outcome.iter().map(|(c, outcome)| format!("{}={}", c, outcome)).collect::<Vec<_>>();
+        format!("{}, {}, {}", hands(&self.opponent), hands(&self.player), outcomes.join(" "))
     }
 
-    total_score
+    /// the meaning of `letter` in `column`, or `None` if it has none
+    fn decode<T: Copy>(column: &[(char, T)], letter: char) -> Option<T> {
+        column.iter()
+            .find(|&&(c, _)| c == letter)
+            .map(|&(_, value)| value)
+    }
 }
 
-fn total_score_part2(file: &Path) -> i32 {
-    let f = File::open(file).expect("input file can be found");
-    let reader = BufReader::new(f);
+/// a line of the strategy guide, the letters of its two columns
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Round {
+    opponent: char,
+    player: char,
+}
 
-    let mut total_score: i32 = 0;
-    for line in reader.lines() {
-        if let Ok(l) = line {
-            let mut split = l.split_ascii_whitespace();
-            let opponent = Hand::from(split.next().expect("valid input line"));
-            let outcome = Outcome::from(split.next().expect("valid input line"));
-            total_score += round_score(&opponent, &determine_hand(&opponent, &outcome));
-        }
+pub type StrategyGuide = Vec<Round>;
+
+/// the puzzle's game, and its reading of the guide
+fn puzzle() -> (Game, Decoding) {
+    let game = Game::rock_paper_scissors();
+    let decoding = Decoding::standard(&game).expect("rock paper scissors has letters for its weapons");
+    (game, decoding)
+}
+
+/// Day 02 - Rock Paper Scissors
+pub struct RockPaperScissors;
+
+impl Solution for RockPaperScissors {
+    type Input = StrategyGuide;
+    type Answer1 = Result<i32, String>;
+    type Answer2 = Result<i32, String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_guide(input, &puzzle().1)
+    }
+
+    fn part1(guide: &StrategyGuide) -> Result<i32, String> {
+        let (game, decoding) = puzzle();
+        score_as_hands(&game, &decoding, guide)
+    }
+
+    fn part2(guide: &StrategyGuide) -> Result<i32, String> {
+        let (game, decoding) = puzzle();
+        score_as_outcomes(&game, &decoding, guide)
+    }
+
+    fn report(guide: &StrategyGuide) -> Option<String> {
+        let (game, decoding) = puzzle();
+        Some(report(&game, &decoding, guide))
+    }
+}
+
+/// one of `letters`, as the whole of the next word on the line
+fn letter(line: &mut Scanner, letters: &[char]) -> Result<char, ParseError> {
+    let expected = format!("one of {}", letters.iter().map(char::to_string).collect::<Vec<_>>().join(", "));
+    let word = line.word(&expected)?;
+    let mut chars = word.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if letters.contains(&c) => Ok(c),
+        _ => Err(line.error(word, expected)),
+    }
+}
+
+/// parse a guide whose columns use the letters of `decoding`. The letters of the second column
+/// must have both a hand and an outcome, so that the guide can be scored either way
+pub fn parse_guide(input: &str, decoding: &Decoding) -> Result<StrategyGuide, ParseError> {
+    let (first, second) = decoding.letters();
+    lines(input, |line| {
+        let opponent = letter(line, &first)?;
+        line.skip_spaces();
+        let player = letter(line, &second)?;
+        line.skip_spaces();
+        Ok(Round { opponent, player })
+    })
+}
+
+/// the opponent's hand in `round`, read with `decoding`
+fn opponent(decoding: &Decoding, round: &Round) -> Result<Hand, String> {
+    Decoding::decode(&decoding.opponent, round.opponent)
+        .ok_or_else(|| format!("'{}' is not an opponent's hand", round.opponent))
+}
+
+/// total score when the second column is the hand to play. Fails if `decoding` has no meaning
+/// for one of the guide's letters, which can only happen if it was parsed with another decoding
+pub fn score_as_hands(game: &Game, decoding: &Decoding, guide: &StrategyGuide) -> Result<i32, String> {
+    guide.iter()
+        .map(|round| {
+            let player = Decoding::decode(&decoding.player, round.player)
+                .ok_or_else(|| format!("'{}' is not a hand to play", round.player))?;
+            Ok(game.round_score(opponent(decoding, round)?, player))
+        })
+        .sum()
+}
+
+/// total score when the second column is the outcome the round needs to end in. Fails like
+/// `score_as_hands`, or if the game has a round that can't end that way
+pub fn score_as_outcomes(game: &Game, decoding: &Decoding, guide: &StrategyGuide) -> Result<i32, String> {
+    guide.iter()
+        .map(|round| {
+            let opponent = opponent(decoding, round)?;
+            let outcome = Decoding::decode(&decoding.outcome, round.player)
+                .ok_or_else(|| format!("'{}' is not an outcome", round.player))?;
+            let player = game.hand_for(opponent, outcome)
+                .ok_or_else(|| format!("no hand ends in a {} against {}", outcome, game.name(opponent)))?;
+            Ok(game.round_score(opponent, player))
+        })
+        .sum()
+}
+
+/// the total score of a strategy guide when it is read with `decoding`
+#[derive(Debug, Clone)]
+pub struct Reading {
+    pub decoding: Decoding,
+    pub score: i32,
+}
+
+/// every ordering of `items`, starting with `items` in order
+fn permutations<T: Copy>(items: &[T]) -> Vec<Vec<T>> {
+    if items.is_empty() {
+        return vec![vec![]];
     }
+    (0..items.len())
+        .flat_map(|i| {
+            let mut rest = items.to_vec();
+            let first = rest.remove(i);
+            permutations(&rest).into_iter().map(move |mut perm| {
+                perm.insert(0, first);
+                perm
+            })
+        })
+        .collect()
+}
+
+/// score the guide with its second column read as the hand to play, under every way of
+/// assigning the game's weapons to the column's letters in `decoding`, best first. Only the
+/// second column is reread: the opponent's column keeps its reading in `decoding`. A game of n
+/// weapons has n! readings
+pub fn rank_hand_readings(game: &Game, decoding: &Decoding, guide: &StrategyGuide) -> Vec<Reading> {
+    let hands: Vec<Hand> = game.hands().collect();
+    let mut readings: Vec<Reading> = permutations(&hands).into_iter()
+        .filter_map(|perm| {
+            let letters = decoding.player.iter().map(|&(c, _)| c);
+            let decoding = Decoding { player: letters.zip(perm).collect(), ..decoding.clone() };
+            Some(Reading { score: score_as_hands(game, &decoding, guide).ok()?, decoding })
+        })
+        .collect();
+    readings.sort_by_key(|reading| std::cmp::Reverse(reading.score));
+    readings
+}
 
-    total_score
+/// score the guide with its second column read as the outcome wanted, under every way of
+/// assigning loss, draw and win to the column's letters in `decoding`, best first. As with
+/// `rank_hand_readings` the opponent's column isn't reread. Readings that ask for an outcome a
+/// round can't end in are left out
+pub fn rank_outcome_readings(game: &Game, decoding: &Decoding, guide: &StrategyGuide) -> Vec<Reading> {
+    let mut readings: Vec<Reading> = permutations(&Outcome::ALL).into_iter()
+        .filter_map(|perm| {
+            let letters = decoding.outcome.iter().map(|&(c, _)| c);
+            let decoding = Decoding { outcome: letters.zip(perm).collect(), ..decoding.clone() };
+            Some(Reading { score: score_as_outcomes(game, &decoding, guide).ok()?, decoding })
+        })
+        .collect();
+    readings.sort_by_key(|reading| std::cmp::Reverse(reading.score));
+    readings
 }
 
+/// the most the player can score against the opponent's column, ignoring the second column and
+/// playing the best hand in every round. Fails like `score_as_hands`
+pub fn best_score(game: &Game, decoding: &Decoding, guide: &StrategyGuide) -> Result<i32, String> {
+    guide.iter()
+        .map(|round| {
+            let opponent = opponent(decoding, round)?;
+            Ok(game.hands().map(|player| game.round_score(opponent, player)).max().unwrap_or(0))
+        })
+        .sum()
+}
+
+/// every reading of the guide ranked by score, one per line, then the best score the player could
+/// have made
+pub fn report(game: &Game, decoding: &Decoding, guide: &StrategyGuide) -> String {
+    let mut out = String::new();
+    for (heading, readings) in [("hands", rank_hand_readings(game, decoding, guide)),
+                                ("outcomes", rank_outcome_readings(game, decoding, guide))] {
+        out += &format!("second column as {}:\n", heading);
+        for reading in readings {
+            out += &format!("{:>6}  {}\n", reading.score, reading.decoding.describe(game));
+        }
+    }
+    match best_score(game, decoding, guide) {
+        Ok(score) => out += &format!("best score: {}\n", score),
+        Err(why) => out += &format!("no best score: {}\n", why),
+    }
+    out
+}
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
-    use crate::d02_rock_paper_scissors::{determine_hand, Hand, Outcome, total_score_part1, total_score_part2};
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use crate::d02_rock_paper_scissors::{best_score, parse_guide, permutations, rank_hand_readings, rank_outcome_readings, report, score_as_hands, score_as_outcomes, Decoding, Game, Hand, Outcome};
+
+    const ROCK: Hand = Hand(0);
+    const PAPER: Hand = Hand(1);
+    const SCISSORS: Hand = Hand(2);
 
     #[test]
     fn determine_hand_draw() {
-        let op = Hand::Rock;
-        let out = Outcome::Draw;
-        assert_eq!(determine_hand(&op, &out), Hand::Rock);
+        assert_eq!(Game::rock_paper_scissors().hand_for(ROCK, Outcome::Draw), Some(ROCK));
     }
 
     #[test]
     fn determine_hand_win() {
-        let op = Hand::Rock;
-        let out = Outcome::Win;
-        assert_eq!(determine_hand(&op, &out), Hand::Paper);
+        assert_eq!(Game::rock_paper_scissors().hand_for(ROCK, Outcome::Win), Some(PAPER));
     }
 
     #[test]
     fn determine_hand_loss() {
-        let op = Hand::Rock;
-        let out = Outcome::Loss;
-        assert_eq!(determine_hand(&op, &out), Hand::Scissors);
+        assert_eq!(Game::rock_paper_scissors().hand_for(ROCK, Outcome::Loss), Some(SCISSORS));
+    }
+
+    #[test]
+    fn rock_paper_scissors_rules() {
+        let game = Game::rock_paper_scissors();
+        assert_eq!(game.outcome(SCISSORS, ROCK), Outcome::Win);
+        assert_eq!(game.outcome(ROCK, SCISSORS), Outcome::Loss);
+        assert_eq!(game.round_score(PAPER, SCISSORS), 9);
+        assert_eq!(game.name(PAPER), "paper");
+    }
+
+    #[test]
+    fn lizard_spock_rules() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let hand = |name| game.hand(name).unwrap();
+        let wins = [
+            ("scissors", "paper"), ("paper", "rock"), ("rock", "lizard"), ("lizard", "spock"), ("spock", "scissors"),
+            ("scissors", "lizard"), ("lizard", "paper"), ("paper", "spock"), ("spock", "rock"), ("rock", "scissors"),
+        ];
+        for (winner, loser) in wins {
+            assert_eq!(game.outcome(hand(loser), hand(winner)), Outcome::Win, "{} beats {}", winner, loser);
+            assert_eq!(game.outcome(hand(winner), hand(loser)), Outcome::Loss);
+        }
+        // spock and lizard both beat paper, and lizard scores more
+        assert_eq!(game.hand_for(hand("paper"), Outcome::Win), Some(hand("lizard")));
+    }
+
+    #[test]
+    fn games_are_checked() {
+        assert!(Game::cyclic(&["a", "b", "c", "d"]).is_err());
+        assert!(Game::new(&["a", "b"], &[("a", "b"), ("b", "a")]).is_err());
+        assert!(Game::new(&["a", "b"], &[("a", "c")]).is_err());
+        // nothing can lose to the weapon that beats everything
+        let game = Game::new(&["a", "b", "c"], &[("a", "b"), ("a", "c")]).unwrap();
+        assert_eq!(game.hand_for(Hand(0), Outcome::Win), None);
+        assert_eq!(game.hand_for(Hand(1), Outcome::Draw), Some(Hand(2)));
+    }
+
+    #[test]
+    fn invalid_column_is_a_parse_error() {
+        let decoding = Decoding::standard(&Game::rock_paper_scissors()).unwrap();
+        let err = parse_guide("A Y\nB Q\n", &decoding).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "Q");
+        assert_eq!(parse_guide("AB Y", &decoding).unwrap_err().text, "AB");
+        assert!(parse_guide("A", &decoding).is_err());
+    }
+
+    #[test]
+    fn decode_other_letters() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let decoding = Decoding::standard(&game).unwrap();
+        // the second column runs from V for rock to Z for lizard, but only X, Y and Z are outcomes too
+        let guide = parse_guide("E X\nA Z", &decoding).unwrap();
+        // scissors against lizard wins 6 + 3, then lizard against rock loses 0 + 5
+        assert_eq!(score_as_hands(&game, &decoding, &guide), Ok(14));
+        // spock and rock both beat scissors, and spock scores 4
+        let guide = parse_guide("C Z", &decoding).unwrap();
+        assert_eq!(score_as_outcomes(&game, &decoding, &guide), Ok(10));
+        let err = parse_guide("E V", &decoding).unwrap_err();
+        assert_eq!((err.text.as_str(), err.expected.as_str()), ("V", "one of X, Y, Z"));
+    }
+
+    #[test]
+    fn letters_without_a_meaning() {
+        // with two weapons, X is a loss but not a hand
+        let game = Game::new(&["heads", "tails"], &[("heads", "tails")]).unwrap();
+        let decoding = Decoding::standard(&game).unwrap();
+        assert_eq!(parse_guide("A X", &decoding).unwrap_err().text, "X");
+        let guide = parse_guide("A Z\nB Y", &decoding).unwrap();
+        // tails loses to heads for 0 + 2, then heads beats tails for 6 + 1
+        assert_eq!(score_as_hands(&game, &decoding, &guide), Ok(9));
+        // nothing beats heads, so no hand can win the first round
+        assert_eq!(score_as_outcomes(&game, &decoding, &guide), Err("no hand ends in a win against heads".to_string()));
+
+        // a guide scored with a decoding it wasn't parsed with
+        let other = Decoding { player: vec![], ..decoding.clone() };
+        assert_eq!(score_as_hands(&game, &other, &guide), Err("'Z' is not a hand to play".to_string()));
+
+        let weapons: Vec<String> = (0..27).map(|n| n.to_string()).collect();
+        let weapons: Vec<&str> = weapons.iter().map(String::as_str).collect();
+        assert!(Decoding::standard(&Game::new(&weapons, &[]).unwrap()).is_err());
+    }
+
+    #[test]
+    fn every_ordering() {
+        assert_eq!(permutations(&[1, 2, 3]), [[1, 2, 3], [1, 3, 2], [2, 1, 3], [2, 3, 1], [3, 1, 2], [3, 2, 1]]);
+        assert_eq!(permutations::<u8>(&[]).len(), 1);
+    }
+
+    #[test]
+    fn rank_the_readings_of_the_example() {
+        let input = read_input(YEAR, 2, InputKind::Example(1)).unwrap();
+        let game = Game::rock_paper_scissors();
+        let decoding = Decoding::standard(&game).unwrap();
+        let guide = parse_guide(&input, &decoding).unwrap();
+
+        let hands = rank_hand_readings(&game, &decoding, &guide);
+        assert_eq!(hands.len(), 6);
+        assert!(hands.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        // the puzzle's reading scores 15, and the best wins every round, reading Y as paper against rock
+        assert!(hands.iter().any(|reading| reading.score == 15 && reading.decoding.player == decoding.player));
+        assert_eq!(hands[0].score, 24);
+        assert_eq!(hands[0].decoding.describe(&game), "A=rock B=paper C=scissors, X=scissors Y=paper Z=rock, X=loss Y=draw Z=win");
+
+        let outcomes = rank_outcome_readings(&game, &decoding, &guide);
+        assert_eq!(outcomes.len(), 6);
+        assert_eq!(outcomes[0].score, 18);
+        assert_eq!(outcomes[0].decoding.outcome, [('X', Outcome::Win), ('Y', Outcome::Loss), ('Z', Outcome::Draw)]);
+        // the puzzle's reading of the outcomes scores the least
+        let last = outcomes.last().unwrap();
+        assert_eq!((last.score, &last.decoding.outcome), (12, &decoding.outcome));
+
+        assert_eq!(best_score(&game, &decoding, &guide), Ok(24));
+    }
+
+    #[test]
+    fn report_the_example() {
+        let input = read_input(YEAR, 2, InputKind::Example(1)).unwrap();
+        let game = Game::rock_paper_scissors();
+        let decoding = Decoding::standard(&game).unwrap();
+        let guide = parse_guide(&input, &decoding).unwrap();
+        assert_eq!(report(&game, &decoding, &guide), "\
+second column as hands:
+    24  A=rock B=paper C=scissors, X=scissors Y=paper Z=rock, X=loss Y=draw Z=win
+    15  A=rock B=paper C=scissors, X=rock Y=paper Z=scissors, X=loss Y=draw Z=win
+    15  A=rock B=paper C=scissors, X=paper Y=rock Z=scissors, X=loss Y=draw Z=win
+    15  A=rock B=paper C=scissors, X=paper Y=scissors Z=rock, X=loss Y=draw Z=win
+    15  A=rock B=paper C=scissors, X=scissors Y=rock Z=paper, X=loss Y=draw Z=win
+     6  A=rock B=paper C=scissors, X=rock Y=scissors Z=paper, X=loss Y=draw Z=win
+second column as outcomes:
+    18  A=rock B=paper C=scissors, X=rock Y=paper Z=scissors, X=win Y=loss Z=draw
+    15  A=rock B=paper C=scissors, X=rock Y=paper Z=scissors, X=loss Y=win Z=draw
+    15  A=rock B=paper C=scissors, X=rock Y=paper Z=scissors, X=draw Y=loss Z=win
+    15  A=rock B=paper C=scissors, X=rock Y=paper Z=scissors, X=draw Y=win Z=loss
+    15  A=rock B=paper C=scissors, X=rock Y=paper Z=scissors, X=win Y=draw Z=loss
+    12  A=rock B=paper C=scissors, X=rock Y=paper Z=scissors, X=loss Y=draw Z=win
+best score: 24
+");
     }
 
     #[test]
     fn test_part1() {
-        let path = Path::new("../input/d02-input.txt");
-        let total = total_score_part1(path);
-        println!("total score for strat guide is {total}");
-        assert_eq!(total, 12156);
+        let input = read_input(YEAR, 2, InputKind::Example(1)).unwrap();
+        let game = Game::rock_paper_scissors();
+        let decoding = Decoding::standard(&game).unwrap();
+        let total = score_as_hands(&game, &decoding, &parse_guide(&input, &decoding).unwrap());
+        assert_eq!(total, Ok(15));
     }
 
     #[test]
     fn test_part2() {
-        let path = Path::new("../input/d02-input.txt");
-        let total = total_score_part2(path);
-        println!("total score for part 2 is {total}");
-        assert_eq!(total, 10835);
+        let input = read_input(YEAR, 2, InputKind::Example(1)).unwrap();
+        let game = Game::rock_paper_scissors();
+        let decoding = Decoding::standard(&game).unwrap();
+        let total = score_as_outcomes(&game, &decoding, &parse_guide(&input, &decoding).unwrap());
+        assert_eq!(total, Ok(12));
     }
-}
\ No newline at end of file
+}
@@ -1,146 +1,200 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use std::ops::RangeInclusive;
-use std::path::Path;
+use aoc_common::interval::{IntervalIndex, IntervalSet};
+use aoc_common::parse_error::{parse_token, ParseError};
+use aoc_common::solution::Solution;
 
-/// parse a pair of integers, separated by a single hyphen into a RangeInclusive<i32>.
+/// parse a pair of integers, separated by a single hyphen into a RangeInclusive<i64>.
 /// ex.  3-8  becomes 3..=8
-fn parse_as_range(s: &str) -> RangeInclusive<i32> {
-    let mut splits = s.split("-");
-    let start = splits.next().expect("input is well-formed").parse::<i32>().unwrap();
-    let end = splits.next().expect("input is well-formed").parse::<i32>().unwrap();
-    RangeInclusive::new(start, end)
+fn parse_as_range(s: &str) -> Result<RangeInclusive<i64>, ParseError> {
+    let (start, end) = s.split_once('-')
+        .ok_or_else(|| ParseError::new(s, "a range of section ids like '2-8'"))?;
+    let start = parse_token::<i64>(start, "a section id").map_err(|e| e.locate(s, start))?;
+    let end_id = parse_token::<i64>(end, "a section id").map_err(|e| e.locate(s, end))?;
+    if end_id < start {
+        return Err(ParseError::new(end, "a section id no less than the first").locate(s, end));
+    }
+    Ok(RangeInclusive::new(start, end_id))
 }
 
 /// returns `true` if r2 is completely contained within r1
-fn contains(r1: &RangeInclusive<i32>, r2: &RangeInclusive<i32>) -> bool {
-    r1.contains(r2.start()) && r1.contains(r2.end())
+fn contains(r1: &IntervalSet, r2: &IntervalSet) -> bool {
+    r1.is_superset(r2)
 }
 
-/// returns `true` if r1 contains any portion of r2
-fn overlaps(r1: &RangeInclusive<i32>, r2: &RangeInclusive<i32>) -> bool {
-    r1.contains(r2.start()) || r1.contains(r2.end())
+/// returns `true` if r1 and r2 share any sections
+fn overlaps(r1: &IntervalSet, r2: &IntervalSet) -> bool {
+    !r1.intersection(r2).is_empty()
 }
 
-/// find fully contained pairs
-fn part1() {
-    let path = Path::new("../input/d04-input.txt");
-    let f = File::open(path).expect("input file can be found");
-    let reader = BufReader::new(f);
-
-    let fully_contained_pairs = reader.lines()
-        .map(|l| l.unwrap())
-        .map(|line| {
-            let mut splits = line.split(",");
-            let left = splits.next().unwrap();
-            let right = splits.next().unwrap();
-            let left_range = parse_as_range(left);
-            let right_range = parse_as_range(right);
-            contains(&left_range, &right_range) || contains(&right_range, &left_range)
-        })
-        .filter(|res| *res)
-        .count();
-    println!("total fully contained pairs = {}", fully_contained_pairs);
+/// the number of pairs whose two assignments, as sets of sections, match `query`
+fn count_pairs(pairs: &Assignments, query: impl Fn(&IntervalSet, &IntervalSet) -> bool) -> usize {
+    pairs.iter()
+        .filter(|(left, right)| query(&IntervalSet::from(left.clone()), &IntervalSet::from(right.clone())))
+        .count()
+}
+
+/// the sections assigned to `k` or more of the elves, across every pair
+pub fn sections_covered(pairs: &Assignments, k: usize) -> IntervalSet {
+    IntervalSet::covered_by(pairs.iter().flat_map(|(left, right)| [left.clone(), right.clone()]), k)
+}
+
+/// an index of every elf's assignment. Elves are numbered in the order they appear, so the
+/// elves of the pair on line `n` (from 0) are `2n` and `2n + 1`
+pub fn assignment_index(pairs: &Assignments) -> IntervalIndex {
+    IntervalIndex::new(pairs.iter().flat_map(|(left, right)| [left.clone(), right.clone()]))
 }
 
-/// find partially contained pairs
-fn part2() {
-    let path = Path::new("../input/d04-input.txt");
-    let f = File::open(path).expect("input file can be found");
-    let reader = BufReader::new(f);
-
-    let fully_contained_pairs = reader.lines()
-        .map(|l| l.unwrap())
-        .map(|line| {
-            let mut splits = line.split(",");
-            let left = splits.next().unwrap();
-            let right = splits.next().unwrap();
-            let left_range = parse_as_range(left);
-            let right_range = parse_as_range(right);
-            overlaps(&left_range, &right_range) || overlaps(&right_range, &left_range)
+/// Day 04 - Camp Cleanup
+pub struct CampCleanup;
+
+/// the pair of section assignments given on each line of input
+type Assignments = Vec<(RangeInclusive<i64>, RangeInclusive<i64>)>;
+
+impl Solution for CampCleanup {
+    type Input = Assignments;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_assignments(input)
+    }
+
+    /// find fully contained pairs
+    fn part1(pairs: &Assignments) -> usize {
+        count_pairs(pairs, |left, right| contains(left, right) || contains(right, left))
+    }
+
+    /// find partially contained pairs
+    fn part2(pairs: &Assignments) -> usize {
+        count_pairs(pairs, overlaps)
+    }
+}
+
+fn parse_assignments(input: &str) -> Result<Assignments, ParseError> {
+    input.lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            let (left, right) = line.split_once(',')
+                .ok_or_else(|| ParseError::new(line, "a pair of ranges like '2-4,6-8'").on_line(idx + 1))?;
+            let left_range = parse_as_range(left).map_err(|e| e.locate(line, left).on_line(idx + 1))?;
+            let right_range = parse_as_range(right).map_err(|e| e.locate(line, right).on_line(idx + 1))?;
+            Ok((left_range, right_range))
         })
-        .filter(|res| *res)
-        .count();
-    println!("total fully contained pairs = {}", fully_contained_pairs);
+        .collect()
 }
 
 
 #[cfg(test)]
 mod tests {
-    use crate::d04_camp_cleanup::{contains, overlaps, parse_as_range, part1, part2};
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use crate::d04_camp_cleanup::{assignment_index, CampCleanup, contains, overlaps, parse_as_range, parse_assignments, sections_covered};
+    use aoc_common::solution::Solution;
 
     #[test]
     fn do_part1() {
-        part1()
+        let input = read_input(YEAR, 4, InputKind::Example(1)).unwrap();
+        let pairs = parse_assignments(&input).unwrap();
+        assert_eq!(CampCleanup::part1(&pairs), 2);
     }
 
     #[test]
     fn do_part2() {
-        part2()
+        let input = read_input(YEAR, 4, InputKind::Example(1)).unwrap();
+        let pairs = parse_assignments(&input).unwrap();
+        assert_eq!(CampCleanup::part2(&pairs), 4);
     }
 
 
+    #[test]
+    fn sections_covered_by_the_example() {
+        let input = read_input(YEAR, 4, InputKind::Example(1)).unwrap();
+        let pairs = parse_assignments(&input).unwrap();
+        assert_eq!(sections_covered(&pairs, 1).ranges().collect::<Vec<_>>(), [2..=9]);
+        assert_eq!(sections_covered(&pairs, 6).ranges().collect::<Vec<_>>(), [4..=7]);
+        // section 6 is assigned to eight elves
+        assert_eq!(sections_covered(&pairs, 8).ranges().collect::<Vec<_>>(), [6..=6]);
+        assert!(sections_covered(&pairs, 9).is_empty());
+    }
+
+    #[test]
+    fn index_the_example_assignments() {
+        let input = read_input(YEAR, 4, InputKind::Example(1)).unwrap();
+        let pairs = parse_assignments(&input).unwrap();
+        let index = assignment_index(&pairs);
+        assert_eq!(index.stab(9), [5]);
+        assert!(index.stab(1).is_empty());
+        assert_eq!(index.overlapping(8..=20), [1, 5, 6, 11]);
+        // the pairs on the same line that overlap are among all the overlapping pairs
+        let all = index.overlapping_pairs();
+        let same_line = all.iter().filter(|&&(a, b)| a / 2 == b / 2).count();
+        assert_eq!(same_line, CampCleanup::part2(&pairs));
+        assert_eq!(all.len(), 49);
+    }
+
     #[test]
     fn test_parse_as_range() {
         let s = "2-8";
-        let r = parse_as_range(s);
+        let r = parse_as_range(s).unwrap();
         assert_eq!(r.start(), &2);
         assert_eq!(r.end(), &8);
+        let err = parse_as_range("8-2").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (3, "2"));
     }
 
     #[test]
     fn test_contains() {
         let r1 = 2..=10;
         let r2 = 3..=9;
-        assert!(contains(&r1, &r2));
+        assert!(contains(&r1.into(), &r2.into()));
     }
 
     #[test]
     fn test_contains_end_bound() {
         let r1 = 2..=10;
         let r2 = 3..=10;
-        assert!(contains(&r1, &r2));
+        assert!(contains(&r1.into(), &r2.into()));
     }
 
     #[test]
     fn test_contains_same_bound() {
         let r1 = 2..=10;
         let r2 = 2..=10;
-        assert!(contains(&r1, &r2));
+        assert!(contains(&r1.into(), &r2.into()));
     }
 
     #[test]
     fn test_not_contains() {
         let r1 = 2..=10;
         let r2 = 1..=10;
-        assert!(contains(&r1, &r2));
+        assert!(!contains(&r1.into(), &r2.into()));
     }
 
     #[test]
     fn test_not_contains_end_bound() {
         let r1 = 2..=10;
         let r2 = 3..=12;
-        assert!(contains(&r1, &r2));
+        assert!(!contains(&r1.into(), &r2.into()));
     }
 
     #[test]
     fn test_overlaps() {
         let r1 = 2..=5;
         let r2 = 3..=6;
-        assert!(overlaps(&r1, &r2));
+        assert!(overlaps(&r1.into(), &r2.into()));
     }
 
     #[test]
     fn test_overlaps2() {
         let r1 = 2..=5;
         let r2 = 3..=6;
-        assert!(overlaps(&r2, &r1));
+        assert!(overlaps(&r2.into(), &r1.into()));
     }
 
     #[test]
     fn test_no_overlaps() {
         let r1 = 2..=5;
         let r2 = 7..=10;
-        assert!(!overlaps(&r2, &r1));
+        assert!(!overlaps(&r2.into(), &r1.into()));
     }
-}
\ No newline at end of file
+}
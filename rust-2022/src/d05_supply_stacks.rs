@@ -1,15 +1,9 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-use regex::Regex;
-use lazy_static::lazy_static;
-
-lazy_static! {
-    static ref MOVE_RE: Regex = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
-}
+use aoc_common::parse_error::ParseError;
+use aoc_common::scan::{blocks, Block, Scanner};
+use aoc_common::solution::Solution;
 
 #[derive(Debug)]
-struct MoveCommand {
+pub struct MoveCommand {
     amount: usize,
     from: usize,
     to: usize,
@@ -25,7 +19,14 @@ impl MoveCommand {
     }
 }
 
-/// initialize the stacks to their initial values
+/// the stacks of crates and the moves to make with them
+#[derive(Debug)]
+pub struct Procedure {
+    stacks: Vec<Vec<char>>,
+    moves: Vec<MoveCommand>,
+}
+
+/// initialize the stacks to their initial values, for an input without a drawing of them
 /// vec[0] is used as temp stack
 fn init_stacks() -> Vec<Vec<char>> {
     vec![
@@ -43,14 +44,14 @@ fn init_stacks() -> Vec<Vec<char>> {
 }
 
 /// performs a single move command
-fn do_move(mc: &MoveCommand, stacks: &mut Vec<Vec<char>>) {
+fn do_move(mc: &MoveCommand, stacks: &mut [Vec<char>]) {
     for _ in 0..mc.amount {
         let c = stacks[mc.from].pop().expect("stack exists");
         stacks[mc.to].push(c);
     }
 }
 
-fn do_ordered_move(mc: &MoveCommand, stacks: &mut Vec<Vec<char>>) {
+fn do_ordered_move(mc: &MoveCommand, stacks: &mut [Vec<char>]) {
     for _ in 0..mc.amount {
         let c = stacks[mc.from].pop().expect("stack exists");
         stacks[0].push(c);
@@ -61,77 +62,169 @@ fn do_ordered_move(mc: &MoveCommand, stacks: &mut Vec<Vec<char>>) {
     }
 }
 
-fn part1() {
-    let path = Path::new("../input-2022/d05-input.txt");
-    let f = File::open(path).unwrap();
-    let reader = BufReader::new(f);
-    let mut stacks = init_stacks();
-
-    for line in reader.lines() {
-        let command_str = line.unwrap();
-        let caps = MOVE_RE.captures(&command_str).unwrap();
-        let move_command = MoveCommand::from(
-            caps[1].parse::<usize>().unwrap(),
-            caps[2].parse::<usize>().unwrap(),
-            caps[3].parse::<usize>().unwrap(),
-        );
-        do_move(&move_command, &mut stacks);
+/// Day 05 - Supply Stacks
+pub struct SupplyStacks;
+
+impl Solution for SupplyStacks {
+    type Input = Procedure;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_procedure(input)
     }
-    println!("crates on top of each stack are:");
-    for i in 1..stacks.len() {
-        print!("{} ", stacks[i].last().unwrap())
+
+    /// the crates on top of each stack after moving crates one at a time
+    fn part1(procedure: &Procedure) -> String {
+        let mut stacks = procedure.stacks.clone();
+        for move_command in &procedure.moves {
+            do_move(move_command, &mut stacks);
+        }
+        top_crates(&stacks)
+    }
+
+    /// the crates on top of each stack after moving crates in order, multiple at a time
+    fn part2(procedure: &Procedure) -> String {
+        let mut stacks = procedure.stacks.clone();
+        for move_command in &procedure.moves {
+            do_ordered_move(move_command, &mut stacks);
+        }
+        top_crates(&stacks)
     }
 }
 
-fn part2() {
-    let path = Path::new("../input-2022/d05-input.txt");
-    let f = File::open(path).unwrap();
-    let reader = BufReader::new(f);
-    let mut stacks = init_stacks();
-
-    for line in reader.lines() {
-        let command_str = line.unwrap();
-        let caps = MOVE_RE.captures(&command_str).unwrap();
-        let move_command = MoveCommand::from(
-            caps[1].parse::<usize>().unwrap(),
-            caps[2].parse::<usize>().unwrap(),
-            caps[3].parse::<usize>().unwrap(),
-        );
-        do_ordered_move(&move_command, &mut stacks);
+/// Parse the drawing of the stacks, a blank line and the moves. The drawing can be left out, for
+/// the stacks given by `init_stacks`
+fn parse_procedure(input: &str) -> Result<Procedure, ParseError> {
+    let (stacks, moves) = match blocks(input)[..] {
+        [drawing, moves] => (parse_drawing(drawing)?, Some(moves)),
+        [moves] => (init_stacks(), Some(moves)),
+        [] => (init_stacks(), None),
+        [_, _, extra, ..] => return Err(ParseError::new(extra.text, "the end of the moves").on_line(extra.first_line)),
+    };
+    let moves = moves.iter()
+        .flat_map(Block::lines)
+        .map(|mut line| parse_move(&mut line, stacks.len()))
+        .collect::<Result<_, _>>()?;
+    Ok(Procedure { stacks, moves })
+}
+
+/// Parse a drawing of the stacks, with the crates in columns above a line of stack numbers:
+///
+/// ```text
+///     [D]
+/// [N] [C]
+/// [Z] [M] [P]
+///  1   2   3
+/// ```
+fn parse_drawing(drawing: Block) -> Result<Vec<Vec<char>>, ParseError> {
+    let lines: Vec<Scanner> = drawing.lines().collect();
+    let (mut numbers, crates) = lines.split_last()
+        .map(|(numbers, crates)| (numbers.clone(), crates))
+        .ok_or_else(|| drawing.ended_early("a drawing of the stacks"))?;
+    let mut stack_count = 0;
+    loop {
+        numbers.skip_spaces();
+        if numbers.is_empty() {
+            break;
+        }
+        let number = numbers.clone();
+        if numbers.unsigned::<usize>("a stack number")? != stack_count + 1 {
+            return Err(number.error_here(format!("stack number {}", stack_count + 1)));
+        }
+        stack_count += 1;
     }
-    println!("part 2 crates on top of each stack are:");
-    for i in 1..stacks.len() {
-        print!("{}", stacks[i].last().unwrap())
+
+    let mut stacks = vec![vec![]; stack_count + 1];
+    for line in crates.iter().rev() {
+        let text = line.rest();
+        for (column, c) in text.char_indices().filter(|&(column, _)| column % 4 == 1) {
+            let stack = column / 4 + 1;
+            match c {
+                ' ' => {}
+                'A'..='Z' if stack <= stack_count => stacks[stack].push(c),
+                _ => {
+                    let token = &text[column..column + c.len_utf8()];
+                    return Err(line.error(token, format!("a crate letter in one of {} stacks", stack_count)));
+                }
+            }
+        }
     }
+    Ok(stacks)
+}
+
+/// parse a move like `move 1 from 2 to 3`, between stacks 1 to `stack_count - 1`
+fn parse_move(line: &mut Scanner, stack_count: usize) -> Result<MoveCommand, ParseError> {
+    let stack_number = |line: &mut Scanner| {
+        let before = line.clone();
+        match line.unsigned::<usize>("a stack number")? {
+            n if (1..stack_count).contains(&n) => Ok(n),
+            _ => Err(before.error_here(format!("a stack number from 1 to {}", stack_count - 1))),
+        }
+    };
+    line.literal("move ")?;
+    let amount = line.unsigned("a crate amount")?;
+    line.literal(" from ")?;
+    let from = stack_number(line)?;
+    line.literal(" to ")?;
+    let to = stack_number(line)?;
+    line.end()?;
+    Ok(MoveCommand::from(amount, from, to))
+}
+
+/// returns the crate on top of each stack, skipping the temp stack
+fn top_crates(stacks: &[Vec<char>]) -> String {
+    stacks[1..].iter()
+        .filter_map(|stack| stack.last())
+        .collect()
 }
 
 
 #[cfg(test)]
 mod tests {
-    use regex::Regex;
-    use crate::d05_supply_stacks::{do_move, init_stacks, MoveCommand, part1, part2};
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use aoc_common::scan::{blocks, Scanner};
+    use crate::d05_supply_stacks::{do_move, init_stacks, MoveCommand, parse_drawing, parse_move, parse_procedure, SupplyStacks};
+    use aoc_common::solution::Solution;
 
     #[test]
     fn do_part1() {
-        part1();
+        let input = read_input(YEAR, 5, InputKind::Example(1)).unwrap();
+        let procedure = parse_procedure(&input).unwrap();
+        assert_eq!(SupplyStacks::part1(&procedure), "CMZ");
     }
 
     #[test]
     fn do_part2() {
-        part2();
+        let input = read_input(YEAR, 5, InputKind::Example(1)).unwrap();
+        let procedure = parse_procedure(&input).unwrap();
+        assert_eq!(SupplyStacks::part2(&procedure), "MCD");
+    }
+
+    #[test]
+    fn test_parse_drawing() {
+        let stacks = parse_drawing(blocks("    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3")[0]).unwrap();
+        assert_eq!(stacks, vec![vec![], vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]);
+
+        let err = parse_drawing(blocks("[N] [c]\n 1   2")[0]).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+        let err = parse_drawing(blocks("[N]\n 1   3")[0]).unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (2, "3"));
+        let err = parse_procedure("[N]\n 1\n\nmove 1 from 1 to 2\n").unwrap_err();
+        assert_eq!(err.line, 4);
     }
 
     #[test]
-    fn test_move_re_captures() {
-        let re = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
-        let input = "move 2 from 3 to 4";
-        let caps = re.captures(input).unwrap();
-        assert!(re.is_match(input));
-        assert_eq!(caps.len(), 4);
-
-        assert_eq!(&caps[1], "2");
-        assert_eq!(&caps[2], "3");
-        assert_eq!(&caps[3], "4");
+    fn test_parse_move() {
+        let mc = parse_move(&mut Scanner::new("move 2 from 3 to 4"), 10).unwrap();
+        assert_eq!((mc.amount, mc.from, mc.to), (2, 3, 4));
+
+        let err = parse_move(&mut Scanner::new("move 2 from 10 to 4"), 10).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (13, "10"));
+        assert_eq!(err.expected, "a stack number from 1 to 9");
+        let err = parse_move(&mut Scanner::new("move 2 form 3 to 4"), 10).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (7, " form"));
     }
 
     #[test]
@@ -142,4 +235,4 @@ mod tests {
         assert_eq!(stack[1], vec!['D', 'B', 'J', 'V', 'B']);
         assert_eq!(stack[4], vec!['W', 'J', 'P', 'M', 'L', 'N', 'D']);
     }
-}
\ No newline at end of file
+}
@@ -2,24 +2,34 @@
 
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash};
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-use regex::Regex;
-use regex::RegexSet;
-use lazy_static::lazy_static;
-
-
-lazy_static! {
-    static ref CD_RE: Regex = Regex::new(r"^\$ cd (.+)$").unwrap();
-    static ref LS_RE: Regex = Regex::new(r"^\$ ls\s*$").unwrap();
-    static ref DIR_RE: Regex = Regex::new(r"^dir (.+)$").unwrap();
-    static ref FILE_RE: Regex = Regex::new(r"^(\d+) (.+)$").unwrap();
-    static ref RE_SET: RegexSet = RegexSet::new(&[
-        r"^\$ cd (.+)$",
-        r"^\$ ls\s*$",
-        r"^dir (.+)$",
-        r"^(\d+) (.+)$"
-    ]).unwrap();
+use aoc_common::parse_error::ParseError;
+use aoc_common::scan::{self, Scanner};
+use aoc_common::solution::Solution;
+
+/// a line of the terminal output
+#[derive(Debug, PartialEq, Eq)]
+enum Line<'a> {
+    Cd(&'a str),
+    Ls,
+    Dir(&'a str),
+    File(usize, &'a str),
+}
+
+fn parse_line<'a>(line: &mut Scanner<'a>) -> Result<Line<'a>, ParseError> {
+    if line.eat("$ cd ") {
+        Ok(Line::Cd(line.remainder("a directory name")?))
+    } else if line.eat("$ ls") {
+        line.skip_spaces();
+        Ok(Line::Ls)
+    } else if line.eat("dir ") {
+        Ok(Line::Dir(line.remainder("a directory name")?))
+    } else if line.rest().starts_with(|c: char| c.is_ascii_digit()) {
+        let size = line.unsigned("a file size")?;
+        line.literal(" ")?;
+        Ok(Line::File(size, line.remainder("a file name")?))
+    } else {
+        Err(line.error(line.rest(), "a cd or ls command, a directory or a file listing"))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -38,7 +48,7 @@ impl FileInfo {
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
-struct FileNode {
+pub struct FileNode {
     parent: String,
     size: usize,
     files: HashSet<FileInfo>,
@@ -56,185 +66,154 @@ impl FileNode {
 type FileMap = HashMap<String, FileNode>;
 
 /// parse the input data into a HashMap
-fn parse_to_map(path: &Path) -> FileMap {
-    let f = std::fs::File::open(path).unwrap();
-    let reader = BufReader::new(f);
-
+fn parse_to_map(input: &str) -> Result<FileMap, ParseError> {
     // file map maps the absolute path of a file to a FileNode
     let mut file_map: HashMap<String, FileNode> = HashMap::new();
     // cur_path is the current directory path
     let mut cur_path: Vec<String> = vec![];
 
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let matches: Vec<_> = RE_SET.matches(&line).into_iter().collect();
-        match &matches[..] {
-            [0] => {
-                let dir_name = CD_RE.captures(&line).unwrap()[1].to_string();
-                match dir_name.as_str() {
-                    ".." => {
-                        cur_path.pop();
-                    }
-                    "/" => {
-                        cur_path.clear();
-                        cur_path.push("".to_string());
-                    },
-                    _ => {
-                        cur_path.push(dir_name);
-                    }
-                }
-            },
-            [1] => {},
-            [2] => {
-                let dir_name = DIR_RE.captures(&line).unwrap()[1].to_string();
+    for line in scan::lines(input, parse_line)? {
+        match line {
+            Line::Cd("..") => {
+                cur_path.pop();
+            }
+            Line::Cd("/") => {
+                cur_path.clear();
+                cur_path.push("".to_string());
+            }
+            Line::Cd(dir_name) => {
+                cur_path.push(dir_name.to_string());
+            }
+            Line::Ls => {}
+            Line::Dir(dir_name) => {
                 let mut parent_dir = cur_path.join("/");
                 if parent_dir.is_empty() { parent_dir = String::from("/"); }
                 // get file node if it exists, and update its vec of directories
                 let node = file_map.entry(parent_dir.clone()).or_insert(FileNode::new(parent_dir.clone(), 0, HashSet::new(), HashSet::new()));
-                node.dirs.insert(FileInfo::new(dir_name, 0));
-            },
-            [3] => {
-                let file_size = FILE_RE.captures(&line).unwrap()[1].to_string().parse::<usize>().unwrap();
-                let file_name = FILE_RE.captures(&line).unwrap()[2].to_string();
+                node.dirs.insert(FileInfo::new(dir_name.to_string(), 0));
+            }
+            Line::File(file_size, file_name) => {
                 let mut parent_dir = cur_path.join("/");
                 if parent_dir.is_empty() { parent_dir = String::from("/"); }
                 // get file node if it exists, and update its vec of files
                 let node = file_map.entry(parent_dir.clone()).or_insert(FileNode::new(parent_dir.clone(), 0, HashSet::new(), HashSet::new()));
-                node.files.insert(FileInfo::new(file_name, file_size));
-            },
-            _ => {
-                panic!("UNKNOWN MATCH {}", &line)
+                node.files.insert(FileInfo::new(file_name.to_string(), file_size));
             }
         }
-
     }
-    file_map
+    if !file_map.contains_key("/") {
+        return Err(ParseError::new("", "a listing of the root directory"));
+    }
+    Ok(file_map)
 }
 
+/// compute the total size of every directory, including the sizes of all its sub-directories
 fn compute_sizes(fmap: &mut FileMap) {
-    // find path of leaf directories (directories that contain only files)
-    let mut leaf_nodes = fmap.iter()
-        .filter(|&(_path, node)| node.dirs.is_empty())
-        .map(|(path, _node)| path)
-        .cloned()
-        .collect::<Vec<String>>();
-
-    // sort leaf nodes by their depth
-    leaf_nodes.sort_by(|n1, n2| {
-        let n1_level = n1.split("/").filter(|s| !s.is_empty()).collect::<Vec<_>>().len();
-        let n2_level = n2.split("/").filter(|s| !s.is_empty()).collect::<Vec<_>>().len();
-        n1_level.cmp(&n2_level)
-    });
-
-    // keeps track of paths that have been visited
-    let mut visited: HashSet<String> = HashSet::new();
-
-    // iterate through the leaf_nodes and their parent dirs, computing the file and dir sizes as we go
-    while let Some(leaf_node) = leaf_nodes.pop() {
-        let mut paths = leaf_node.split("/").collect::<Vec<&str>>();
-
-        while !paths.is_empty() {
-            let mut cur_path = paths.join("/");
-            if cur_path.is_empty() { cur_path = "/".to_string(); }
-
-            if !visited.contains(&cur_path) {
-                // compute size of files at the current path
-                let file_size = fmap.get(&cur_path)
-                    .expect("fileNode exists at path")
-                    .files.iter()
-                    .map(|f| f.size)
-                    .sum::<usize>();
-                fmap.get_mut(&cur_path).unwrap().size += file_size;
-
-                // get paths to child directories
-                let child_paths = fmap.get(&cur_path)
-                    .unwrap()
-                    .dirs
-                    .iter()
-                    .map(|fd| {
-                        if cur_path == "/" {
-                            format!("{}{}", &cur_path, fd.name)
-                        } else {
-                            format!("{}/{}", &cur_path, fd.name)
-                        }
-                    })
-                    .collect::<Vec<String>>();
-
-                // update sizes of dirs in the current path
-                let total_child_size = child_paths
-                    .into_iter()
-                    .map(|s| fmap.get(&s).unwrap().size)
-                    .sum::<usize>();
-
-                fmap.get_mut(&cur_path).unwrap().size += total_child_size;
-                visited.insert(cur_path.clone());
-            }
-
-            paths.pop();
-        }
+    // visit the deepest directories first, so that the sizes of child directories
+    // are known before their parent's size is computed
+    let mut dir_paths = fmap.keys().cloned().collect::<Vec<String>>();
+    dir_paths.sort_by_key(|path| std::cmp::Reverse(path.split('/').filter(|s| !s.is_empty()).count()));
+
+    for cur_path in dir_paths {
+        let node = fmap.get(&cur_path).expect("fileNode exists at path");
+        let file_size = node.files.iter().map(|f| f.size).sum::<usize>();
+
+        // directories that were never listed contain nothing
+        let total_child_size = node.dirs
+            .iter()
+            .map(|fd| {
+                if cur_path == "/" {
+                    format!("{}{}", &cur_path, fd.name)
+                } else {
+                    format!("{}/{}", &cur_path, fd.name)
+                }
+            })
+            .filter_map(|child_path| fmap.get(&child_path))
+            .map(|child| child.size)
+            .sum::<usize>();
 
+        fmap.get_mut(&cur_path).unwrap().size = file_size + total_child_size;
     }
 }
 
+/// Day 07 - No Space Left On Device
+pub struct NoSpaceLeft;
 
+impl Solution for NoSpaceLeft {
+    type Input = FileMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-fn part1() {
-    let path = Path::new("../input/d07-input.txt");
-    let mut map = parse_to_map(path);
-    compute_sizes(&mut map);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut map = parse_to_map(input)?;
+        compute_sizes(&mut map);
+        Ok(map)
+    }
 
-    // find all dirs with a total size of at most 100_000
-    let mut total = 0_usize;
-    for (k, v) in map.iter().filter(|&(_p, n)| n.size <= 100_000) {
-        println!("{}  {:?}", k, v);
-        total += v.size;
+    /// find the total sum of all dirs with a total size of at most 100_000
+    fn part1(map: &FileMap) -> usize {
+        map.values()
+            .filter(|n| n.size <= 100_000)
+            .map(|n| n.size)
+            .sum()
     }
-    // find the total sum of those dirs
-    println!("part 1 total sum of dirs {}", total);
-}
 
-/// find the directory closest to 30_000_000
-/// ans = 24_933_642
-fn part2() {
-    let path = Path::new("../input/d07-input.txt");
-    let mut map = parse_to_map(path);
-    compute_sizes(&mut map);
-
-    // for (k, v) in map.iter() {
-    //     println!("{} : {:?}", k, v);
-    // }
-
-    // tot_used =  3_693_945  real used= 48_381_165
-    // tot_un   = 66_306_055
-    // target   = 36_306_055
-    let used_space = map.get("/").unwrap().size;
-    println!("total used at root {}", &used_space);
-    let unused_space = 70_000_000 - used_space;
-    println!("total unused {}", &unused_space);
-    let target = unused_space - 30_000_000;
-    println!("total need space to reach 30_000_000 {}", &target);
-
-    // sort by FileNode.size and take the first FileNode
-    let mut nodes: Vec<&FileNode> = map.values().filter(|n| n.size >= target).collect::<Vec<&FileNode>>();
-    nodes.sort_by_key(|n| n.size);
-    for node in nodes.iter() {
-        println!("{}", &node.size);
+    /// find the size of the smallest directory that, if deleted, would free up enough space
+    /// to leave 30_000_000 unused
+    fn part2(map: &FileMap) -> usize {
+        let used_space = map.get("/").unwrap().size;
+        let unused_space = 70_000_000 - used_space;
+        let target = 30_000_000_usize.saturating_sub(unused_space);
+
+        map.values()
+            .map(|n| n.size)
+            .filter(|&size| size >= target)
+            .min()
+            .expect("root directory is always large enough")
     }
 }
 
 
 #[cfg(test)]
 mod tests {
-    use crate::d07_no_space_left::{part1, part2};
+    use aoc_common::input::{read_input, InputKind};
+    use crate::YEAR;
+    use aoc_common::scan::Scanner;
+    use crate::d07_no_space_left::{parse_line, Line, NoSpaceLeft};
+    use aoc_common::solution::Solution;
 
     #[test]
     fn test_part1() {
-        part1();
+        let input = read_input(YEAR, 7, InputKind::Example(2)).unwrap();
+        let map = NoSpaceLeft::parse(&input).unwrap();
+        assert_eq!(NoSpaceLeft::part1(&map), 95437);
     }
 
     #[test]
     fn test_part2() {
-        part2();
+        let input = read_input(YEAR, 7, InputKind::Example(2)).unwrap();
+        let map = NoSpaceLeft::parse(&input).unwrap();
+        assert_eq!(NoSpaceLeft::part2(&map), 24933642);
+    }
+
+    #[test]
+    fn test_example() {
+        // the test input only has / and /twjcmp, and the disk has plenty of free space
+        let input = read_input(YEAR, 7, InputKind::Example(1)).unwrap();
+        let map = NoSpaceLeft::parse(&input).unwrap();
+        assert_eq!(NoSpaceLeft::part1(&map), 86053);
+        assert_eq!(NoSpaceLeft::part2(&map), 86053);
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let line = |text| parse_line(&mut Scanner::new(text));
+        assert_eq!(line("$ cd /"), Ok(Line::Cd("/")));
+        assert_eq!(line("$ ls"), Ok(Line::Ls));
+        assert_eq!(line("dir a"), Ok(Line::Dir("a")));
+        assert_eq!(line("14848514 b.txt"), Ok(Line::File(14848514, "b.txt")));
+        assert_eq!(line("$ rm b.txt").unwrap_err().text, "$ rm b.txt");
+        assert_eq!(line("12x b.txt").unwrap_err().column, 3);
     }
 
     #[test]
@@ -247,8 +226,8 @@ mod tests {
     #[test]
     fn test_split() {
         let s1 = "/twjcmp/fpp";
-        let sp1 = s1.split("/").collect::<Vec<&str>>();
+        let sp1 = s1.split('/').collect::<Vec<&str>>();
         dbg!(&sp1);
         dbg!(sp1.join("/"));
     }
-}
\ No newline at end of file
+}
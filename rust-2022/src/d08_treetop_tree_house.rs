@@ -1,173 +1,215 @@
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use aoc_common::grid::{Grid, DIRECTIONS4, Pos};
+use aoc_common::parse_error::ParseError;
+use aoc_common::solution::{Part, Solution};
+use aoc_common::visual::{Canvas, Frames, Rgb};
 
-/// Day 08 Tree Top TreeHouse
-///
+// Day 08 Tree Top TreeHouse
 
-type Matrix = Vec<Vec<u8>>;
+type Matrix = Grid<u8>;
 
 
-/// read_input file into a Matrix of u8 digits
-fn read_input(path: &Path) -> Matrix {
-    let f = std::fs::File::open(path).unwrap();
-    let reader = BufReader::new(f);
+/// read_input into a Matrix of u8 digits
+fn read_input(input: &str) -> Result<Matrix, ParseError> {
+    let matrix = Grid::parse(input, |c| {
+        c.to_digit(10)
+            .map(|d| d as u8)
+            .ok_or_else(|| ParseError::new(&c.to_string(), "a tree height from 0 to 9"))
+    })?;
 
-    let matrix : Matrix = reader.lines()
-        .map(|l| l.unwrap())
-        .map(|s|
-            s.chars()
-                .map(|c| c.to_digit(10).unwrap() as u8).collect::<Vec<u8>>())
-        .collect();
-
-    matrix
+    if matrix.height() < 2 || matrix.width() < 2 {
+        return Err(ParseError::new(input, "a grid of at least two rows and columns of trees"));
+    }
+    Ok(matrix)
 }
 
-/// returns true if the element (tree height) at the given row, col in the Matrix is visible from the edge
-fn is_visible(matrix: &Matrix, row: usize, col: usize) -> bool {
-
-    let tree_height = matrix[row][col];
+/// returns true if the element (tree height) at the given position in the Matrix is visible from the edge
+fn is_visible(matrix: &Matrix, pos: Pos) -> bool {
+    let tree_height = matrix[pos];
 
-    // check from the North of the given position
-    let from_north = (0..row).all(|r| matrix[r][col] < tree_height);
-    let from_south = (row+1..matrix.len()).all(|r| matrix[r][col] < tree_height);
-    let from_west = (0..col).all(|c| matrix[row][c] < tree_height);
-    let from_east = (col+1..matrix[0].len()).all(|c| matrix[row][c] < tree_height);
+    // visible if every tree between it and one of the edges is shorter
+    DIRECTIONS4
+        .into_iter()
+        .any(|step| matrix.ray(pos, step).all(|p| matrix[p] < tree_height))
+}
 
-    from_north || from_south || from_west || from_east
+/// the number of trees that can be seen from `pos` looking in the direction `step`, the view
+/// stops at the edge or at the first tree at least as tall as the one at `pos`
+fn viewing_distance(matrix: &Matrix, pos: Pos, step: (isize, isize)) -> usize {
+    let tt = matrix[pos];
+    let mut count = 0;
+    for p in matrix.ray(pos, step) {
+        count += 1;
+        if matrix[p] >= tt {
+            break;
+        }
+    }
+    count
 }
 
 /// compute the scenic score for a tree height at row,col in the matrix
-fn scenic_score(matrix: &Matrix, row: usize, col: usize) -> usize {
-    // target tree height being examined
-    let tt = matrix[row][col];
+fn scenic_score(matrix: &Matrix, pos: Pos) -> usize {
+    DIRECTIONS4
+        .into_iter()
+        .map(|step| viewing_distance(matrix, pos, step))
+        .product()
+}
 
-    // computes the count of trees <= tt, stopping once the first tree == tt is found
-    let tree_count = |heights: &[u8]|
-        heights.iter()
-            .position(|h| *h == tt)
-            .map_or(heights.len(), |pos| pos + 1);
+/// draw the forest with each tree's height as its glyph. For part one the trees visible from
+/// outside the grid are green, for part two the trees are shaded by their scenic score, with the
+/// best tree in red
+fn draw_forest(matrix: &Matrix, part: Part) -> Canvas {
+    let scores = matrix.positions().map(|pos| scenic_score(matrix, pos)).collect::<Vec<_>>();
+    let best = scores.iter().copied().max().unwrap_or(0).max(1);
+    let mut canvas = Canvas::new(matrix.width(), matrix.height());
+    for (pos, score) in matrix.positions().zip(scores) {
+        let colour = match part {
+            Part::One if is_visible(matrix, pos) => Rgb::GREEN,
+            Part::One => Rgb::GREY,
+            Part::Two if score == best => Rgb::RED,
+            Part::Two => Rgb::GREY.blend(Rgb::YELLOW, score as f64 / best as f64),
+        };
+        let glyph = char::from_digit(matrix[pos] as u32, 10).unwrap_or('?');
+        canvas.draw(pos.1, pos.0, glyph, colour);
+    }
+    canvas
+}
 
+/// Day 08 - Treetop Tree House
+pub struct TreetopTreeHouse;
 
-    let north_heights: Vec<u8> = matrix[..row].iter().rev().map(|v| v[col]).take_while(|h| *h <= tt).collect();
-    let south_heights: Vec<u8> = matrix[row+1..].iter().map(|v| v[col]).take_while(|h| *h <= tt).collect();
-    let west_heights: Vec<u8> = matrix[row][..col].iter().rev().take_while(|h| **h <= tt).cloned().collect();
-    let east_heights: Vec<u8> = matrix[row][col+1..].iter().take_while(|h| **h <= tt).cloned().collect();
+impl Solution for TreetopTreeHouse {
+    type Input = Matrix;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    tree_count(&north_heights) * tree_count(&south_heights) * tree_count(&west_heights) * tree_count(&east_heights)
-}
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
 
-/// how many trees are visible from outside the grid?
-fn part1(input_path: &Path) {
-    let matrix = read_input(input_path);
-    let mut count: usize = 0;
-    // dont iterate the outside edge of the matrix, start from the first inner row/col
-    for r in 1..(matrix.len()-1) {
-        for c in 1..(matrix[0].len() - 1) {
-            if is_visible(&matrix, r, c) {
-                count += 1;
-                //println!("{:02},{:02} : {} ", r, c, &matrix[r][c]);
+    /// how many trees are visible from outside the grid?
+    fn part1(matrix: &Matrix) -> usize {
+        let mut count: usize = 0;
+        // dont iterate the outside edge of the matrix, start from the first inner row/col
+        for r in 1..(matrix.height() - 1) {
+            for c in 1..(matrix.width() - 1) {
+                if is_visible(matrix, (r, c)) {
+                    count += 1;
+                }
             }
         }
+        // all trees on the perimeter are visible
+        let perim_length = (matrix.width() * 2) + ((matrix.height() - 2) * 2);
+        count + perim_length
     }
-    // all trees on the perimeter are visible
-    let perim_length = (matrix[0].len() * 2) + ((matrix.len() - 2) * 2);
-    count += perim_length;
 
-    println!("There are {count} trees visible from outside the grid");
-}
-
-fn part2(input_path: &Path) {
-    let matrix = read_input(input_path);
-
-    let mut highest_score = usize::MIN;
-
-    // dont iterate the outside edge of the matrix, start from the first inner row/col
-    for r in 1..(matrix.len()-1) {
-        for c in 1..(matrix[0].len() - 1) {
-            let score = scenic_score(&matrix, r, c);
-            println!("score {r},{c}  = {score}");
-            if score > highest_score {
-                highest_score = score;
+    /// what is the highest scenic score possible for any tree?
+    fn part2(matrix: &Matrix) -> usize {
+        let mut highest_score = usize::MIN;
+
+        // dont iterate the outside edge of the matrix, start from the first inner row/col
+        for r in 1..(matrix.height() - 1) {
+            for c in 1..(matrix.width() - 1) {
+                let score = scenic_score(matrix, (r, c));
+                if score > highest_score {
+                    highest_score = score;
+                }
             }
         }
+        highest_score
     }
 
-    println!("highest scenic score is {}", highest_score);
+    /// the forest, showing the visible trees or the scenic scores
+    fn visualise(matrix: &Matrix, part: Part, frames: &mut dyn Frames) -> bool {
+        frames.frame(&draw_forest(matrix, part));
+        true
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
-    use crate::d08_treetop_tree_house::{is_visible, Matrix, part1, part2, read_input};
+    use aoc_common::input::{self, InputKind};
+    use crate::YEAR;
+    use crate::d08_treetop_tree_house::{draw_forest, is_visible, read_input, scenic_score, viewing_distance, Matrix, TreetopTreeHouse};
+    use aoc_common::grid::Grid;
+    use aoc_common::solution::{Part, Solution};
+    use aoc_common::visual::Rgb;
 
     #[test]
     fn do_part1() {
-        part1(Path::new("../input-2022/d08-input.txt"));
+        let matrix = read_input(&input::read_input(YEAR, 8, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(TreetopTreeHouse::part1(&matrix), 21);
     }
 
     #[test]
     fn do_part2() {
-        part2(Path::new("../input-2022/d08-input.txt"));
+        let matrix = read_input(&input::read_input(YEAR, 8, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(TreetopTreeHouse::part2(&matrix), 8);
     }
 
     #[test]
     fn test_parse() {
-        let mats = read_input(Path::new("../input-2022/d08-input.txt"));
-        assert_eq!(mats[0].len(), 99);
-        assert_eq!(mats.len(), 99);
-
+        let mats = read_input(&input::read_input(YEAR, 8, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(mats.width(), 5);
+        assert_eq!(mats.height(), 5);
     }
 
     #[test]
     fn test_is_visibile() {
-        let matrix: Matrix = vec![
+        let matrix: Matrix = Grid::from_rows(vec![
             vec![4, 3, 2],
             vec![0, 4, 3],
             vec![0, 0, 4],
-        ];
-        assert!(is_visible(&matrix, 1, 1));
+        ]);
+        assert!(is_visible(&matrix, (1, 1)));
     }
 
     #[test]
     fn test_is_not_visibile() {
-        let matrix: Matrix = vec![
+        let matrix: Matrix = Grid::from_rows(vec![
             vec![4, 3, 2],
             vec![5, 2, 3],
             vec![0, 4, 4],
-        ];
-        assert!(!is_visible(&matrix, 1, 1));
+        ]);
+        assert!(!is_visible(&matrix, (1, 1)));
     }
 
     #[test]
     fn test_perim_length() {
-        let matrix: Matrix = vec![
+        let matrix: Matrix = Grid::from_rows(vec![
             vec![4, 3, 2],
             vec![5, 2, 3],
             vec![0, 4, 4],
-        ];
-        let len = (matrix[0].len() * 2) + ((matrix.len() - 2) * 2);
+        ]);
+        let len = (matrix.width() * 2) + ((matrix.height() - 2) * 2);
         assert_eq!(len, 8);
     }
 
     #[test]
-    fn test_north_trees() {
-        let matrix: Matrix = vec![
+    fn test_viewing_distances() {
+        let matrix: Matrix = Grid::from_rows(vec![
             vec![ 1,  2,  3,  4],
             vec![ 5,  6,  7,  8],
             vec![ 1,  4,  3,  5],
             vec![ 1,  5,  7 , 8],
-        ];
-        let row = 1;
-        let col = 1;
-        let tt = matrix[1][1];
-        let north_heights: Vec<u8> = matrix[..row].iter().rev().map(|v| v[col]).take_while(|h| *h <= tt).collect();
-        let south_heights: Vec<u8> = matrix[row+1..].iter().map(|v| v[col]).take_while(|h| *h <= tt).collect();
-        let west_heights: Vec<u8> = matrix[row][..col].iter().rev().take_while(|h| **h <= tt).cloned().collect();
-        let east_heights: Vec<u8> = matrix[row][col+1..].iter().take_while(|h| **h <= tt).cloned().collect();
-        assert_eq!(north_heights, vec![2]);
-        assert_eq!(south_heights, vec![4, 5]);
-        assert_eq!(east_heights, vec![]);
-        assert_eq!(west_heights, vec![5]);
+        ]);
+        assert_eq!(viewing_distance(&matrix, (1, 1), (-1, 0)), 1);
+        assert_eq!(viewing_distance(&matrix, (1, 1), (1, 0)), 2);
+        assert_eq!(viewing_distance(&matrix, (1, 1), (0, 1)), 1);
+        assert_eq!(viewing_distance(&matrix, (1, 1), (0, -1)), 1);
+        assert_eq!(viewing_distance(&matrix, (2, 1), (0, 1)), 2);
+        assert_eq!(scenic_score(&matrix, (1, 1)), 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn draw_the_forest() {
+        let matrix = read_input(&input::read_input(YEAR, 8, InputKind::Example(1)).unwrap()).unwrap();
+        let visible = draw_forest(&matrix, Part::One);
+        assert_eq!(visible.rows().next().unwrap().iter().map(|cell| cell.glyph).collect::<String>(), "30373");
+        let green = visible.rows().flatten().filter(|cell| cell.colour == Rgb::GREEN).count();
+        assert_eq!(green, 21);
+        // the tree with the best view, scoring 8, is in the middle of the fourth row
+        let scores = draw_forest(&matrix, Part::Two);
+        assert_eq!(scores.get(2, 3).unwrap().colour, Rgb::RED);
+    }
+}
@@ -1,186 +1,188 @@
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::collections::HashSet;
+use aoc_common::geometry::{Direction, Point2};
+use aoc_common::parse_error::ParseError;
+use aoc_common::scan::{self, Scanner};
+use aoc_common::solution::{Part, Solution};
+use aoc_common::visual::{Frames, Rgb, Viewport};
 
-/// Advent of Code Day 09 - Rope Bride
-///
+// Advent of Code Day 09 - Rope Bride
 
 /// Movement
 #[derive(Debug)]
-enum Move {
-    Up(i32),
-    Down(i32),
-    Left(i32),
-    Right(i32),
+pub enum Move {
+    Up(u32),
+    Down(u32),
+    Left(u32),
+    Right(u32),
 }
 
-/// x,y position in a cartesian coordinate system
-#[derive(Debug, Eq, PartialEq, Default, Copy, Clone, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
+/// read_input into a vec of Moves
+fn read_input(input: &str) -> Result<Vec<Move>, ParseError> {
+    scan::lines(input, parse_move)
 }
 
-type Vector = Point;
-
-impl Point {
+/// a line like `R 4`
+fn parse_move(line: &mut Scanner) -> Result<Move, ParseError> {
+    let dir = line.one_of::<fn(u32) -> Move>(
+        &[("U", Move::Up), ("D", Move::Down), ("L", Move::Left), ("R", Move::Right)],
+        "one of U, D, L, R",
+    )?;
+    line.literal(" ")?;
+    Ok(dir(line.unsigned("a number of steps")?))
+}
 
-    fn new(x: i32, y: i32) -> Self { Self { x, y } }
+/// pull a rope made of `knots` knots through the given moves, returning the number of
+/// positions the tail of the rope visited at least once
+fn tail_positions(moves: &[Move], knots: usize) -> usize {
+    pull_rope(moves, knots, |_| ())
+}
 
-    /// compute the vector originating from this point to the other point
-    fn vec_to(&self, other: &Point) -> Vector {
-        Vector {
-            x: other.x - self.x,
-            y: other.y - self.y,
+/// pull the rope through the moves, calling `after_step` with the knots (head first) after
+/// each step the head takes, and return the number of positions the tail visited
+fn pull_rope(moves: &[Move], knots: usize, mut after_step: impl FnMut(&[Point2])) -> usize {
+    let mut points: Vec<Point2> = vec![Point2::ORIGIN; knots];
+    let mut visited: HashSet<Point2> = HashSet::new();
+    visited.insert(Point2::ORIGIN);
+
+    for mv in moves {
+        // the distance to move, and the direction to move the head one unit in
+        let (amt, dir) = match *mv {
+            Move::Up(amt) => (amt, Direction::North),
+            Move::Right(amt) => (amt, Direction::East),
+            Move::Down(amt) => (amt, Direction::South),
+            Move::Left(amt) => (amt, Direction::West),
+        };
+        for _ in 0..amt {
+            // move the head of points first
+            // then iterate thru the remaining points to see if each of them should move based on
+            //  the position of the previous point
+            points[0] += dir.vec();
+            for i in 1..knots {
+                // a knot that is no longer touching the previous knot moves one step straight
+                // or diagonally towards it
+                if points[i].chebyshev(points[i - 1]) >= 2 {
+                    let step = (points[i - 1] - points[i]).signum();
+                    points[i] += step;
+                    if i == knots - 1 {
+                        visited.insert(points[i]);
+                    }
+                }
+            }
+            after_step(&points);
         }
     }
+    visited.len()
+}
 
-    /// returns the length of this vector rounded down to the nearest integer
-    fn vec_length(&self) -> i32 {
-        let len = ((self.x.pow(2) + self.y.pow(2)) as f32).sqrt();
-        len.trunc() as i32
+/// the number of knots in the rope for each part
+fn knots(part: Part) -> usize {
+    match part {
+        Part::One => 2,
+        Part::Two => 10,
     }
+}
 
-    /// returns a new point obtained from moving this point by some delta amount
-    fn do_move(&self, delta: &Point) -> Point {
-        Point {
-            x: self.x + delta.x,
-            y: self.y + delta.y,
+/// draw the rope after every step, over the positions its tail has visited so far
+fn draw_rope(moves: &[Move], knots: usize, frames: &mut dyn Frames) {
+    // a first pass finds every point the rope reaches, so all the frames share one view
+    let mut reached = vec![Point2::ORIGIN];
+    pull_rope(moves, knots, |points| reached.extend_from_slice(points));
+    let view = Viewport::fit(reached, 120);
+
+    let mut visited = HashSet::from([Point2::ORIGIN]);
+    pull_rope(moves, knots, |points| {
+        visited.insert(points[knots - 1]);
+        let mut canvas = view.canvas();
+        for &p in &visited {
+            view.draw(&mut canvas, p, '#', Rgb::GREY);
         }
-    }
-
-    /// returns the delta vector required to move this point one unit towards the other point
-    fn delta_one(&self, other: &Point) -> Point {
-        let v = self.vec_to(other);
-        match v {
-            // other is on top of this point
-            Vector { x: 0, y: 0 } => Point::default(),
-            // other is directly up from this point
-            Vector { x: 0, y: vy} if vy > 0 => Point::new(0, 1),
-            // other is directly down from this point
-            Vector { x: 0, y: vy} if vy < 0 => Point::new(0, -1),
-            // other is directly left of this point
-            Vector { x: vx, y: 0} if vx < 0 => Point::new(-1, 0),
-            // other is directly right of this point
-            Vector { x: vx, y: 0} if vx > 0 => Point::new(1, 0),
-            // other is up and left from this point
-            Vector { x: vx, y: vy } if vx < 0 && vy > 0 => Point::new(-1, 1),
-            // other is up and right from this point
-            Vector { x: vx, y: vy } if vx > 0 && vy > 0 => Point::new(1, 1),
-            // other is down and left from this point
-            Vector { x: vx, y: vy } if vx < 0 && vy < 0 => Point::new(-1, -1),
-            // other os down and right
-            Vector { x: vx, y: vy } if vx > 0 && vy < 0 => Point::new(1, -1),
-            _ => panic!("{:?} did not match a pattern", &v),
+        view.draw(&mut canvas, Point2::ORIGIN, 's', Rgb::WHITE);
+        // knots nearer the head are drawn last, so they cover the ones behind them
+        for (i, &p) in points.iter().enumerate().rev() {
+            let glyph = if i == 0 { 'H' } else { char::from_digit(i as u32, 10).unwrap_or('T') };
+            let colour = Rgb::YELLOW.blend(Rgb::RED, 1.0 - i as f64 / knots as f64);
+            view.draw(&mut canvas, p, glyph, colour);
         }
-    }
+        frames.frame(&canvas);
+    });
 }
 
+/// Day 09 - Rope Bridge
+pub struct RopeBridge;
 
-/// read_input file into a vec of Moves
-fn read_input(path: &Path) -> Vec<Move> {
-    let f = std::fs::File::open(path).unwrap();
-    let reader = BufReader::new(f);
-
-    let moves : Vec<Move> = reader.lines()
-        .map(|line| line.unwrap())
-        .map(|s: String| {
-            let v: Vec<&str> = s.split(" ").collect();
-            let amt: i32 = v[1].parse().expect("valid i32 amount");
-            match v[0] {
-                "U" => Move::Up(amt),
-                "D" => Move::Down(amt),
-                "L" => Move::Left(amt),
-                "R" => Move::Right(amt),
-                _ => panic!("invalid move found in input"),
-            }
-        })
-        .collect();
+impl Solution for RopeBridge {
+    type Input = Vec<Move>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    /// how many positions does the tail of a two knot rope visit at least once?
+    fn part1(moves: &Vec<Move>) -> usize {
+        tail_positions(moves, knots(Part::One))
+    }
 
-   moves
+    /// how many positions does the tail of a ten knot rope visit at least once?
+    fn part2(moves: &Vec<Move>) -> usize {
+        tail_positions(moves, knots(Part::Two))
+    }
+
+    /// the rope moving across the bridge, and the trail its tail leaves
+    fn visualise(moves: &Vec<Move>, part: Part, frames: &mut dyn Frames) -> bool {
+        draw_rope(moves, knots(part), frames);
+        true
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
-    use std::path::Path;
-    use crate::d09_rope_bridge::{Move, Point, read_input, Vector};
+    use aoc_common::input::{self, InputKind};
+    use crate::YEAR;
+    use aoc_common::solution::Part;
+    use aoc_common::visual::Canvas;
+    use crate::d09_rope_bridge::{draw_rope, knots, read_input, tail_positions};
 
     #[test]
     fn test_read_input() {
-        let path = Path::new("../input-2022/d09-input.txt");
-        let moves = read_input(&path);
-        dbg!(moves);
+        let input = input::read_input(YEAR, 9, InputKind::Example(1)).unwrap();
+        let moves = read_input(&input).unwrap();
+        assert_eq!(moves.len(), 8);
+        let err = read_input("R 4\nR -3\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert!(read_input("X 4").is_err());
     }
 
     #[test]
     fn do_part1() {
-        let path = Path::new("../input-2022/d09-input.txt");
-        let moves = read_input(&path);
-        let mut visited: HashSet<Point> = HashSet::new();
-        let mut head = Point::default();
-        let mut tail = Point::default();
-        visited.insert(tail);
-
-        for mv in moves {
-            // compute distance to move, and the amount delta to move the point one unit
-            let (amt, delta) = {
-                match mv {
-                    Move::Up(amt) => (amt, Vector::new(0, 1)),
-                    Move::Right(amt) => (amt, Vector::new(1, 0)),
-                    Move::Down(amt) => (amt, Vector::new(0, -1)),
-                    Move::Left(amt) => (amt, Vector::new(-1, 0)),
-                }
-            };
-            for _ in 0..amt {
-                // move head, and compute distance from tail to head
-                head = head.do_move(&delta);
-                if tail.vec_to(&head).vec_length() >= 2 {
-                    // move tail one unit closer to head, and mark new pos as visited
-                    let delta_vec = tail.delta_one(&head);
-                    tail = tail.do_move(&delta_vec);
-                    visited.insert(tail);
-                }
-            }
-        }
-        println!("tail visited {} positions at least once", visited.len())
+        let input = input::read_input(YEAR, 9, InputKind::Example(1)).unwrap();
+        let moves = read_input(&input).unwrap();
+        assert_eq!(tail_positions(&moves, 2), 13);
     }
 
     #[test]
     fn do_part2() {
-        let path = Path::new("../input-2022/d09-input.txt");
-        let moves = read_input(&path);
-        let mut points: Vec<Point> = Vec::with_capacity(10);
-        for _ in 0..10 {
-            points.push(Point::default());
-        }
-        let mut visited: HashSet<Point> = HashSet::new();
-        visited.insert(Point::default());
-
-        for mv in moves {
-            let (amt, delta) = {
-                match mv {
-                    Move::Up(amt) => (amt, Vector::new(0, 1)),
-                    Move::Right(amt) => (amt, Vector::new(1, 0)),
-                    Move::Down(amt) => (amt, Vector::new(0, -1)),
-                    Move::Left(amt) => (amt, Vector::new(-1, 0)),
-                }
-            };
-            for _ in 0..amt {
-                // move the head of points first
-                // then iterate thru points 1..=9 to see if each of them should move based on
-                //  the position of the previous point
-                points[0] = points[0].do_move(&delta);
-                for i in 1..=9_usize {
-                    if points[i].vec_to(&points[i - 1]).vec_length() >= 2 {
-                        let delta_vec = points[i].delta_one(&points[i - 1]);
-                        points[i] = points[i].do_move(&delta_vec);
-                        if i == 9 {
-                            visited.insert(points[i]);
-                        }
-                    }
-                }
-            }
-        }
-        println!("part 2 tail visited {} positions at least once", visited.len())
+        let input = input::read_input(YEAR, 9, InputKind::Example(1)).unwrap();
+        let moves = read_input(&input).unwrap();
+        assert_eq!(tail_positions(&moves, 10), 1);
+        let input = input::read_input(YEAR, 9, InputKind::Example(2)).unwrap();
+        let moves = read_input(&input).unwrap();
+        assert_eq!(tail_positions(&moves, 10), 36);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn draw_a_frame_for_every_step() {
+        let input = input::read_input(YEAR, 9, InputKind::Example(1)).unwrap();
+        let moves = read_input(&input).unwrap();
+        let mut frames: Vec<Canvas> = Vec::new();
+        draw_rope(&moves, knots(Part::One), &mut frames);
+        // the example moves the head 24 steps, within a 6 by 5 square
+        assert_eq!(frames.len(), 24);
+        let last = frames.last().unwrap();
+        assert_eq!((last.width(), last.height()), (6, 5));
+        let trail = last.rows().flatten().filter(|cell| cell.glyph == '#').count();
+        // of the 13 positions the tail visited, the start, the head and the tail cover one each
+        assert_eq!(trail, 10);
+    }
+}
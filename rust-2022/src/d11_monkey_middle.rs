@@ -1,269 +1,231 @@
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-/// Day 11 Monkey in the middle
+use aoc_common::parse_error::ParseError;
+use aoc_common::scan::{blocks, Block};
+use aoc_common::solution::Solution;
+// Day 11 Monkey in the middle
 
 /// If the Option == None, it means we perform the operation on the old worry level value
 #[derive(Eq, PartialEq, Clone, Debug)]
-enum Op {
+pub enum Op {
     Add(Option<usize>),
     Mul(Option<usize>),
 }
 
-#[derive(Debug, Eq, PartialEq)]
-struct Monkey {
+impl Op {
+    /// the new worry level, or None if it overflows
+    fn apply(&self, old: u128) -> Option<u128> {
+        match *self {
+            Op::Add(v) => old.checked_add(v.map_or(old, |v| v as u128)),
+            Op::Mul(v) => old.checked_mul(v.map_or(old, |v| v as u128)),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Monkey {
     id: usize,
     items: Vec<usize>,
     item_op: Op,
     test_divisor: usize,
     test_true: usize,
     test_false: usize,
-    inspection_count: usize,
 }
 
-fn read_input(path: &Path) -> Vec<Monkey> {
-    let f = std::fs::File::open(path).unwrap();
-    let reader = BufReader::new(f);
-    let mut line_iter = reader.lines();
-    let mut monkeys: Vec<Monkey> = vec![];
-
-    while let Some(buf_line) = line_iter.next() {
-        let line = buf_line.unwrap();
-        if line.starts_with("Monkey") {
-            let id = line.trim().split([' ', ':']).filter(|&s| s.starts_with(|c:char| c.is_ascii_digit())).last().unwrap();
-            let id = id.parse::<usize>().unwrap();
-
-            let items_line = line_iter.next().unwrap().unwrap();
-            let items: Vec<_> = items_line.trim()
-                .split([' ', ':', ','])
-                .filter(|&s| s.starts_with(|c: char| c == '-' || c.is_ascii_digit()))
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect();
-
-            // parse operation line
-            let op_line = line_iter.next().unwrap().unwrap();
-            let tokens: Vec<_> = op_line.trim()
-                .split(' ')
-                .collect();
-            let op_value: Option<usize> = match tokens[5] {
-                "old" => None,
-                s => Some(s.parse::<usize>().unwrap()),
-            };
-            let op: Op = match tokens[4] {
-                "+" => Op::Add(op_value),
-                "*" => Op::Mul(op_value),
-                _ => panic!("unknown operation {}",tokens[4]),
-            };
-
-            // parse test divisible line
-            let td_line = line_iter.next().unwrap().unwrap();
-            let test_divisor = td_line.split(' ').last().unwrap().parse::<usize>().unwrap();
-
-            // parse if true line
-            let test_true_line = line_iter.next().unwrap().unwrap();
-            let test_true = test_true_line.split(' ').last().unwrap().parse::<usize>().unwrap();
+/// parses a monkey's description, i.e.
+///
+/// ```text
+/// Monkey 0:
+///   Starting items: 79, 98
+///   Operation: new = old * 19
+///   Test: divisible by 23
+///     If true: throw to monkey 2
+///     If false: throw to monkey 3
+/// ```
+///
+/// The monkey should be number `id` of `count` monkeys, and throw to the others.
+/// `divisor_product` is the product of the divisors of the monkeys before it, which must still
+/// fit in a u64 once multiplied by this monkey's
+fn parse_monkey(block: Block, id: usize, count: usize, divisor_product: u64) -> Result<Monkey, ParseError> {
+    let mut lines = block.lines();
+    // the next line, which starts with `prefix` after any indentation
+    let mut next_line = |prefix: &str, example: &str| {
+        let mut line = lines.next().ok_or_else(|| block.ended_early(format!("a line like '{}'", example)))?;
+        line.skip_spaces();
+        line.literal(prefix)?;
+        Ok::<_, ParseError>(line)
+    };
+
+    let mut line = next_line("Monkey ", "Monkey 0:")?;
+    let before = line.clone();
+    if line.unsigned::<usize>("a monkey id")? != id {
+        return Err(before.error_here(format!("monkey {}", id)));
+    }
+    line.literal(":")?;
+    line.end()?;
+
+    let mut line = next_line("Starting items: ", "Starting items: 79, 98")?;
+    let items = line.separated(", ", |s| s.unsigned("a worry level"))?;
+    line.end()?;
+
+    let mut line = next_line("Operation: new = old ", "Operation: new = old * 19")?;
+    let op = line.one_of::<fn(Option<usize>) -> Op>(&[("+", Op::Add), ("*", Op::Mul)], "one of + or *")?;
+    line.literal(" ")?;
+    let op_value = if line.eat("old") { None } else { Some(line.unsigned("a number or 'old'")?) };
+    line.end()?;
+
+    // the number ending the next line, which must be in `valid`
+    let mut last_number = |prefix: &str, example: &str, expected: &str, valid: &dyn Fn(usize) -> bool| {
+        let mut line = next_line(prefix, example)?;
+        let before = line.clone();
+        let n = line.unsigned(expected)?;
+        if !valid(n) {
+            return Err(before.error_here(expected));
+        }
+        line.end()?;
+        Ok::<_, ParseError>(n)
+    };
+    let divisor_fits = |n: usize| n > 0 && (n as u64).checked_mul(divisor_product).is_some();
+    let divisor = "a divisor above 0 that keeps the product of the divisors within 64 bits";
+    let test_divisor = last_number("Test: divisible by ", "Test: divisible by 23", divisor, &divisor_fits)?;
+    let target = format!("the id of another of the {} monkeys", count);
+    let other = |n| n < count && n != id;
+    let test_true = last_number("If true: throw to monkey ", "If true: throw to monkey 2", &target, &other)?;
+    let test_false = last_number("If false: throw to monkey ", "If false: throw to monkey 3", &target, &other)?;
+
+    if let Some(line) = lines.next() {
+        return Err(line.error(line.rest(), "a blank line before the next monkey"));
+    }
+    Ok(Monkey {
+        id,
+        items,
+        item_op: op(op_value),
+        test_divisor,
+        test_true,
+        test_false,
+    })
+}
 
-            // parse if false line
-            let test_false_line = line_iter.next().unwrap().unwrap();
-            let test_false = test_false_line.split(' ').last().unwrap().parse::<usize>().unwrap();
+fn read_input(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let blocks = blocks(input);
+    let mut monkeys = vec![];
+    let mut divisor_product = 1;
+    for (id, &block) in blocks.iter().enumerate() {
+        let monkey = parse_monkey(block, id, blocks.len(), divisor_product)?;
+        divisor_product *= monkey.test_divisor as u64;
+        monkeys.push(monkey);
+    }
+    Ok(monkeys)
+}
 
-            monkeys.push(Monkey {
-                id,
-                items,
-                item_op: op,
-                test_divisor,
-                test_true,
-                test_false,
-                inspection_count: 0
-            })
+/// the product of the inspection counts of the two most active monkeys after `rounds` rounds.
+/// `relieve` is applied to each worry level after the monkey's operation. Fails if a worry level
+/// or the product overflows
+fn monkey_business(monkeys: &[Monkey], rounds: usize, relieve: impl Fn(u128) -> u128) -> Result<usize, String> {
+    let mut items: Vec<Vec<u128>> = monkeys.iter()
+        .map(|m| m.items.iter().map(|&item| item as u128).collect())
+        .collect();
+    let mut inspection_counts = vec![0_usize; monkeys.len()];
+
+    for _round in 0..rounds {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            inspection_counts[i] += items[i].len();
+            for worry in std::mem::take(&mut items[i]) {
+                let worry = monkey.item_op.apply(worry).ok_or("a worry level is too large to keep track of")?;
+                let worry = relieve(worry);
+                let target = if worry.is_multiple_of(monkey.test_divisor as u128) { monkey.test_true } else { monkey.test_false };
+                items[target].push(worry);
+            }
         }
     }
-
-    monkeys
+    inspection_counts.sort_unstable_by(|a, b| b.cmp(a));
+    inspection_counts.iter()
+        .take(2)
+        .try_fold(1_usize, |product, &count| product.checked_mul(count))
+        .ok_or_else(|| "the monkey business is too large to count".to_string())
 }
 
 /// Chasing all of the monkeys at once is impossible; you're going to have to focus on
 /// the two most active monkeys if you want any hope of getting your stuff back. Count the
 /// total number of times each monkey inspects items over 20 rounds:
 /// Keep track of how many times a monkey inspected an item. The level of monkey business is the
-/// product of the two monkeys with the highest inspection count
-pub fn part1() {
-    let mut monkeys = read_input(Path::new("../input-2022/d11-input.txt"));
-
-    for _round in 0..20 {
-        for i in 0..monkeys.len() {
-            let true_id = monkeys[i].test_true;
-            let false_id = monkeys[i].test_false;
-            let mut true_items: Vec<usize> = vec![];
-            let mut false_items: Vec<usize> = vec![];
-            let item_len = monkeys[i].items.len();
-            monkeys.get_mut(i).unwrap().inspection_count += item_len;
+/// product of the two monkeys with the highest inspection count.
+///
+/// Worry levels are divided by 3, so they can't be kept small with a modulus and an `old * old`
+/// operation can make them too large to track
+fn part1(monkeys: &[Monkey]) -> Result<usize, String> {
+    monkey_business(monkeys, 20, |worry| worry / 3)
+}
 
-            for item in &monkeys[i].items {
-                let mut worry = item.clone();
-                match &monkeys[i].item_op {
-                    Op::Add(Some(v)) => worry += v,
-                    Op::Add(None) => worry += worry,
-                    Op::Mul(Some(v)) => worry *= v,
-                    Op::Mul(None) => worry *= worry,
-                }
-                let div3: usize = worry / 3;
-                if div3 % &monkeys[i].test_divisor == 0 {
-                    true_items.push(div3);
-                } else {
-                    false_items.push(div3);
-                }
-            }
-            monkeys.get_mut(i).unwrap().items.clear();
-            monkeys.get_mut(true_id).unwrap().items.append(&mut true_items);
-            monkeys.get_mut(false_id).unwrap().items.append(&mut false_items);
-        }
-    }
-    monkeys.sort_by(|m1, m2| m2.inspection_count.partial_cmp(&m1.inspection_count).unwrap() );
-    let monkey_level: usize = monkeys.iter().take(2).map(|m| &m.inspection_count).product();
-    dbg!(&monkeys);
-    println!("monkey level {}", monkey_level);
+/// part2 is like part1 but we don't divide worry levels by 3. And we do 10_000 rounds instead of 20.
+/// Worry levels are kept below the product of the divisors instead, which parsing checks fits
+/// in a u64, so squaring one always fits in a u128
+fn part2(monkeys: &[Monkey]) -> Result<usize, String> {
+    let divisor_product = monkeys.iter().map(|m| m.test_divisor as u128).product::<u128>();
+    monkey_business(monkeys, 10_000, |worry| worry % divisor_product)
 }
 
-/// part2 is like part1 but we don't divide worry levels by 3. And we do 10_000 rounds instead of 20
-pub fn part2() {
-    let mut monkeys = read_input(Path::new("../input-2022/d11-input.txt"));
-    let divisor_product = monkeys.iter().map(|m| m.test_divisor).product::<usize>();
+/// Day 11 - Monkey in the Middle
+pub struct MonkeyInTheMiddle;
 
-    for _round in 0..10_000 {
-        for i in 0..monkeys.len() {
-            let true_id = monkeys[i].test_true;
-            let false_id = monkeys[i].test_false;
-            let mut true_items: Vec<usize> = vec![];
-            let mut false_items: Vec<usize> = vec![];
-            let item_len = monkeys[i].items.len();
-            monkeys.get_mut(i).unwrap().inspection_count += item_len;
+impl Solution for MonkeyInTheMiddle {
+    type Input = Vec<Monkey>;
+    type Answer1 = Result<usize, String>;
+    type Answer2 = Result<usize, String>;
 
-            for item in &monkeys[i].items {
-                let mut worry = item % divisor_product;
-                match &monkeys[i].item_op {
-                    Op::Add(Some(v)) => worry += v,
-                    Op::Add(None) => worry += worry,
-                    Op::Mul(Some(v)) => worry *= v,
-                    Op::Mul(None) => worry *= worry,
-                }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
 
-                if worry % &monkeys[i].test_divisor == 0 {
-                    true_items.push(worry.clone());
-                } else {
-                    false_items.push(worry.clone());
-                }
-            }
-            monkeys.get_mut(i).unwrap().items.clear();
-            monkeys.get_mut(true_id).unwrap().items.append(&mut true_items);
-            monkeys.get_mut(false_id).unwrap().items.append(&mut false_items);
-        }
+    fn part1(monkeys: &Vec<Monkey>) -> Result<usize, String> {
+        part1(monkeys)
     }
-    monkeys.sort_by(|m1, m2| m2.inspection_count.partial_cmp(&m1.inspection_count).unwrap() );
-    let monkey_level: usize = monkeys.iter().take(2).map(|m| &m.inspection_count).product();
-    dbg!(&monkeys);
-    println!("monkey level {}", monkey_level);
-}
 
+    fn part2(monkeys: &Vec<Monkey>) -> Result<usize, String> {
+        part2(monkeys)
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::d11_monkey_middle::{Monkey, Op, part1, part2};
+    use aoc_common::input::{self, InputKind};
+    use crate::YEAR;
+    use aoc_common::scan::blocks;
+    use crate::d11_monkey_middle::{Monkey, Op, parse_monkey, part1, part2, read_input};
 
     #[test]
     fn test_part1() {
-        part1();
+        let monkeys = read_input(&input::read_input(YEAR, 11, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(part1(&monkeys), Ok(10605));
     }
 
     #[test]
     fn test_part2() {
-        part2();
+        let monkeys = read_input(&input::read_input(YEAR, 11, InputKind::Example(1)).unwrap()).unwrap();
+        assert_eq!(part2(&monkeys), Ok(2713310158));
     }
 
     #[test]
-    fn test_monkey_line_parsing() {
-        let line = "Monkey 0:";
-        let tokens: Vec<_> = line.split([' ', ':']).collect();
-        assert_eq!(tokens.len(), 3);
-        assert!(tokens[1].parse::<usize>().is_ok());
-    }
-
-    #[test]
-    fn test_items_line_parsing() {
-        let line = "  Starting items: 76, -88, 96, 97, 58, 61, 67";
-        let tokens: Vec<_> = line.trim()
-            .split([' ', ':', ','])
-            .filter(|&s| s.starts_with(|c: char| c == '-' || c.is_ascii_digit()))
-            .map(|s| s.parse::<usize>().unwrap())
-            .collect();
-        assert_eq!(tokens.len(), 7);
-    }
-
-    #[test]
-    fn test_operation_line_parsing_with_last_numeric() {
-        let line = "  Operation: new = old * 19";
-        let tokens: Vec<_> = line.trim()
-            .split(' ')
-            .collect();
-        assert_eq!(tokens.len(), 6);
-        assert!(matches!(tokens[4], "*" | "+"));
-        assert!(tokens[5].starts_with(|c: char| c == '-' || c.is_ascii_digit()));
-        assert_eq!(tokens[5].parse::<usize>().unwrap(), usize::from(19_usize));
-    }
-
-    // no negative numbers in input file, this test is not needed
-    // #[test]
-    // fn test_operation_line_parsing_with_last_negative_numeric() {
-    //     let line = "  Operation: new = old * -19";
-    //     let tokens: Vec<_> = line.trim()
-    //         .split(' ')
-    //         .collect();
-    //     assert_eq!(tokens.len(), 6);
-    //     assert!(matches!(tokens[4], "*" | "+" | "-" | "/"));
-    //     assert!(tokens[5].starts_with(|c: char| c == '-' || c.is_ascii_digit()));
-    //     assert_eq!(tokens[5].parse::<u128>().unwrap(), -19);
-    // }
-
-    #[test]
-    fn test_operation_line_parsing_with_last_eq_old() {
-        let line = "  Operation: new = old * old";
-        let tokens: Vec<_> = line.trim()
-            .split(' ')
-            .collect();
-        assert_eq!(tokens.len(), 6);
-        assert!(matches!(tokens[4], "*" | "+"));
-        assert_eq!(tokens[5], "old");
-    }
-
-    #[test]
-    fn test_divisible_line_parsing() {
-        let line = "  Test: divisible by 17";
-        let tokens: Vec<_> = line.trim()
-            .split(' ')
-            .collect();
-        assert_eq!(tokens.len(), 4);
-        assert_eq!(tokens.last().unwrap(), &"17");
-    }
-
-    #[test]
-    fn test_if_true_line_parsing() {
-        let line = "  If true: throw to monkey 2";
-        let tokens: Vec<_> = line.trim()
-            .split(' ')
-            .collect();
-        assert_eq!(tokens.len(), 6);
-        assert_eq!(tokens[1], "true:");
-        assert_eq!(tokens.last().unwrap(), &"2");
-    }
-
-    #[test]
-    fn test_if_false_line_parsing() {
-        let line = "  If false: throw to monkey 3";
-        let tokens: Vec<_> = line.trim()
-            .split(' ')
-            .collect();
-        assert_eq!(tokens.len(), 6);
-        assert_eq!(tokens[1], "false:");
-        assert_eq!(tokens.last().unwrap(), &"3");
+    fn test_monkey_parsing() {
+        let monkey = |text: &str| parse_monkey(blocks(text)[0], 0, 4, 1);
+        let monkey0 = "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old * 19\n  Test: divisible by 23\n    If true: throw to monkey 2\n    If false: throw to monkey 3";
+        let parsed = monkey(monkey0).unwrap();
+        assert_eq!(parsed.items, vec![79, 98]);
+        assert_eq!(parsed.item_op, Op::Mul(Some(19)));
+        assert_eq!((parsed.test_divisor, parsed.test_true, parsed.test_false), (23, 2, 3));
+
+        let err = monkey(&monkey0.replace("* 19", "- 19")).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 24, "-"));
+        let err = monkey(&monkey0.replace("79, 98", "79, x")).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 23, "x"));
+        let err = monkey(monkey0.rsplit_once('\n').unwrap().0).unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (6, "a line like 'If false: throw to monkey 3'"));
+
+        let err = monkey(&monkey0.replace("monkey 2", "monkey 0")).unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (5, 30, "the id of another of the 4 monkeys"));
+        assert_eq!(monkey(&monkey0.replace("monkey 3", "monkey 4")).unwrap_err().line, 6);
+        assert_eq!(monkey(&monkey0.replace("by 23", "by 0")).unwrap_err().line, 4);
+        let err = parse_monkey(blocks(monkey0)[0], 0, 4, u64::MAX / 22).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (4, 22, "23"));
+        assert_eq!(monkey(&monkey0.replace("Monkey 0", "Monkey 1")).unwrap_err().text, "1:");
     }
 
     #[test]
@@ -272,73 +234,38 @@ mod tests {
   Starting items: 76, 88, 96, 97, 58, 61, 67
   Operation: new = old * 19
   Test: divisible by 3
-    If true: throw to monkey 2
-    If false: throw to monkey 3
+    If true: throw to monkey 1
+    If false: throw to monkey 1
 
-Monkey 5:
+Monkey 1:
   Starting items: 59, 62, 53, 62
   Operation: new = old * old
   Test: divisible by 7
-    If true: throw to monkey 4
-    If false: throw to monkey 7";
-
-        let lines: Vec<&str> = input.lines().collect();
-        let mut line_iter = lines.iter();
-        let mut monkeys: Vec<Monkey> = vec![];
-        while let Some(&line) = line_iter.next() {
-            if line.starts_with("Monkey") {
-                let id = line.trim().split([' ', ':']).filter(|&s| s.starts_with(|c:char| c.is_ascii_digit())).last().unwrap();
-                let id = id.parse::<usize>().unwrap();
-
-                let items_line = line_iter.next().unwrap();
-                let items: Vec<_> = items_line.trim()
-                    .split([' ', ':', ','])
-                    .filter(|&s| s.starts_with(|c: char| c == '-' || c.is_ascii_digit()))
-                    .map(|s| s.parse::<usize>().unwrap())
-                    .collect();
-
-                // parse operation line
-                let op_line = line_iter.next().unwrap();
-                let tokens: Vec<_> = op_line.trim()
-                    .split(' ')
-                    .collect();
-                let op_value = match tokens[5] {
-                    "old" => None,
-                    s => Some(s.parse::<usize>().unwrap()),
-                };
-                let op: Op = match tokens[4] {
-                    "+" => Op::Add(op_value),
-                    "*" => Op::Mul(op_value),
-                    _ => panic!("unknown operation {}",tokens[4]),
-                };
-
-                // parse test divisible line
-                let td_line = line_iter.next().unwrap();
-                let test_divisor = td_line.split(' ').last().unwrap().parse::<usize>().unwrap();
-
-                // parse if true line
-                let test_true_line = line_iter.next().unwrap();
-                let test_true = test_true_line.split(' ').last().unwrap().parse::<usize>().unwrap();
+    If true: throw to monkey 0
+    If false: throw to monkey 0";
 
-                // parse if false line
-                let test_false_line = line_iter.next().unwrap();
-                let test_false = test_false_line.split(' ').last().unwrap().parse::<usize>().unwrap();
-
-                monkeys.push(Monkey {
-                    id,
-                    items,
-                    item_op: op,
-                    test_divisor,
-                    test_true,
-                    test_false,
-                    inspection_count: 0,
-                })
-            }
-        }
-        let monkey1 = Monkey { id: 0, items: vec![76, 88, 96, 97, 58, 61, 67], item_op: Op::Mul(Some(19)), test_divisor: 3, test_true: 2, test_false: 3, inspection_count: 0 };
-        let monkey2 = Monkey { id: 5, items: vec![59, 62, 53, 62], item_op: Op::Mul(None), test_divisor: 7, test_true: 4, test_false: 7, inspection_count: 0 };
+        let monkeys = read_input(input).unwrap();
+        let monkey1 = Monkey { id: 0, items: vec![76, 88, 96, 97, 58, 61, 67], item_op: Op::Mul(Some(19)), test_divisor: 3, test_true: 1, test_false: 1 };
+        let monkey2 = Monkey { id: 1, items: vec![59, 62, 53, 62], item_op: Op::Mul(None), test_divisor: 7, test_true: 0, test_false: 0 };
         assert_eq!(monkeys.len(), 2);
         assert_eq!(monkeys[0], monkey1);
         assert_eq!(monkeys[1], monkey2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn squared_worry_levels_do_not_overflow() {
+        let squaring = |divisors: [u64; 2]| format!(
+            "Monkey 0:\n  Starting items: 4294967296\n  Operation: new = old * old\n  Test: divisible by {}\n    If true: throw to monkey 1\n    If false: throw to monkey 1\n\n\
+             Monkey 1:\n  Starting items: 18446744073709551615\n  Operation: new = old * old\n  Test: divisible by {}\n    If true: throw to monkey 0\n    If false: throw to monkey 0",
+            divisors[0], divisors[1]
+        );
+        let monkeys = read_input(&squaring([3, 5])).unwrap();
+        // after dividing by 3 the worry levels still outgrow a u128 within a few rounds
+        assert!(part1(&monkeys).is_err());
+        // monkey 0 inspects one item in the first round and both items after that
+        assert_eq!(part2(&monkeys), Ok(19_999 * 20_000));
+
+        let err = read_input(&squaring([1 << 40, 1 << 30])).unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (11, "1073741824"));
+    }
+}
@@ -1,11 +1,61 @@
 extern crate core;
 
-mod d01_calorie_counting;
-mod d02_rock_paper_scissors;
-mod d04_camp_cleanup;
-mod d05_supply_stacks;
-mod d07_no_space_left;
-mod d08_treetop_tree_house;
-mod d09_rope_bridge;
-mod d11_monkey_middle;
+pub mod d01_calorie_counting;
+pub mod d02_rock_paper_scissors;
+pub mod d04_camp_cleanup;
+pub mod d05_supply_stacks;
+pub mod d07_no_space_left;
+pub mod d08_treetop_tree_house;
+pub mod d09_rope_bridge;
+pub mod d11_monkey_middle;
 
+use aoc_common::solution::Day;
+
+/// the puzzle year these solutions are for
+pub const YEAR: u16 = 2022;
+
+/// all days with a solution, in day order
+pub static DAYS: &[Day] = &[
+    Day::new::<d01_calorie_counting::CalorieCounting>(1, "Calorie Counting"),
+    Day::new::<d02_rock_paper_scissors::RockPaperScissors>(2, "Rock Paper Scissors"),
+    Day::new::<d04_camp_cleanup::CampCleanup>(4, "Camp Cleanup"),
+    Day::new::<d05_supply_stacks::SupplyStacks>(5, "Supply Stacks"),
+    Day::new::<d07_no_space_left::NoSpaceLeft>(7, "No Space Left On Device"),
+    Day::new::<d08_treetop_tree_house::TreetopTreeHouse>(8, "Treetop Tree House"),
+    Day::new::<d09_rope_bridge::RopeBridge>(9, "Rope Bridge"),
+    Day::new::<d11_monkey_middle::MonkeyInTheMiddle>(11, "Monkey in the Middle"),
+];
+
+/// returns the registered solution for `day`, if there is one
+pub fn find_day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::answers::{Answers, ANSWERS_FILE};
+    use aoc_common::input::InputLocator;
+    use aoc_common::verify::{table, verify, Check, Status};
+    use crate::{DAYS, YEAR, find_day};
+
+    #[test]
+    fn days_are_registered_in_order() {
+        assert!(DAYS.windows(2).all(|w| w[0].day < w[1].day));
+    }
+
+    #[test]
+    fn find_registered_day() {
+        assert_eq!(find_day(7).unwrap().title, "No Space Left On Device");
+        assert!(find_day(3).is_none());
+    }
+
+    #[test]
+    fn registered_days_give_the_known_answers() {
+        let locator = InputLocator::find(YEAR).unwrap();
+        let answers = Answers::load(&locator.dir().join(ANSWERS_FILE)).unwrap();
+        let checks = verify(DAYS, &locator, &answers);
+        let failed: Vec<&Check> = checks.iter().filter(|c| c.status() == Status::Fail).collect();
+        assert!(failed.is_empty(), "\n{}", table(&checks));
+        assert!(checks.iter().any(|c| c.status() == Status::Pass));
+    }
+}
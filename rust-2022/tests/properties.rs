@@ -0,0 +1,334 @@
+//! Property tests for the 2022 days. Parsing must never panic, whatever the text, and every
+//! generated puzzle is printed as input text, parsed back and solved, and must give the same
+//! answers as a simple reference solution written from the puzzle description
+
+use std::collections::{BTreeMap, HashSet};
+use proptest::collection::vec;
+use proptest::prelude::*;
+use aoc_common::solution::{Part, Solution, SolveError};
+use rust_2022::d01_calorie_counting::Inventory;
+use rust_2022::d04_camp_cleanup::{assignment_index, CampCleanup};
+use rust_2022::{find_day, DAYS};
+
+/// solve `part` of `day` for `input`, which must parse
+fn solve(day: u8, input: &str, part: Part) -> String {
+    find_day(day).unwrap()
+        .solve(input, part)
+        .unwrap_or_else(|e| panic!("{}", e.diagnostic(input)))
+}
+
+fn join<T: ToString>(items: &[T], separator: &str) -> String {
+    items.iter().map(T::to_string).collect::<Vec<_>>().join(separator)
+}
+
+/// the score of a round of rock paper scissors, where 0 is rock, 1 paper and 2 scissors
+fn round_score(opponent: usize, player: usize) -> usize {
+    let outcome = [3, 6, 0][(player + 3 - opponent) % 3];
+    player + 1 + outcome
+}
+
+/// the top crate of each stack after running `moves` on `stacks`, moving `chunk` crates at a time
+fn top_crates(mut stacks: Vec<Vec<char>>, moves: &[(usize, usize, usize)], chunk: bool) -> String {
+    for &(amount, from, to) in moves {
+        let at = stacks[from].len() - amount;
+        let mut moved = stacks[from].split_off(at);
+        if !chunk {
+            moved.reverse();
+        }
+        stacks[to].extend(moved);
+    }
+    stacks.iter().filter_map(|stack| stack.last()).collect()
+}
+
+/// a stacks drawing, with no trailing spaces on the crate rows
+fn draw_stacks(stacks: &[Vec<char>]) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut rows: Vec<String> = (0..height).rev()
+        .map(|row| {
+            let cells: Vec<String> = stacks.iter()
+                .map(|stack| stack.get(row).map_or("   ".to_string(), |c| format!("[{}]", c)))
+                .collect();
+            cells.join(" ").trim_end().to_string()
+        })
+        .collect();
+    rows.push((1..=stacks.len()).map(|n| format!(" {} ", n)).collect::<Vec<_>>().join(" "));
+    rows.join("\n")
+}
+
+/// a directory of the device, for day 7
+#[derive(Debug, Default)]
+struct Dir {
+    files: Vec<(String, u64)>,
+    dirs: BTreeMap<String, Dir>,
+}
+
+impl Dir {
+    fn size(&self) -> u64 {
+        self.files.iter().map(|(_, size)| size).sum::<u64>() + self.dirs.values().map(Dir::size).sum::<u64>()
+    }
+
+    fn sizes(&self, sizes: &mut Vec<u64>) {
+        sizes.push(self.size());
+        self.dirs.values().for_each(|dir| dir.sizes(sizes));
+    }
+
+    /// the terminal output of listing this directory and everything below it
+    fn session(&self, out: &mut Vec<String>) {
+        out.push("$ ls".to_string());
+        out.extend(self.dirs.keys().map(|name| format!("dir {}", name)));
+        out.extend(self.files.iter().map(|(name, size)| format!("{} {}", size, name)));
+        for (name, dir) in &self.dirs {
+            out.push(format!("$ cd {}", name));
+            dir.session(out);
+            out.push("$ cd ..".to_string());
+        }
+    }
+}
+
+/// the number of positions the tail of a rope of `knots` knots visits
+fn rope_tail_positions(moves: &[(usize, i32)], knots: usize) -> usize {
+    let mut rope = vec![(0_i32, 0_i32); knots];
+    let mut visited = HashSet::from([(0, 0)]);
+    for &(dir, steps) in moves {
+        let (dx, dy) = [(0, 1), (0, -1), (-1, 0), (1, 0)][dir];
+        for _ in 0..steps {
+            rope[0] = (rope[0].0 + dx, rope[0].1 + dy);
+            for i in 1..knots {
+                let (x, y) = (rope[i - 1].0 - rope[i].0, rope[i - 1].1 - rope[i].1);
+                if x.abs() > 1 || y.abs() > 1 {
+                    rope[i] = (rope[i].0 + x.signum(), rope[i].1 + y.signum());
+                }
+            }
+            visited.insert(rope[knots - 1]);
+        }
+    }
+    visited.len()
+}
+
+/// a monkey for day 11: items, operation (0 is `+ n`, 1 `* n`, 2 `* old`, 3 `+ old`), divisor,
+/// and the monkeys thrown to
+#[derive(Debug, Clone)]
+struct Monkey {
+    items: Vec<u64>,
+    op: (usize, u64),
+    divisor: u64,
+    targets: (usize, usize),
+}
+
+impl Monkey {
+    fn text(&self, id: usize) -> String {
+        let op = match self.op {
+            (0, n) => format!("+ {}", n),
+            (1, n) => format!("* {}", n),
+            (2, _) => "* old".to_string(),
+            _ => "+ old".to_string(),
+        };
+        format!(
+            "Monkey {}:\n  Starting items: {}\n  Operation: new = old {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}",
+            id, join(&self.items, ", "), op, self.divisor, self.targets.0, self.targets.1
+        )
+    }
+}
+
+/// the monkey business after `rounds` rounds, or `None` if a worry level or the answer
+/// overflows
+fn monkey_business(monkeys: &[Monkey], rounds: usize, relief: bool) -> Option<u64> {
+    let modulus: u128 = monkeys.iter().map(|m| u128::from(m.divisor)).product();
+    let mut items: Vec<Vec<u128>> = monkeys.iter().map(|m| m.items.iter().map(|&i| u128::from(i)).collect()).collect();
+    let mut inspections = vec![0_u64; monkeys.len()];
+    for _ in 0..rounds {
+        for (id, monkey) in monkeys.iter().enumerate() {
+            for old in std::mem::take(&mut items[id]) {
+                inspections[id] += 1;
+                let new = match monkey.op {
+                    (0, n) => old.checked_add(u128::from(n))?,
+                    (1, n) => old.checked_mul(u128::from(n))?,
+                    (2, _) => old.checked_mul(old)?,
+                    _ => old.checked_add(old)?,
+                };
+                let new = if relief { new / 3 } else { new % modulus };
+                let target = if new % u128::from(monkey.divisor) == 0 { monkey.targets.0 } else { monkey.targets.1 };
+                items[target].push(new);
+            }
+        }
+    }
+    inspections.sort_unstable_by(|a, b| b.cmp(a));
+    inspections[0].checked_mul(inspections[1])
+}
+
+fn monkeys() -> impl Strategy<Value = Vec<Monkey>> {
+    (2..6usize).prop_flat_map(|count| {
+        // mostly small worry levels, and some large enough that squaring one overflows a u64
+        let item = prop_oneof![4 => 1..100u64, 1 => 1u64 << 32..u64::MAX];
+        let monkey = (vec(item, 1..5), (0..4usize, 1..20u64), 0..count - 1, 0..count - 1);
+        (vec(monkey, count), Just([2, 3, 5, 7, 11, 13, 17, 19, 23]).prop_shuffle())
+    })
+    .prop_map(|(monkeys, primes)| {
+        monkeys.into_iter()
+            .enumerate()
+            .map(|(id, (items, op, t, f))| {
+                // a target for each other monkey, skipping this one
+                let other = |n: usize| if n >= id { n + 1 } else { n };
+                Monkey { items, op, divisor: primes[id], targets: (other(t), other(f)) }
+            })
+            .collect()
+    })
+}
+
+proptest! {
+    #[test]
+    fn parsing_never_panics(text in "[0-9a-zA-Z :,\\-+#\\[\\]$/.\n]{0,200}") {
+        for day in DAYS {
+            if let Err(e) = day.parse(&text) {
+                prop_assert!(e.line <= text.lines().count() + 1, "day {}: {} is past the end", day.day, e);
+                prop_assert!(e.column >= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn calorie_counting(elves in vec(vec(1..100_000u32, 1..6), 1..20)) {
+        let input = elves.iter().map(|elf| join(elf, "\n")).collect::<Vec<_>>().join("\n\n");
+        let mut totals: Vec<u32> = elves.iter().map(|elf| elf.iter().sum()).collect();
+        totals.sort_unstable_by(|a, b| b.cmp(a));
+        prop_assert_eq!(solve(1, &input, Part::One), totals[0].to_string());
+        prop_assert_eq!(solve(1, &input, Part::Two), totals.iter().take(3).sum::<u32>().to_string());
+        let inventory: Inventory = input.parse().unwrap();
+        let top: Vec<u64> = totals.iter().map(|&total| total as u64).collect();
+        prop_assert_eq!(inventory.top(5), &top[..top.len().min(5)]);
+        for elf in 0..elves.len() {
+            let total = inventory.total(elf).unwrap();
+            prop_assert_eq!(top[inventory.rank(elf).unwrap() - 1], total);
+        }
+    }
+
+    #[test]
+    fn rock_paper_scissors(rounds in vec((0..3usize, 0..3usize), 1..50)) {
+        let input = join(&rounds.iter().map(|&(o, p)| format!("{} {}", "ABC".as_bytes()[o] as char, "XYZ".as_bytes()[p] as char)).collect::<Vec<_>>(), "\n");
+        let part1: usize = rounds.iter().map(|&(o, p)| round_score(o, p)).sum();
+        // the second column is the outcome: lose, draw or win
+        let part2: usize = rounds.iter().map(|&(o, outcome)| round_score(o, (o + outcome + 2) % 3)).sum();
+        prop_assert_eq!(solve(2, &input, Part::One), part1.to_string());
+        prop_assert_eq!(solve(2, &input, Part::Two), part2.to_string());
+    }
+
+    #[test]
+    fn camp_cleanup(pairs in vec(((1..100u32, 0..20u32), (1..100u32, 0..20u32)), 1..50)) {
+        let pairs: Vec<((u32, u32), (u32, u32))> = pairs.into_iter().map(|((a, l), (b, m))| ((a, a + l), (b, b + m))).collect();
+        let input = join(&pairs.iter().map(|((a, b), (c, d))| format!("{}-{},{}-{}", a, b, c, d)).collect::<Vec<_>>(), "\n");
+        let contained = pairs.iter().filter(|((a, b), (c, d))| (a <= c && d <= b) || (c <= a && b <= d)).count();
+        let overlapping = pairs.iter().filter(|((a, b), (c, d))| a <= d && c <= b).count();
+        prop_assert_eq!(solve(4, &input, Part::One), contained.to_string());
+        prop_assert_eq!(solve(4, &input, Part::Two), overlapping.to_string());
+        // the index finds the same overlapping assignments as comparing every two of them
+        let elves: Vec<(u32, u32)> = pairs.iter().flat_map(|&(left, right)| [left, right]).collect();
+        let mut every_pair = vec![];
+        for i in 0..elves.len() {
+            for j in i + 1..elves.len() {
+                if elves[i].0 <= elves[j].1 && elves[j].0 <= elves[i].1 {
+                    every_pair.push((i, j));
+                }
+            }
+        }
+        let assignments = CampCleanup::parse(&input).unwrap();
+        prop_assert_eq!(assignment_index(&assignments).overlapping_pairs(), every_pair);
+    }
+
+    #[test]
+    fn supply_stacks(
+        stacks in vec(vec(proptest::char::range('A', 'Z'), 0..6), 2..10),
+        crate_seed in proptest::char::range('A', 'Z'),
+        move_seeds in vec((0..100usize, 0..100usize, 0..100usize), 1..20),
+    ) {
+        let mut stacks = stacks;
+        stacks[0].push(crate_seed);
+        // pick moves that can be made: from a stack with crates, to another stack
+        let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+        let mut moves = vec![];
+        for (amount, from, to) in move_seeds {
+            let filled: Vec<usize> = (0..stacks.len()).filter(|&s| heights[s] > 0).collect();
+            let from = filled[from % filled.len()];
+            let to = (from + 1 + to % (stacks.len() - 1)) % stacks.len();
+            let amount = 1 + amount % heights[from];
+            heights[from] -= amount;
+            heights[to] += amount;
+            moves.push((amount, from, to));
+        }
+        let move_lines: Vec<String> = moves.iter().map(|(amount, from, to)| format!("move {} from {} to {}", amount, from + 1, to + 1)).collect();
+        let input = format!("{}\n\n{}\n", draw_stacks(&stacks), move_lines.join("\n"));
+
+        prop_assert_eq!(solve(5, &input, Part::One), top_crates(stacks.clone(), &moves, false));
+        prop_assert_eq!(solve(5, &input, Part::Two), top_crates(stacks, &moves, true));
+    }
+
+    #[test]
+    fn no_space_left(files in vec((vec(0..3usize, 0..4), 1..3_000_000u64), 1..20)) {
+        let mut root = Dir::default();
+        for (i, (path, size)) in files.iter().enumerate() {
+            let dir = path.iter().fold(&mut root, |dir, &d| dir.dirs.entry(["a", "b", "c"][d].to_string()).or_default());
+            dir.files.push((format!("f{}.txt", i), *size));
+        }
+        let mut session = vec!["$ cd /".to_string()];
+        root.session(&mut session);
+        let input = session.join("\n");
+
+        let mut sizes = vec![];
+        root.sizes(&mut sizes);
+        let small: u64 = sizes.iter().filter(|&&size| size <= 100_000).sum();
+        let needed = 30_000_000_u64.saturating_sub(70_000_000 - root.size());
+        let freed = sizes.iter().filter(|&&size| size >= needed).min().unwrap();
+        prop_assert_eq!(solve(7, &input, Part::One), small.to_string());
+        prop_assert_eq!(solve(7, &input, Part::Two), freed.to_string());
+    }
+
+    #[test]
+    fn treetop_tree_house(grid in (2..8usize, 2..8usize).prop_flat_map(|(w, h)| vec(vec(0..10u8, w), h))) {
+        let input = join(&grid.iter().map(|row| join(row, "")).collect::<Vec<_>>(), "\n");
+        let (h, w) = (grid.len() as i32, grid[0].len() as i32);
+        let at = |x: i32, y: i32| grid[y as usize][x as usize];
+        let inside = |x: i32, y: i32| (0..w).contains(&x) && (0..h).contains(&y);
+        // the trees seen looking from (x, y) in direction (dx, dy), and whether the view reaches the edge
+        let view = |x: i32, y: i32, (dx, dy): (i32, i32)| {
+            let (mut seen, mut cx, mut cy) = (0, x + dx, y + dy);
+            while inside(cx, cy) {
+                seen += 1;
+                if at(cx, cy) >= at(x, y) {
+                    return (seen, false);
+                }
+                (cx, cy) = (cx + dx, cy + dy);
+            }
+            (seen, true)
+        };
+        let dirs = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+        let trees: Vec<(i32, i32)> = (0..h).flat_map(|y| (0..w).map(move |x| (x, y))).collect();
+        let visible = trees.iter().filter(|&&(x, y)| dirs.iter().any(|&d| view(x, y, d).1)).count();
+        let scenic = trees.iter().map(|&(x, y)| dirs.iter().map(|&d| view(x, y, d).0).product::<usize>()).max().unwrap();
+        prop_assert_eq!(solve(8, &input, Part::One), visible.to_string());
+        prop_assert_eq!(solve(8, &input, Part::Two), scenic.to_string());
+    }
+
+    #[test]
+    fn rope_bridge(moves in vec((0..4usize, 1..10i32), 1..30)) {
+        let input = join(&moves.iter().map(|&(dir, steps)| format!("{} {}", ["U", "D", "L", "R"][dir], steps)).collect::<Vec<_>>(), "\n");
+        prop_assert_eq!(solve(9, &input, Part::One), rope_tail_positions(&moves, 2).to_string());
+        prop_assert_eq!(solve(9, &input, Part::Two), rope_tail_positions(&moves, 10).to_string());
+    }
+}
+
+proptest! {
+    // part 2 plays 10000 rounds, so fewer cases are run
+    #![proptest_config(ProptestConfig::with_cases(24))]
+
+    #[test]
+    fn monkey_in_the_middle(monkeys in monkeys()) {
+        let input = monkeys.iter().enumerate().map(|(id, m)| m.text(id)).collect::<Vec<_>>().join("\n\n");
+        let day = find_day(11).unwrap();
+        for (part, rounds, relief) in [(Part::One, 20, true), (Part::Two, 10_000, false)] {
+            // a part without an answer must say so, rather than panic or give a wrong one
+            match monkey_business(&monkeys, rounds, relief) {
+                Some(answer) => prop_assert_eq!(solve(11, &input, part), answer.to_string()),
+                None => prop_assert!(matches!(day.solve(&input, part), Err(SolveError::NoAnswer(_)))),
+            }
+        }
+    }
+}